@@ -0,0 +1,664 @@
+// the built-in `asyncio` module: a single threaded event loop whose clock is virtual, so that
+// `sleep()` and timeouts move the clock forward instead of waiting, and scripts run reproducibly
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use crate::interpreter::{exception_type, FrameExit, Interpreter};
+use crate::object::{future, CallableObject, PyObjectTrait, EventObject, ExceptionKind, ExceptionObject, FutureCallback, FutureObject, GeneratorObject, IntLongObject, Kwargs, ListObject, ModuleObject, NativeCoroutineObject, NoneObject, ObjectType, Poll, QueueObject, Real};
+use crate::utils::{DowncastTrait, PyObject, PyResult};
+
+/// a callback the event loop runs in its next iteration, refer to asyncio(events.Handle)
+enum Handle {
+    /// `Task.__step`, with the exception to throw into the coroutine
+    Step(PyObject, Option<PyObject>),
+    /// a callback of the future, which is done
+    Callback(FutureCallback, PyObject),
+    /// a timer is due, its future is resolved unless it is done already
+    Timer(PyObject)
+}
+
+/// a future resolved with `None` once the clock reaches `when`, the timer is dropped as soon as
+/// the future is done otherwise, refer to asyncio(events.TimerHandle)
+struct Timer {
+    when: f64,
+    future: PyObject
+}
+
+/// refer to asyncio(base_events.BaseEventLoop)
+struct EventLoop {
+    /// the virtual clock, in seconds
+    time: Cell<f64>,
+    ready: RefCell<VecDeque<Handle>>,
+    /// the pending timers, ordered by their time and then by the order they were scheduled in
+    timers: RefCell<Vec<Timer>>,
+    stopping: Cell<bool>,
+    current_task: RefCell<Option<PyObject>>,
+    /// the tasks created, for the tasks left pending to be cancelled when the loop finishes
+    tasks: RefCell<Vec<PyObject>>
+}
+
+thread_local! {
+    /// the loop `asyncio.run()` is running, refer to asyncio(events._get_running_loop)
+    static RUNNING_LOOP: RefCell<Option<Rc<EventLoop>>> = const { RefCell::new(None) };
+}
+
+fn running_loop() -> Option<Rc<EventLoop>> {
+    RUNNING_LOOP.with(|running| running.borrow().clone())
+}
+
+/// refer to asyncio(events.get_running_loop)
+fn get_running_loop() -> PyResult<Rc<EventLoop>> {
+    match running_loop() {
+        Some(event_loop) => Ok(event_loop),
+        None => ExceptionObject::raise(ExceptionKind::RuntimeError, "no running event loop")
+    }
+}
+
+/// schedule a callback of a future which is done, the callbacks of futures outliving their loop
+/// are never called
+pub fn call_soon_callback(callback: FutureCallback, fut: PyObject) {
+    if let Some(event_loop) = running_loop() {
+        event_loop.call_soon(Handle::Callback(callback, fut));
+    }
+}
+
+fn new_exception(kind: ExceptionKind, msg: String) -> PyObject {
+    ExceptionObject::new(kind, vec![crate::object::StringObject::new_from_string(msg)])
+}
+
+
+impl EventLoop {
+    fn new() -> Self {
+        Self {
+            time: Cell::new(0.0),
+            ready: RefCell::new(VecDeque::new()),
+            timers: RefCell::new(vec![]),
+            stopping: Cell::new(false),
+            current_task: RefCell::new(None),
+            tasks: RefCell::new(vec![])
+        }
+    }
+
+    fn call_soon(&self, handle: Handle) {
+        self.ready.borrow_mut().push_back(handle);
+    }
+
+    /// resolve `fut` after `delay` seconds of the virtual clock
+    fn call_later(&self, delay: f64, fut: PyObject) {
+        let when = self.time.get() + delay;
+        let mut timers = self.timers.borrow_mut();
+        let index = timers.partition_point(|timer| timer.when <= when);
+        timers.insert(index, Timer { when, future: fut });
+    }
+
+    /// refer to asyncio(base_events.BaseEventLoop.create_task)
+    fn create_task(&self, coro: PyObject, name: Option<String>) -> PyResult {
+        if !Interpreter::is_coroutine(&coro) {
+            return Err(new_exception(ExceptionKind::TypeError, format!("a coroutine was expected, got {}", coro.borrow().repr()?)));
+        }
+        let task = FutureObject::new_task(coro, name);
+        self.call_soon(Handle::Step(task.clone(), None));
+        let mut tasks = self.tasks.borrow_mut();
+        tasks.retain(|task| !future(task).done());
+        tasks.push(task.clone());
+        Ok(task)
+    }
+
+    /// refer to asyncio(tasks.ensure_future)
+    fn ensure_future(&self, obj: PyObject) -> PyResult {
+        if obj.downcast_refcell::<FutureObject>().is_some() {
+            return Ok(obj);
+        }
+        if Interpreter::is_coroutine(&obj) {
+            return self.create_task(obj, None);
+        }
+        ExceptionObject::raise(ExceptionKind::TypeError, "An asyncio.Future, a coroutine or an awaitable is required")
+    }
+
+    /// an iteration of the loop: when nothing is ready the clock jumps to the next timer, then
+    /// the due timers and the callbacks ready are run, refer to asyncio(base_events.BaseEventLoop._run_once)
+    fn run_once(&self) -> PyResult<()> {
+        self.timers.borrow_mut().retain(|timer| !future(&timer.future).done());
+        if self.ready.borrow().is_empty() && !self.stopping.get() {
+            let next = self.timers.borrow().first().map(|timer| timer.when);
+            match next {
+                Some(when) => self.time.set(self.time.get().max(when)),
+                // a real loop would wait forever
+                None => return ExceptionObject::raise(ExceptionKind::RuntimeError, "Event loop stopped before Future completed.")
+            }
+        }
+        loop {
+            let mut timers = self.timers.borrow_mut();
+            if timers.first().is_none_or(|timer| timer.when > self.time.get()) {
+                break;
+            }
+            let timer = timers.remove(0);
+            self.call_soon(Handle::Timer(timer.future));
+        }
+        let ntodo = self.ready.borrow().len();
+        for _ in 0..ntodo {
+            let handle = self.ready.borrow_mut().pop_front().expect("handles are only taken here");
+            if let Err(err) = self.run_handle(handle) {
+                // refer to asyncio(base_events.BaseEventLoop.default_exception_handler)
+                eprintln!("Exception in callback: {}", err.borrow().repr()?);
+            }
+        }
+        Ok(())
+    }
+
+    fn run_handle(&self, handle: Handle) -> PyResult<()> {
+        match handle {
+            Handle::Step(task, exc) => self.task_step(&task, exc),
+            Handle::Timer(fut) => release(&fut),
+            Handle::Callback(callback, fut) => match callback {
+                FutureCallback::Wakeup(task) => self.task_wakeup(&task, &fut),
+                FutureCallback::Release(waiter) => release(&waiter),
+                FutureCallback::Gather(outer) => future(&outer).child_done(&fut),
+                FutureCallback::StopLoop => {
+                    self.stopping.set(true);
+                    Ok(())
+                },
+                FutureCallback::Callable(callable) => Interpreter::call(&callable, vec![fut]).map(|_| ())
+            }
+        }
+    }
+
+    /// run the coroutine of the task up to its next suspension, refer to asyncio(tasks.Task.__step)
+    fn task_step(&self, task: &PyObject, exc: Option<PyObject>) -> PyResult<()> {
+        let (coro, exc) = {
+            let state = future(task);
+            if state.done() {
+                let exc = match &exc {
+                    Some(exc) => exc.borrow().repr()?,
+                    None => "None".to_string()
+                };
+                return Err(new_exception(ExceptionKind::InvalidStateError, format!("_step(): already done: {}, {}", state.repr()?, exc)));
+            }
+            let mut exc = exc;
+            if state.must_cancel() {
+                if !exc.as_ref().is_some_and(|exc| ExceptionObject::is_exception(exc, ExceptionKind::CancelledError)) {
+                    exc = Some(state.make_cancelled_error());
+                }
+                state.set_must_cancel(false);
+            }
+            state.set_waiter(None);
+            (state.coro(), exc)
+        };
+        let previous = self.current_task.replace(Some(task.clone()));
+        let result = match exc {
+            Some(exc) => GeneratorObject::throw_to(&coro, exc),
+            None => GeneratorObject::send_to(&coro, NoneObject::new())
+        };
+        *self.current_task.borrow_mut() = previous;
+        let state = future(task);
+        let result = match result {
+            Ok(FrameExit::Yield(result)) => result,
+            Ok(FrameExit::Return(value)) => {
+                if state.must_cancel() {
+                    state.set_must_cancel(false);
+                    state.set_cancelled(None);
+                    return Ok(());
+                }
+                return state.set_result(value);
+            },
+            Ok(FrameExit::Generator) => unreachable!("a resumed frame does not return a generator"),
+            Err(err) if ExceptionObject::is_exception(&err, ExceptionKind::CancelledError) => {
+                state.set_cancelled(Some(err));
+                return Ok(());
+            },
+            Err(err) => return state.set_exception(err)
+        };
+        if result.downcast_refcell::<FutureObject>().is_some() {
+            if Rc::ptr_eq(&result, task) {
+                let exc = new_exception(ExceptionKind::RuntimeError, format!("Task cannot await on itself: {}", state.repr()?));
+                self.call_soon(Handle::Step(task.clone(), Some(exc)));
+                return Ok(());
+            }
+            future(&result).add_callback(FutureCallback::Wakeup(task.clone()));
+            state.set_waiter(Some(result.clone()));
+            if state.must_cancel() && FutureObject::cancel(&result, state.cancel_message()) {
+                state.set_must_cancel(false);
+            }
+            return Ok(());
+        }
+        let exc = if result.borrow().object_type() == ObjectType::NONE {
+            // a bare yield gives way to the other tasks
+            None
+        } else if result.downcast_refcell::<GeneratorObject>().is_some() {
+            Some(new_exception(
+                ExceptionKind::RuntimeError,
+                format!("yield was used instead of yield from for generator in task {} with {}", state.repr()?, result.borrow().repr()?)
+            ))
+        } else {
+            Some(new_exception(ExceptionKind::RuntimeError, format!("Task got bad yield: {}", result.borrow().repr()?)))
+        };
+        self.call_soon(Handle::Step(task.clone(), exc));
+        Ok(())
+    }
+
+    /// the future the task waited for is done, refer to asyncio(tasks.Task.__wakeup)
+    fn task_wakeup(&self, task: &PyObject, fut: &PyObject) -> PyResult<()> {
+        let result = future(fut).result();
+        match result {
+            Ok(_) => self.task_step(task, None),
+            Err(exc) => self.task_step(task, Some(exc))
+        }
+    }
+
+    /// refer to asyncio(base_events.BaseEventLoop.run_until_complete)
+    fn run_until_complete(&self, fut: &PyObject) -> PyResult {
+        future(fut).add_callback(FutureCallback::StopLoop);
+        let result = loop {
+            if let Err(err) = self.run_once() {
+                break Err(err);
+            }
+            if self.stopping.get() {
+                break Ok(());
+            }
+        };
+        self.stopping.set(false);
+        result?;
+        let fut = future(fut);
+        if !fut.done() {
+            return ExceptionObject::raise(ExceptionKind::RuntimeError, "Event loop stopped before Future completed.");
+        }
+        fut.result()
+    }
+
+    /// refer to asyncio(runners._cancel_all_tasks)
+    fn cancel_all_tasks(&self) -> PyResult<()> {
+        let to_cancel: Vec<PyObject> = self.tasks.borrow().iter().filter(|task| !future(task).done()).cloned().collect();
+        if to_cancel.is_empty() {
+            return Ok(());
+        }
+        for task in &to_cancel {
+            FutureObject::cancel(task, None);
+        }
+        let outer = self.gather(to_cancel, true)?;
+        self.run_until_complete(&outer).map(|_| ())
+    }
+
+    /// refer to asyncio(tasks.gather)
+    fn gather(&self, aws: Vec<PyObject>, return_exceptions: bool) -> PyResult {
+        if aws.is_empty() {
+            let outer = FutureObject::new_future();
+            future(&outer).set_result(ListObject::new_from_values(vec![]))?;
+            return Ok(outer);
+        }
+        let outer = FutureObject::new_gather(return_exceptions);
+        let mut futures: Vec<(PyObject, PyObject)> = vec![];
+        for arg in aws {
+            if let Some((_, fut)) = futures.iter().find(|(other, _)| Rc::ptr_eq(other, &arg)) {
+                future(&outer).add_child(fut.clone(), false);
+                continue;
+            }
+            let fut = self.ensure_future(arg.clone())?;
+            future(&fut).add_callback(FutureCallback::Gather(outer.clone()));
+            future(&outer).add_child(fut.clone(), true);
+            futures.push((arg, fut));
+        }
+        Ok(outer)
+    }
+}
+
+/// resolve a future with `None` unless it is done, refer to asyncio(tasks._release_waiter)
+fn release(fut: &PyObject) -> PyResult<()> {
+    let fut = future(fut);
+    if fut.done() {
+        return Ok(());
+    }
+    fut.set_result(NoneObject::new())
+}
+
+/// bind the arguments of a function of the module like those of a Python function, `params`
+/// naming its parameters of which the first `required` ones have no default value
+fn bind_args(name: &str, params: &[&str], required: usize, args: Vec<PyObject>, kwargs: Kwargs) -> PyResult<Vec<Option<PyObject>>> {
+    if args.len() > params.len() {
+        let expected = if required == params.len() { required.to_string() } else { format!("from {} to {}", required, params.len()) };
+        let given = if args.len() == 1 { "was" } else { "were" };
+        let plural = if params.len() == 1 { "" } else { "s" };
+        return Err(new_exception(
+            ExceptionKind::TypeError,
+            format!("{}() takes {} positional argument{} but {} {} given", name, expected, plural, args.len(), given)
+        ));
+    }
+    let mut bound: Vec<Option<PyObject>> = params.iter().map(|_| None).collect();
+    for (i, arg) in args.into_iter().enumerate() {
+        bound[i] = Some(arg);
+    }
+    for (key, value) in kwargs {
+        let index = match params.iter().position(|param| *param == key) {
+            Some(index) => index,
+            None => return Err(new_exception(ExceptionKind::TypeError, format!("{}() got an unexpected keyword argument '{}'", name, key)))
+        };
+        if bound[index].is_some() {
+            return Err(new_exception(ExceptionKind::TypeError, format!("{}() got multiple values for argument '{}'", name, key)));
+        }
+        bound[index] = Some(value);
+    }
+    if let Some(index) = (0..required).find(|index| bound[*index].is_none()) {
+        return Err(new_exception(
+            ExceptionKind::TypeError,
+            format!("{}() missing 1 required positional argument: '{}'", name, params[index])
+        ));
+    }
+    Ok(bound)
+}
+
+/// a number of seconds, `None` standing for no timeout
+fn seconds(obj: &PyObject) -> PyResult<Option<f64>> {
+    if obj.borrow().object_type() == ObjectType::NONE {
+        return Ok(None);
+    }
+    match Real::extract(obj) {
+        Some(value) => Ok(Some(value.as_f64())),
+        None => Err(new_exception(
+            ExceptionKind::TypeError,
+            format!("'<=' not supported between instances of '{}' and 'int'", obj.borrow().type_name())
+        ))
+    }
+}
+
+/// `run(main)`: run the coroutine in a new event loop, the tasks it leaves pending are cancelled,
+/// refer to asyncio(runners.run)
+fn asyncio_run(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    let args = bind_args("run", &["main", "debug"], 1, args, kwargs)?;
+    let main = args[0].clone().expect("main is required");
+    if running_loop().is_some() {
+        return ExceptionObject::raise(ExceptionKind::RuntimeError, "asyncio.run() cannot be called from a running event loop");
+    }
+    if !Interpreter::is_coroutine(&main) {
+        return Err(new_exception(ExceptionKind::ValueError, format!("a coroutine was expected, got {}", main.borrow().repr()?)));
+    }
+    let event_loop = Rc::new(EventLoop::new());
+    RUNNING_LOOP.with(|running| *running.borrow_mut() = Some(event_loop.clone()));
+    let result = event_loop.create_task(main, None).and_then(|task| event_loop.run_until_complete(&task));
+    let shutdown = event_loop.cancel_all_tasks();
+    RUNNING_LOOP.with(|running| running.borrow_mut().take());
+    event_loop.ready.borrow_mut().clear();
+    event_loop.timers.borrow_mut().clear();
+    event_loop.tasks.borrow_mut().clear();
+    let result = result?;
+    shutdown?;
+    Ok(result)
+}
+
+/// `sleep(delay, result=None)`, a delay not above zero only gives way to the other tasks once,
+/// refer to asyncio(tasks.sleep)
+fn asyncio_sleep(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    let args = bind_args("sleep", &["delay", "result"], 1, args, kwargs)?;
+    let delay = args[0].clone().expect("delay is required");
+    let result = args[1].clone().unwrap_or_else(NoneObject::new);
+    let mut waiting: Option<Option<PyObject>> = None;
+    Ok(NativeCoroutineObject::new("sleep", Box::new(move |throw| {
+        if let Some(exc) = throw {
+            if let Some(Some(fut)) = waiting.take() {
+                FutureObject::cancel(&fut, None);
+            }
+            return Err(exc);
+        }
+        if waiting.is_some() {
+            return Ok(Poll::Ready(result.clone()));
+        }
+        let delay = seconds(&delay)?.unwrap_or(0.0);
+        if delay <= 0.0 {
+            waiting = Some(None);
+            return Ok(Poll::Pending(NoneObject::new()));
+        }
+        let event_loop = get_running_loop()?;
+        let fut = FutureObject::new_future();
+        event_loop.call_later(delay, fut.clone());
+        waiting = Some(Some(fut.clone()));
+        Ok(Poll::Pending(fut))
+    })))
+}
+
+/// `create_task(coro, *, name=None)`, refer to asyncio(tasks.create_task)
+fn asyncio_create_task(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    if args.len() > 1 {
+        return Err(new_exception(
+            ExceptionKind::TypeError,
+            format!("create_task() takes 1 positional argument but {} were given", args.len())
+        ));
+    }
+    let args = bind_args("create_task", &["coro", "name"], 1, args, kwargs)?;
+    let event_loop = get_running_loop()?;
+    let name = match args[1].as_ref().filter(|name| name.borrow().object_type() != ObjectType::NONE) {
+        Some(name) => Some(name.borrow().str()?),
+        None => None
+    };
+    event_loop.create_task(args[0].clone().expect("coro is required"), name)
+}
+
+/// `gather(*aws, return_exceptions=False)`, refer to asyncio(tasks.gather)
+fn asyncio_gather(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    let mut return_exceptions = false;
+    for (key, value) in kwargs {
+        match key.as_str() {
+            "return_exceptions" => return_exceptions = Interpreter::is_true(&value)?,
+            _ => return Err(new_exception(ExceptionKind::TypeError, format!("gather() got an unexpected keyword argument '{}'", key)))
+        }
+    }
+    if args.is_empty() {
+        let outer = FutureObject::new_future();
+        future(&outer).set_result(ListObject::new_from_values(vec![]))?;
+        return Ok(outer);
+    }
+    get_running_loop()?.gather(args, return_exceptions)
+}
+
+/// `ensure_future(obj)`, refer to asyncio(tasks.ensure_future)
+fn asyncio_ensure_future(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    let args = bind_args("ensure_future", &["coro_or_future"], 1, args, kwargs)?;
+    let obj = args[0].clone().expect("coro_or_future is required");
+    if obj.downcast_refcell::<FutureObject>().is_some() {
+        return Ok(obj);
+    }
+    get_running_loop()?.ensure_future(obj)
+}
+
+/// `current_task()`, refer to asyncio(tasks.current_task)
+fn asyncio_current_task(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    bind_args("current_task", &["loop"], 0, args, kwargs)?;
+    let event_loop = get_running_loop()?;
+    let task = event_loop.current_task.borrow().clone();
+    Ok(task.unwrap_or_else(NoneObject::new))
+}
+
+/// `all_tasks()`: the tasks of the running loop which are not done, refer to asyncio(tasks.all_tasks)
+fn asyncio_all_tasks(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    bind_args("all_tasks", &["loop"], 0, args, kwargs)?;
+    let event_loop = get_running_loop()?;
+    let tasks: Vec<PyObject> = event_loop.tasks.borrow().iter().filter(|task| !future(task).done()).cloned().collect();
+    crate::object::SetObject::new_from_iterable(Some(&ListObject::new_from_values(tasks)), false)
+}
+
+/// the state of `wait_for()` between its steps
+enum WaitFor {
+    Start,
+    /// no timeout: the awaitable is awaited in place
+    Awaiting(PyObject),
+    /// waiting for the first of the future being done and the timeout
+    Waiting { fut: PyObject, waiter: PyObject },
+    /// the future has been cancelled, the waiter is released once it is done, then the
+    /// exception is raised or, without one, the future's CancelledError becomes TimeoutError
+    Cancelling { fut: PyObject, waiter: PyObject, exc: Option<PyObject> }
+}
+
+/// `wait_for(aw, timeout)`, the awaitable is cancelled once the timeout expires,
+/// refer to asyncio(tasks.wait_for) of Python 3.11
+fn asyncio_wait_for(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    let args = bind_args("wait_for", &["fut", "timeout"], 2, args, kwargs)?;
+    let aw = args[0].clone().expect("fut is required");
+    let timeout = args[1].clone().expect("timeout is required");
+    let mut state = WaitFor::Start;
+    let mut timeout_waiter: Option<PyObject> = None;
+    Ok(NativeCoroutineObject::new("wait_for", Box::new(move |throw| {
+        let (next, poll) = wait_for_step(std::mem::replace(&mut state, WaitFor::Start), &aw, &timeout, &mut timeout_waiter, throw);
+        state = next;
+        if !matches!(poll, Ok(Poll::Pending(_))) {
+            // refer to `timeout_handle.cancel()`
+            if let Some(waiter) = timeout_waiter.take() {
+                FutureObject::cancel(&waiter, None);
+            }
+        }
+        poll
+    })))
+}
+
+fn wait_for_step(state: WaitFor, aw: &PyObject, timeout: &PyObject, timeout_waiter: &mut Option<PyObject>, throw: Option<PyObject>) -> (WaitFor, PyResult<Poll>) {
+    /// cancel the future, then wait for it to be done, refer to asyncio(tasks._cancel_and_wait)
+    fn cancel_and_wait(fut: PyObject, exc: Option<PyObject>) -> (WaitFor, PyResult<Poll>) {
+        let waiter = FutureObject::new_future();
+        future(&fut).add_callback(FutureCallback::Release(waiter.clone()));
+        FutureObject::cancel(&fut, None);
+        (WaitFor::Cancelling { fut, waiter: waiter.clone(), exc }, Ok(Poll::Pending(waiter)))
+    }
+    fn finish(fut: &PyObject) -> (WaitFor, PyResult<Poll>) {
+        (WaitFor::Start, future(fut).result().map(Poll::Ready))
+    }
+    match state {
+        WaitFor::Start => {
+            if let Some(exc) = throw {
+                return (WaitFor::Start, Err(exc));
+            }
+            let start = || -> PyResult<(WaitFor, PyResult<Poll>)> {
+                let event_loop = get_running_loop()?;
+                let timeout = match seconds(timeout)? {
+                    Some(timeout) => timeout,
+                    None => {
+                        let iter = match Interpreter::awaitable_iter(aw)? {
+                            Some(iter) => iter,
+                            None => return ExceptionObject::raise(
+                                ExceptionKind::TypeError,
+                                &format!("object {} can't be used in 'await' expression", aw.borrow().type_name())
+                            )
+                        };
+                        return Ok(wait_for_step(WaitFor::Awaiting(iter), aw, &NoneObject::new(), &mut None, None));
+                    }
+                };
+                let fut = event_loop.ensure_future(aw.clone())?;
+                if timeout <= 0.0 {
+                    if future(&fut).done() {
+                        return Ok(finish(&fut));
+                    }
+                    return Ok(cancel_and_wait(fut, None));
+                }
+                let waiter = FutureObject::new_future();
+                event_loop.call_later(timeout, waiter.clone());
+                future(&fut).add_callback(FutureCallback::Release(waiter.clone()));
+                Ok((WaitFor::Waiting { fut, waiter: waiter.clone() }, Ok(Poll::Pending(waiter))))
+            };
+            match start() {
+                Ok((state, poll)) => {
+                    if let WaitFor::Waiting { waiter, .. } = &state {
+                        *timeout_waiter = Some(waiter.clone());
+                    }
+                    (state, poll)
+                },
+                Err(err) => (WaitFor::Start, Err(err))
+            }
+        },
+        WaitFor::Awaiting(iter) => {
+            let exit = match throw {
+                Some(exc) => GeneratorObject::throw_to(&iter, exc),
+                None => GeneratorObject::send_to(&iter, NoneObject::new())
+            };
+            match exit {
+                Ok(FrameExit::Yield(value)) => (WaitFor::Awaiting(iter), Ok(Poll::Pending(value))),
+                Ok(FrameExit::Return(value)) => (WaitFor::Start, Ok(Poll::Ready(value))),
+                Ok(FrameExit::Generator) => unreachable!("a resumed frame does not return a generator"),
+                Err(err) => (WaitFor::Start, Err(err))
+            }
+        },
+        WaitFor::Waiting { fut, waiter } => match throw {
+            Some(exc) if ExceptionObject::is_exception(&exc, ExceptionKind::CancelledError) => {
+                if future(&fut).done() {
+                    return finish(&fut);
+                }
+                cancel_and_wait(fut, Some(exc))
+            },
+            Some(exc) => (WaitFor::Start, Err(exc)),
+            None => {
+                drop(waiter);
+                if future(&fut).done() {
+                    return finish(&fut);
+                }
+                cancel_and_wait(fut, None)
+            }
+        },
+        WaitFor::Cancelling { fut, waiter, exc } => {
+            drop(waiter);
+            if let Some(exc) = throw.or(exc) {
+                return (WaitFor::Start, Err(exc));
+            }
+            match future(&fut).result() {
+                Err(err) if ExceptionObject::is_exception(&err, ExceptionKind::CancelledError) => {
+                    (WaitFor::Start, Err(ExceptionObject::new(ExceptionKind::TimeoutError, vec![])))
+                },
+                result => (WaitFor::Start, result.map(Poll::Ready))
+            }
+        }
+    }
+}
+
+fn asyncio_queue(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    let args = bind_args("Queue", &["maxsize"], 0, args, kwargs)?;
+    let maxsize = match &args[0] {
+        Some(maxsize) => match IntLongObject::extract(maxsize) {
+            Some(maxsize) => maxsize,
+            None => return Err(new_exception(
+                ExceptionKind::TypeError,
+                format!("'{}' object cannot be interpreted as an integer", maxsize.borrow().type_name())
+            ))
+        },
+        None => 0
+    };
+    Ok(QueueObject::new(maxsize))
+}
+
+fn asyncio_event(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    bind_args("Event", &[], 0, args, kwargs)?;
+    Ok(EventObject::new())
+}
+
+type ModuleFunction = fn(Vec<PyObject>, Kwargs) -> PyResult;
+
+/// the `asyncio` module
+pub fn module() -> PyObject {
+    let mut dict: HashMap<String, PyObject> = HashMap::new();
+    let functions: [(&str, ModuleFunction); 11] = [
+        ("run", asyncio_run),
+        ("sleep", asyncio_sleep),
+        ("create_task", asyncio_create_task),
+        ("gather", asyncio_gather),
+        ("ensure_future", asyncio_ensure_future),
+        ("wait_for", asyncio_wait_for),
+        ("current_task", asyncio_current_task),
+        ("all_tasks", asyncio_all_tasks),
+        ("Queue", asyncio_queue),
+        ("Event", asyncio_event),
+        ("Future", asyncio_future)
+    ];
+    for (name, function) in functions {
+        dict.insert(name.to_string(), CallableObject::new_native_kw(name, Box::new(function)));
+    }
+    let exceptions = [
+        ExceptionKind::CancelledError,
+        ExceptionKind::InvalidStateError,
+        ExceptionKind::QueueEmpty,
+        ExceptionKind::QueueFull,
+        ExceptionKind::TimeoutError
+    ];
+    for kind in exceptions {
+        dict.insert(kind.name().to_string(), exception_type(kind));
+    }
+    ModuleObject::new("asyncio", dict)
+}
+
+/// `Future()`, which belongs to the running loop
+fn asyncio_future(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    bind_args("Future", &[], 0, args, kwargs)?;
+    get_running_loop()?;
+    Ok(FutureObject::new_future())
+}
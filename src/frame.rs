@@ -2,62 +2,91 @@ use std::cell::{Ref, RefCell};
 use std::collections::HashMap;
 use std::rc::Rc;
 use crate::InputStream;
-use crate::object::{CallableObject, CodeObject};
+use crate::object::{CallableObject, CellObject, CodeObject, DictObject, ExceptionHandler, ExceptionKind, ExceptionObject, IntLongObject, Kwargs, StringObject, TupleObject, TypeObject, CO_FAST_CELL, CO_FAST_FREE, CO_VARARGS, CO_VARKEYWORDS};
 use crate::utils::ByteCode;
-use crate::utils::PyObject;
+use crate::utils::{DowncastTrait, PyObject, PyResult};
 
 
 pub struct Frame {
     stack: Vec<PyObject>,
     code: InputStream,
-    locals: HashMap<String, PyObject>,
-    fast_locals: HashMap<u8, PyObject>,
-    globals: HashMap<String, PyObject>,
+    /// the dict of the names of a module or class body, the globals in the case of a module
+    locals: PyObject,
+    fast_locals: HashMap<u32, PyObject>,
+    /// the names of the fast locals followed by those of the free variables
+    local_names: Vec<String>,
+    local_kinds: Vec<u8>,
+    num_args: usize,
+    /// the cells the free variables are copied from by COPY_FREE_VARS
+    closure: Vec<PyObject>,
+    globals: PyObject,
     names: Vec<PyObject>,
     consts: Vec<PyObject>,
-    parent: Option<Box<Frame>>
+    /// the keyword names set by KW_NAMES for the next CALL
+    kw_names: Option<PyObject>,
+    /// the name and qualified name of the code, a generator running the frame takes them
+    name: String,
+    qualname: String,
+    flags: u32,
+    exception_table: Vec<ExceptionHandler>,
+    /// the offset of the instruction being run, or the last one run by a suspended frame
+    lasti: usize
 }
 
 impl Frame {
+    /// the frame of the `__main__` module
     pub fn new_from_code(code: Rc<RefCell<CodeObject>>) -> Self {
         let code = code.borrow();
+        let globals = new_namespace();
+        set_name(&globals, "__name__", StringObject::new_from_str("__main__"));
         Self {
             stack: Vec::with_capacity(code.num_stack() as usize),
             code: InputStream::new(code.code()),
-            locals: Default::default(),
-            globals: Default::default(),
+            locals: globals.clone(),
+            globals,
             fast_locals: Default::default(),
+            local_names: code.local_names(),
+            local_kinds: code.local_kinds(),
+            num_args: 0,
+            closure: vec![],
             names: code.names(),
             consts: code.consts(),
-            parent: None
+            kw_names: None,
+            name: code.name(),
+            qualname: code.qualified_name(),
+            flags: code.flags(),
+            exception_table: code.exception_table(),
+            lasti: 0
         }
     }
 
-    pub fn new_from_callable(callable: Ref<CallableObject>, args: Vec<PyObject>) -> Self {
-        let mut fast_locals: HashMap<u8, PyObject> = HashMap::new();
+    pub fn new_from_callable(callable: Ref<CallableObject>, args: Vec<PyObject>, kwargs: Kwargs) -> PyResult<Self> {
         let code = callable.code();
-        let code = code.borrow();
-        let num_args = code.num_args();
-        let default_args = callable.defaults().len();
-        let mut j = num_args;
-        for i in (0..default_args).rev() {
-            j -= 1;
-            fast_locals.insert(j as u8, callable.defaults().get(i).unwrap().clone());
-        }
-        for i in 0..args.len() {
-            fast_locals.insert(i as u8, args.get(i).unwrap().clone());
-        }
+        let code = code.downcast_refcell::<CodeObject>().expect("invalid code object");
+        let fast_locals = bind_arguments(&callable, &code, args, kwargs)?;
+        let num_args = code.num_args() as usize;
+        let name = code.name();
+        let local_names = code.local_names();
 
-        Self {
+        Ok(Self {
             stack: vec![],
             code: InputStream::new(code.code()),
-            locals: Default::default(),
-            globals: Default::default(),
+            locals: new_namespace(),
+            globals: callable.globals(),
             fast_locals,
+            local_names,
+            local_kinds: code.local_kinds(),
+            num_args,
+            closure: callable.closure().clone(),
             names: code.names(),
             consts: code.consts(),
-            parent: None,
-        }
+            kw_names: None,
+            name,
+            qualname: code.qualified_name(),
+            flags: code.flags(),
+            exception_table: code.exception_table(),
+            lasti: 0
+        })
     }
 
     pub fn finish(&self) -> bool {
@@ -79,6 +108,44 @@ impl Frame {
         }
     }
 
+    /// the code byte `offset` bytes after the next one to run, without moving to it
+    pub fn peek_code(&self, offset: i64) -> Option<u8> {
+        self.code.peek(offset)
+    }
+
+    /// mark the start of the next instruction, which is where the exceptions it raises come from
+    pub fn begin_instruction(&mut self) {
+        self.lasti = self.code.position();
+    }
+
+    /// the handler of the exceptions raised by the current instruction
+    pub fn exception_handler(&self) -> Option<ExceptionHandler> {
+        self.exception_table.iter().find(|handler| handler.start <= self.lasti && self.lasti < handler.end).copied()
+    }
+
+    /// go on at `handler` with `exc` on top of the stack, refer to CPython(exception_unwind)
+    pub fn unwind(&mut self, handler: &ExceptionHandler, exc: PyObject) {
+        self.stack.truncate(handler.depth);
+        if handler.lasti {
+            self.stack.push(IntLongObject::new_from_i64((self.lasti / 2) as i64));
+        }
+        self.stack.push(exc);
+        let position = self.code.position();
+        self.code.jump_offset(handler.target as i64 - position as i64);
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn qualname(&self) -> &str {
+        &self.qualname
+    }
+
     #[allow(dead_code)]
     pub fn backward_code(&mut self, n: usize) {
         self.code.unread(n);
@@ -91,17 +158,27 @@ impl Frame {
         self.code.forward(n);
     }
 
-    pub fn parent(&mut self) -> Option<Box<Frame>> {
-        self.parent.take()
-    }
-    pub fn set_parent(&mut self, parent: Option<Box<Frame>>) {
-        self.parent = parent;
-    }
 
     pub fn pop(&mut self) -> PyObject {
         self.stack.pop().take().unwrap()
     }
 
+    /// pop the top `n` items, in the order they were pushed
+    pub fn pop_n(&mut self, n: usize) -> Vec<PyObject> {
+        self.stack.split_off(self.stack.len() - n)
+    }
+
+    /// the item `n` places down the stack, `peek(1)` is the top
+    pub fn peek(&self, n: usize) -> PyObject {
+        self.stack[self.stack.len() - n].clone()
+    }
+
+    /// exchange the top with the item `n` places down the stack
+    pub fn swap(&mut self, n: usize) {
+        let len = self.stack.len();
+        self.stack.swap(len - 1, len - n);
+    }
+
     pub fn top(&mut self) -> PyObject{
         self.stack.last().unwrap().clone()
     }
@@ -112,32 +189,305 @@ impl Frame {
     pub fn get_const(&self, index: usize) -> PyObject {
         self.consts.get(index).unwrap().clone()
     }
-    pub fn get_name(&self, index: usize) -> PyObject {
-        self.names.get(index).unwrap().clone()
+    pub fn get_name(&self, index: usize) -> String {
+        StringObject::extract(self.names.get(index).unwrap()).expect("names should be str")
+    }
+
+    pub fn set_kw_names(&mut self, names: PyObject) {
+        self.kw_names = Some(names);
+    }
+
+    pub fn take_kw_names(&mut self) -> Option<PyObject> {
+        self.kw_names.take()
     }
 
-    pub fn load_fast(&self, key: u8) -> PyObject {
+    pub fn load_fast(&self, key: u32) -> PyObject {
         self.fast_locals.get(&key).unwrap().clone()
     }
 
-    pub fn set_local(&mut self, key: PyObject, value: PyObject) {
-        self.locals.insert(key.borrow().hash_key(), value);
+    pub fn store_fast(&mut self, key: u32, value: PyObject) {
+        self.fast_locals.insert(key, value);
     }
 
-    pub fn look_up_name(&self, name: PyObject) -> Option<PyObject> {
-        if let Some(res) = self.locals.get(&name.borrow().hash_key()) {
-            return Some(res.clone());
+    pub fn delete_fast(&mut self, key: u32) -> PyResult<()> {
+        match self.fast_locals.remove(&key) {
+            Some(_) => Ok(()),
+            None => ExceptionObject::raise(
+                ExceptionKind::UnboundLocalError,
+                &format!("cannot access local variable '{}' where it is not associated with a value", self.local_names[key as usize])
+            )
         }
-        if let Some(res) = self.globals.get(&name.borrow().hash_key()) {
-            return Some(res.clone());
+    }
+
+    /// wrap the local `index` into a cell, keeping the value of an argument
+    pub fn make_cell(&mut self, index: u32) {
+        let value = self.fast_locals.remove(&index);
+        self.fast_locals.insert(index, CellObject::new(value));
+    }
+
+    /// the cell of the cell or free variable `index`
+    pub fn load_closure(&self, index: u32) -> PyObject {
+        self.fast_locals.get(&index).expect("cell should be made").clone()
+    }
+
+    /// the value in the cell of the variable `index`, refer to CPython(format_exc_unbound)
+    pub fn load_deref(&self, index: u32) -> PyResult {
+        let value = self.load_closure(index).downcast_refcell::<CellObject>().expect("invalid cell object").get();
+        match value {
+            Some(value) => Ok(value),
+            None => self.unbound_deref(index)
         }
-        None
     }
 
-    pub fn look_up_global(&self, name: PyObject) -> Option<PyObject> {
-        if let Some(res) = self.globals.get(&name.borrow().hash_key()) {
-            return Some(res.clone());
+    pub fn store_deref(&mut self, index: u32, value: Option<PyObject>) {
+        self.load_closure(index).downcast_refcell_mut::<CellObject>().expect("invalid cell object").set(value);
+    }
+
+    pub fn delete_deref(&mut self, index: u32) -> PyResult<()> {
+        self.load_deref(index)?;
+        self.store_deref(index, None);
+        Ok(())
+    }
+
+    fn unbound_deref<T>(&self, index: u32) -> PyResult<T> {
+        let name = &self.local_names[index as usize];
+        if self.local_kinds.get(index as usize).is_some_and(|kind| kind & CO_FAST_FREE != 0) {
+            return ExceptionObject::raise(
+                ExceptionKind::NameError,
+                &format!("cannot access free variable '{}' where it is not associated with a value in enclosing scope", name)
+            );
         }
+        ExceptionObject::raise(
+            ExceptionKind::UnboundLocalError,
+            &format!("cannot access local variable '{}' where it is not associated with a value", name)
+        )
+    }
+
+    /// a class body looks its free variables up in the class namespace first
+    pub fn load_class_deref(&self, index: u32) -> PyResult {
+        match get_name(&self.locals, &self.local_names[index as usize]) {
+            Some(value) => Ok(value),
+            None => self.load_deref(index)
+        }
+    }
+
+    /// the free variables are the last locals
+    pub fn copy_free_vars(&mut self, n: usize) {
+        let offset = self.local_names.len() - n;
+        for (i, cell) in self.closure[..n].iter().enumerate() {
+            self.fast_locals.insert((offset + i) as u32, cell.clone());
+        }
+    }
+
+    /// the class and the first argument which zero argument super() stands for, refer to CPython(super_init_without_args)
+    pub fn super_args(&self) -> PyResult<Vec<PyObject>> {
+        let first = match self.fast_locals.get(&0) {
+            Some(first) if self.num_args > 0 => first.clone(),
+            _ => return ExceptionObject::raise(ExceptionKind::RuntimeError, "super(): no arguments")
+        };
+        // the first argument is in a cell when a nested function uses it
+        let first = if self.local_kinds.first().is_some_and(|kind| kind & CO_FAST_CELL != 0) {
+            match first.downcast_refcell::<CellObject>().and_then(|cell| cell.get()) {
+                Some(first) => first,
+                None => return ExceptionObject::raise(ExceptionKind::RuntimeError, "super(): arg[0] deleted")
+            }
+        } else {
+            first
+        };
+        let index = self.local_names.iter().zip(&self.local_kinds)
+            .position(|(name, kind)| name == "__class__" && kind & CO_FAST_FREE != 0);
+        let cell = match index.and_then(|index| self.fast_locals.get(&(index as u32))) {
+            Some(cell) => cell.clone(),
+            None => return ExceptionObject::raise(ExceptionKind::RuntimeError, "super(): __class__ cell not found")
+        };
+        let class = match cell.downcast_refcell::<CellObject>().and_then(|cell| cell.get()) {
+            Some(class) => class,
+            None => return ExceptionObject::raise(ExceptionKind::RuntimeError, "super(): empty __class__ cell")
+        };
+        if class.downcast_refcell::<TypeObject>().is_none() {
+            return ExceptionObject::raise(
+                ExceptionKind::RuntimeError,
+                &format!("super(): __class__ is not a type ({})", class.borrow().type_name())
+            );
+        }
+        Ok(vec![class, first])
+    }
+
+    pub fn globals(&self) -> PyObject {
+        self.globals.clone()
+    }
+
+    /// run the frame as a class body, which stores its names into `locals`
+    pub fn set_locals(&mut self, locals: PyObject) {
+        self.locals = locals;
+    }
+
+    pub fn set_local(&mut self, name: String, value: PyObject) {
+        set_name(&self.locals, &name, value);
+    }
+
+    /// unbind a name of the module or class body, giving `None` when it is not bound
+    pub fn delete_local(&mut self, name: &str) -> Option<PyObject> {
+        remove_name(&self.locals, name)
+    }
+
+    pub fn look_up_name(&self, name: &str) -> Option<PyObject> {
+        get_name(&self.locals, name).or_else(|| self.look_up_global(name))
+    }
+
+    pub fn look_up_global(&self, name: &str) -> Option<PyObject> {
+        get_name(&self.globals, name)
+    }
+}
+
+/// an empty dict of names
+/// `'a', 'b', and 'c'`, refer to CPython(format_missing)
+fn format_names(names: &[String]) -> String {
+    let names: Vec<String> = names.iter().map(|name| format!("'{}'", name)).collect();
+    match names.len() {
+        1 => names[0].clone(),
+        2 => format!("{} and {}", names[0], names[1]),
+        n => format!("{}, and {}", names[..n - 1].join(", "), names[n - 1])
+    }
+}
+
+/// bind the arguments of a call to the parameters of the function, the extra positional and
+/// keyword arguments go to the `*args` tuple and the `**kwargs` dict when the function has them,
+/// refer to CPython(initialize_locals)
+fn bind_arguments(callable: &CallableObject, code: &CodeObject, args: Vec<PyObject>, kwargs: Kwargs) -> PyResult<HashMap<u32, PyObject>> {
+    let mut fast_locals: HashMap<u32, PyObject> = HashMap::new();
+    let name = code.name();
+    let local_names = code.local_names();
+    let num_args = code.num_args() as usize;
+    let num_pos_only = code.num_pos_only_args() as usize;
+    let total_args = num_args + code.num_kw_only_args() as usize;
+    let flags = code.flags();
+    let var_keywords = if flags & CO_VARKEYWORDS != 0 {
+        let index = total_args + usize::from(flags & CO_VARARGS != 0);
+        let dict = DictObject::new_from_items(vec![])?;
+        fast_locals.insert(index as u32, dict.clone());
+        Some(dict)
+    } else {
         None
+    };
+    let given = args.len();
+    let mut args = args.into_iter();
+    for (i, value) in args.by_ref().take(num_args).enumerate() {
+        fast_locals.insert(i as u32, value);
     }
+    if flags & CO_VARARGS != 0 {
+        fast_locals.insert(total_args as u32, TupleObject::new_from_values(args.collect()));
+    }
+
+    let mut pos_only_as_keyword: Vec<String> = vec![];
+    for (key, value) in kwargs {
+        let index = match local_names[num_pos_only..total_args].iter().position(|name| *name == key) {
+            Some(index) => index + num_pos_only,
+            None => {
+                if let Some(dict) = &var_keywords {
+                    DictObject::insert(dict, StringObject::new_from_string(key), value)?;
+                    continue;
+                }
+                if local_names[..num_pos_only].contains(&key) {
+                    pos_only_as_keyword.push(key);
+                    continue;
+                }
+                return ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    &format!("{}() got an unexpected keyword argument '{}'", name, key)
+                );
+            }
+        };
+        if fast_locals.contains_key(&(index as u32)) {
+            return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("{}() got multiple values for argument '{}'", name, key)
+            );
+        }
+        fast_locals.insert(index as u32, value);
+    }
+    if !pos_only_as_keyword.is_empty() {
+        // refer to CPython(positional_only_passed_as_keyword)
+        let names: Vec<String> = pos_only_as_keyword.iter().map(|name| format!("'{}'", name)).collect();
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("{}() got some positional-only arguments passed as keyword arguments: {}", name, names.join(", "))
+        );
+    }
+
+    let default_args = callable.defaults().len();
+    if given > num_args && flags & CO_VARARGS == 0 {
+        // refer to CPython(too_many_positional)
+        let takes = match (default_args, num_args) {
+            (0, 1) => "1 positional argument".to_string(),
+            (0, n) => format!("{} positional arguments", n),
+            (d, n) => format!("from {} to {} positional arguments", n - d, n)
+        };
+        let kw_only_given = (num_args..total_args).filter(|i| fast_locals.contains_key(&(*i as u32))).count();
+        let given = match kw_only_given {
+            0 => format!("{} {}", given, if given == 1 { "was" } else { "were" }),
+            n => format!(
+                "{} positional argument{} (and {} keyword-only argument{}) were",
+                given, if given == 1 { "" } else { "s" }, n, if n == 1 { "" } else { "s" }
+            )
+        };
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("{}() takes {} but {} given", name, takes, given)
+        );
+    }
+
+    let required = num_args - default_args;
+    let missing: Vec<String> = (0..required).filter(|i| !fast_locals.contains_key(&(*i as u32)))
+        .map(|i| local_names[i].clone())
+        .collect();
+    if !missing.is_empty() {
+        let plural = if missing.len() == 1 { "" } else { "s" };
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("{}() missing {} required positional argument{}: {}", name, missing.len(), plural, format_names(&missing))
+        );
+    }
+    for (i, value) in callable.defaults().iter().enumerate() {
+        fast_locals.entry((required + i) as u32).or_insert_with(|| value.clone());
+    }
+
+    let mut missing: Vec<String> = vec![];
+    for (i, param) in local_names.iter().enumerate().take(total_args).skip(num_args) {
+        if fast_locals.contains_key(&(i as u32)) {
+            continue;
+        }
+        match callable.kw_defaults().iter().find(|(key, _)| key == param) {
+            Some((_, value)) => {
+                fast_locals.insert(i as u32, value.clone());
+            },
+            None => missing.push(param.clone())
+        }
+    }
+    if !missing.is_empty() {
+        let plural = if missing.len() == 1 { "" } else { "s" };
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("{}() missing {} required keyword-only argument{}: {}", name, missing.len(), plural, format_names(&missing))
+        );
+    }
+    Ok(fast_locals)
+}
+
+pub fn new_namespace() -> PyObject {
+    DictObject::new_from_items(vec![]).unwrap()
+}
+
+/// the value bound to `name` in a namespace, whose keys are all str so the lookup cannot fail
+pub fn get_name(namespace: &PyObject, name: &str) -> Option<PyObject> {
+    DictObject::get(namespace, &StringObject::new_from_str(name)).expect("namespace keys should be str")
+}
+
+/// unbind `name` in a namespace, returning the value it had
+pub fn remove_name(namespace: &PyObject, name: &str) -> Option<PyObject> {
+    DictObject::remove(namespace, &StringObject::new_from_str(name)).expect("namespace keys should be str")
+}
+
+pub fn set_name(namespace: &PyObject, name: &str, value: PyObject) {
+    DictObject::insert(namespace, StringObject::new_from_str(name), value).expect("namespace keys should be str");
 }
\ No newline at end of file
@@ -1,266 +1,1556 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::rc::Rc;
-use crate::frame::Frame as FrameRaw;
-use crate::object::{CallableObject, CodeObject, FalseObject, IntObject, ListObject, NoneObject, NullObject, TrueObject};
+use crate::frame::{get_name, new_namespace, Frame as FrameRaw};
+use crate::object::{descriptor_get, AsyncGenValueObject, ByteArrayObject, GeneratorKind, NativeCoroutineObject, CallableObject, Kwargs, CodeObject, DictObject, EllipsisObject, FloatObject, NotImplementedObject, IntLongObject, Real, IterObject, SetObject, ExceptionKind, ExceptionObject, FalseObject, GeneratorObject, InstanceObject, IntObject, ListObject, MethodObject, NoneObject, NullObject, ObjectType, RangeObject, SliceObject, StringObject, SuperObject, PropertyObject, StaticMethodObject, ClassMethodObject, TrueObject, TupleObject, TypeObject};
 use crate::utils::ByteCode::*;
-use crate::utils::{BinaryOp, ByteCode, CmpOP, DowncastTrait, PyObject};
+use crate::utils::{BinaryOp, ByteCode, CmpOP, DowncastTrait, PyObject, PyResult, UnaryOp};
 
 
-type Frame = Option<Box<FrameRaw>>;
+/// why a frame stopped running
+pub enum FrameExit {
+    Return(PyObject),
+    /// the frame is suspended at a yield and resumes with the value sent to it
+    Yield(PyObject),
+    /// RETURN_GENERATOR, the frame is to be run by the generator wrapping it
+    Generator
+}
+
 pub struct Interpreter {
-    cur_frame: Frame,
-    return_value: Option<PyObject>,
-    builtins: HashMap<String, PyObject>
+    cur_frame: Option<Box<FrameRaw>>,
+    return_value: Option<PyObject>
+}
+
+thread_local! {
+    /// the builtins are shared by every frame, natives calling back into Python code reach them as well
+    static BUILTINS: HashMap<String, PyObject> = Interpreter::setup_builtins();
+    /// the callables standing for the exception types, shared by the builtins and the modules
+    /// defining exceptions so that `except` tells their kind
+    static EXCEPTION_TYPES: RefCell<Vec<(ExceptionKind, PyObject)>> = RefCell::new(vec![]);
+    /// the exception an except clause is handling, which a bare `raise` raises again,
+    /// refer to CPython(_PyErr_StackItem)
+    static HANDLED_EXCEPTION: RefCell<Option<PyObject>> = RefCell::new(None);
+    /// the modules imported so far, refer to CPython(sys.modules)
+    static MODULES: RefCell<HashMap<String, PyObject>> = RefCell::new(HashMap::new());
+    /// the number of frames being evaluated, refer to CPython(PyThreadState.recursion_remaining)
+    static RECURSION_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// the depth of frames beyond which a call raises RecursionError, refer to CPython(Py_DEFAULT_RECURSION_LIMIT)
+const RECURSION_LIMIT: usize = 1000;
+
+/// the native stack of the thread evaluating Python code, room for `RECURSION_LIMIT` frames in the
+/// debug build, which takes tens of kilobytes per frame
+const STACK_SIZE: usize = 256 << 20;
+
+/// run `f` on a thread with a stack deep enough for the recursion limit, the panics of `f` go on
+/// in the caller
+pub fn with_interpreter_stack<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    let thread = std::thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(f)
+        .expect("failed to spawn the interpreter thread");
+    match thread.join() {
+        Ok(value) => value,
+        Err(panic) => std::panic::resume_unwind(panic)
+    }
 }
 
-fn native_print(args: Vec<PyObject>) -> PyObject {
-    for arg in args {
-        // FIXME: print problem in function.py
-        print!("{}", arg.borrow());
-        // print!("hello");
+fn native_print(args: Vec<PyObject>) -> PyResult {
+    for (i, arg) in args.iter().enumerate() {
+        if i != 0 {
+            print!(" ");
+        }
+        print!("{}", Interpreter::str(arg)?);
     }
     println!();
-    NoneObject::new()
+    Ok(NoneObject::new())
+}
+
+fn native_repr(args: Vec<PyObject>) -> PyResult {
+    if args.len() != 1 {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("repr() takes exactly one argument ({} given)", args.len())
+        );
+    }
+    let repr = Interpreter::repr(&args[0])?;
+    Ok(StringObject::new_from_string(repr))
+}
+
+fn native_str(args: Vec<PyObject>) -> PyResult {
+    match args.len() {
+        0 => Ok(StringObject::new_from_str("")),
+        1 => {
+            let s = Interpreter::str(&args[0])?;
+            Ok(StringObject::new_from_string(s))
+        },
+        n => ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("str() takes at most 1 argument ({} given)", n)
+        )
+    }
+}
+
+fn native_len(args: Vec<PyObject>) -> PyResult {
+    if args.len() != 1 {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("len() takes exactly one argument ({} given)", args.len())
+        );
+    }
+    let length = args[0].borrow().length()?;
+    Ok(IntObject::new_from_i32(length as i32))
+}
+
+fn native_hash(args: Vec<PyObject>) -> PyResult {
+    if args.len() != 1 {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("hash() takes exactly one argument ({} given)", args.len())
+        );
+    }
+    let hash = args[0].borrow().hash()?;
+    Ok(IntLongObject::new_from_i64(hash))
+}
+
+fn native_set(args: Vec<PyObject>) -> PyResult {
+    if args.len() > 1 {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("set expected at most 1 argument, got {}", args.len())
+        );
+    }
+    SetObject::new_from_iterable(args.first(), false)
+}
+
+/// the identity of an object is the address of its allocation, stable for the object's lifetime
+fn native_id(args: Vec<PyObject>) -> PyResult {
+    if args.len() != 1 {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("id() takes exactly one argument ({} given)", args.len())
+        );
+    }
+    Ok(IntLongObject::new_from_i64(Rc::as_ptr(&args[0]) as *const () as usize as i64))
+}
+
+fn native_bool(args: Vec<PyObject>) -> PyResult {
+    match args.len() {
+        0 => Ok(FalseObject::new()),
+        1 => Ok(Interpreter::new_bool_object(Interpreter::is_true(&args[0])?)),
+        n => ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("bool expected at most 1 argument, got {}", n)
+        )
+    }
+}
+
+fn native_float(args: Vec<PyObject>) -> PyResult {
+    if args.len() > 1 {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("float expected at most 1 argument, got {}", args.len())
+        );
+    }
+    let arg = match args.first() {
+        Some(arg) => arg,
+        None => return Ok(FloatObject::new_from_f64(0.0))
+    };
+    if let Some(real) = Real::extract(arg) {
+        return Ok(FloatObject::new_from_f64(real.as_f64()));
+    }
+    if let Some(s) = arg.downcast_refcell::<StringObject>() {
+        return match s.string().trim().parse::<f64>() {
+            Ok(value) => Ok(FloatObject::new_from_f64(value)),
+            _ => ExceptionObject::raise(ExceptionKind::ValueError, &format!("could not convert string to float: {}", arg.borrow().repr()?))
+        };
+    }
+    ExceptionObject::raise(
+        ExceptionKind::TypeError,
+        &format!("float() argument must be a string or a real number, not '{}'", arg.borrow().type_name())
+    )
+}
+
+fn native_tuple(args: Vec<PyObject>) -> PyResult {
+    match args.len() {
+        0 => Ok(TupleObject::new_from_values(vec![])),
+        1 => Ok(TupleObject::new_from_values(IterObject::collect(&args[0])?)),
+        n => ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("tuple expected at most 1 argument, got {}", n)
+        )
+    }
+}
+
+fn native_list(args: Vec<PyObject>) -> PyResult {
+    match args.len() {
+        0 => Ok(ListObject::new_from_values(vec![])),
+        1 => Ok(ListObject::new_from_values(IterObject::collect(&args[0])?)),
+        n => ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("list expected at most 1 argument, got {}", n)
+        )
+    }
+}
+
+fn native_frozenset(args: Vec<PyObject>) -> PyResult {
+    if args.len() > 1 {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("frozenset expected at most 1 argument, got {}", args.len())
+        );
+    }
+    if let Some(arg) = args.first() {
+        if arg.borrow().object_type() == ObjectType::FROZENSET {
+            return Ok(arg.clone());
+        }
+    }
+    SetObject::new_from_iterable(args.first(), true)
+}
+
+fn native_dict(args: Vec<PyObject>) -> PyResult {
+    match args.len() {
+        0 => DictObject::new_from_items(vec![]),
+        1 => DictObject::new_from_items(DictObject::extract_items(&args[0])?),
+        n => ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("dict expected at most 1 argument, got {}", n)
+        )
+    }
+}
+
+/// run the body of a `class` statement in the namespace prepared by the metaclass and create the
+/// class by calling the metaclass, refer to CPython(builtin___build_class__)
+fn native_build_class(mut args: Vec<PyObject>, mut kwargs: Kwargs) -> PyResult {
+    if args.len() < 2 {
+        return ExceptionObject::raise(ExceptionKind::TypeError, "__build_class__: not enough arguments");
+    }
+    let bases = args.split_off(2);
+    if StringObject::extract(&args[1]).is_err() {
+        return ExceptionObject::raise(ExceptionKind::TypeError, "__build_class__: name is not a string");
+    }
+    let metaclass = kwargs.iter().position(|(key, _)| key == "metaclass").map(|i| kwargs.remove(i).1);
+    // a metaclass which is a class yields to the most derived metaclass of the bases
+    let metaclass = match metaclass {
+        Some(metaclass) if metaclass.downcast_refcell::<TypeObject>().is_some() => TypeObject::calculate_metaclass(&metaclass, &bases)?,
+        Some(metaclass) => metaclass,
+        None => match bases.first() {
+            Some(base) => TypeObject::calculate_metaclass(&TypeObject::type_of(base), &bases)?,
+            None => TypeObject::type_type()
+        }
+    };
+    let bases = TupleObject::new_from_values(bases);
+    let namespace = match Interpreter::load_attr(&metaclass, "__prepare__") {
+        Ok(prepare) => Interpreter::call_kw(&prepare, vec![args[1].clone(), bases.clone()], kwargs.clone())?,
+        Err(_) => new_namespace()
+    };
+    if namespace.downcast_refcell::<DictObject>().is_none() {
+        let name = match metaclass.downcast_refcell::<TypeObject>() {
+            Some(metaclass) => metaclass.name().to_string(),
+            None => "<metaclass>".to_string()
+        };
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("{}.__prepare__() must return a mapping, not {}", name, namespace.borrow().type_name())
+        );
+    }
+    let frame = match args[0].downcast_refcell::<CallableObject>() {
+        Some(func) if !func.is_native() => FrameRaw::new_from_callable(func, vec![], vec![])?,
+        _ => return ExceptionObject::raise(ExceptionKind::TypeError, "__build_class__: func must be a function")
+    };
+    let mut frame = Box::new(frame);
+    frame.set_locals(namespace.clone());
+    Interpreter::run_frame(frame)?;
+    Interpreter::call_kw(&metaclass, vec![args[1].clone(), bases, namespace], kwargs)
+}
+
+/// whether `class` is a subclass of a class or of any class of a tuple, `object` matches the builtin types too
+fn match_class_info(class: Option<PyObject>, info: &PyObject, fname: &str) -> PyResult<bool> {
+    if let Some(tuple) = info.downcast_refcell::<TupleObject>() {
+        for info in tuple.values() {
+            if match_class_info(class.clone(), info, fname)? {
+                return Ok(true);
+            }
+        }
+        return Ok(false);
+    }
+    if info.downcast_refcell::<TypeObject>().is_none() {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("{}() arg 2 must be a type, a tuple of types, or a union", fname)
+        );
+    }
+    if Rc::ptr_eq(info, &TypeObject::object()) {
+        return Ok(true);
+    }
+    Ok(class.is_some_and(|class| TypeObject::is_subclass(&class, info)))
+}
+
+fn native_isinstance(args: Vec<PyObject>) -> PyResult {
+    if args.len() != 2 {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("isinstance expected 2 arguments, got {}", args.len())
+        );
+    }
+    let class = TypeObject::type_of(&args[0]);
+    Ok(Interpreter::new_bool_object(match_class_info(Some(class), &args[1], "isinstance")?))
+}
+
+fn native_issubclass(args: Vec<PyObject>) -> PyResult {
+    if args.len() != 2 {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("issubclass expected 2 arguments, got {}", args.len())
+        );
+    }
+    if args[0].downcast_refcell::<TypeObject>().is_none() {
+        return ExceptionObject::raise(ExceptionKind::TypeError, "issubclass() arg 1 must be a class");
+    }
+    Ok(Interpreter::new_bool_object(match_class_info(Some(args[0].clone()), &args[1], "issubclass")?))
+}
+
+/// whether calling `obj` can succeed, refer to CPython(PyCallable_Check)
+fn is_callable(obj: &PyObject) -> bool {
+    if obj.downcast_refcell::<CallableObject>().is_some()
+        || obj.downcast_refcell::<MethodObject>().is_some()
+        || obj.downcast_refcell::<StaticMethodObject>().is_some()
+        || obj.downcast_refcell::<TypeObject>().is_some() {
+        return true;
+    }
+    obj.downcast_refcell::<InstanceObject>().is_some_and(|instance| TypeObject::lookup(&instance.class(), "__call__").is_some())
+}
+
+/// iter(iterable) or iter(callable, sentinel), refer to CPython(builtin_iter)
+fn native_iter(args: Vec<PyObject>) -> PyResult {
+    match args.len() {
+        1 => IterObject::get_iter(&args[0]),
+        2 if !is_callable(&args[0]) => ExceptionObject::raise(ExceptionKind::TypeError, "iter(v, w): v must be callable"),
+        2 => Ok(IterObject::new_callable(args[0].clone(), args[1].clone())),
+        0 => ExceptionObject::raise(ExceptionKind::TypeError, "iter expected at least 1 argument, got 0"),
+        n => ExceptionObject::raise(ExceptionKind::TypeError, &format!("iter expected at most 2 arguments, got {}", n))
+    }
+}
+
+/// next(iterator[, default]), the default is returned instead of raising StopIteration, refer to CPython(builtin_next)
+fn native_next(args: Vec<PyObject>) -> PyResult {
+    match args.len() {
+        0 => return ExceptionObject::raise(ExceptionKind::TypeError, "next expected at least 1 argument, got 0"),
+        1 | 2 => {},
+        n => return ExceptionObject::raise(ExceptionKind::TypeError, &format!("next expected at most 2 arguments, got {}", n))
+    }
+    // a generator's StopIteration keeps the value it returned, refer to CPython(builtin_next)
+    let item = args[0].borrow().next();
+    match (item, args.get(1)) {
+        (Ok(Some(item)), _) => Ok(item),
+        (Ok(None), None) => Err(ExceptionObject::new(ExceptionKind::StopIteration, vec![])),
+        (Ok(None), Some(default)) => Ok(default.clone()),
+        (Err(err), Some(default)) => {
+            let stopped = ExceptionObject::is_exception(&err, ExceptionKind::StopIteration);
+            if stopped { Ok(default.clone()) } else { Err(err) }
+        },
+        (Err(err), None) => Err(err)
+    }
+}
+
+/// import a built-in module, every import of it giving the same module object
+fn import_module(name: &str) -> PyResult {
+    if let Some(module) = MODULES.with(|modules| modules.borrow().get(name).cloned()) {
+        return Ok(module);
+    }
+    let module = match name {
+        "asyncio" => crate::asyncio::module(),
+        _ => return ExceptionObject::raise(ExceptionKind::ModuleNotFoundError, &format!("No module named '{}'", name))
+    };
+    MODULES.with(|modules| modules.borrow_mut().insert(name.to_string(), module.clone()));
+    Ok(module)
+}
+
+/// exception types are callables producing an exception instance of their kind
+pub fn exception_type(kind: ExceptionKind) -> PyObject {
+    EXCEPTION_TYPES.with(|types| {
+        let mut types = types.borrow_mut();
+        if let Some((_, constructor)) = types.iter().find(|(known, _)| *known == kind) {
+            return constructor.clone();
+        }
+        let constructor = CallableObject::new_native(kind.name(), Box::new(move |args| Ok(ExceptionObject::new(kind, args))));
+        types.push((kind, constructor.clone()));
+        constructor
+    })
 }
 
 impl Interpreter {
     const CMP_OP: [CmpOP; 6] = [CmpOP::LT, CmpOP::LE, CmpOP::EQ, CmpOP::NEQ, CmpOP::GT, CmpOP::GE];
     pub fn new(code: Rc<RefCell<CodeObject>>) -> Self {
-        let builtins = Self::setup_builtins();
-
         Self {
             cur_frame: Some(Box::new(FrameRaw::new_from_code(code))),
-            return_value: None,
-            builtins
+            return_value: None
         }
     }
 
     fn setup_builtins() -> HashMap<String, PyObject> {
         let mut builtins: HashMap<String, PyObject> = HashMap::new();
-        builtins.insert("$String_True".to_string(), TrueObject::new());
-        builtins.insert("$String_False".to_string(), FalseObject::new());
-        builtins.insert("$String_None".to_string(), NoneObject::new());
-        builtins.insert("$String_print".to_string(), CallableObject::new_native(Box::new(native_print)));
+        builtins.insert("True".to_string(), TrueObject::new());
+        builtins.insert("False".to_string(), FalseObject::new());
+        builtins.insert("None".to_string(), NoneObject::new());
+        builtins.insert("print".to_string(), CallableObject::new_native("print", Box::new(native_print)));
+        builtins.insert("len".to_string(), CallableObject::new_native("len", Box::new(native_len)));
+        builtins.insert("repr".to_string(), CallableObject::new_native("repr", Box::new(native_repr)));
+        builtins.insert("str".to_string(), CallableObject::new_native("str", Box::new(native_str)));
+        builtins.insert("hash".to_string(), CallableObject::new_native("hash", Box::new(native_hash)));
+        let dict = CallableObject::new_native("dict", Box::new(native_dict));
+        dict.downcast_refcell_mut::<CallableObject>().unwrap()
+            .insert_method("fromkeys", CallableObject::new_native("fromkeys", Box::new(DictObject::fromkeys)));
+        builtins.insert("dict".to_string(), dict);
+        builtins.insert("Ellipsis".to_string(), EllipsisObject::new());
+        builtins.insert("NotImplemented".to_string(), NotImplementedObject::new());
+        builtins.insert("id".to_string(), CallableObject::new_native("id", Box::new(native_id)));
+        builtins.insert("bool".to_string(), CallableObject::new_native("bool", Box::new(native_bool)));
+        builtins.insert("float".to_string(), CallableObject::new_native("float", Box::new(native_float)));
+        builtins.insert("list".to_string(), CallableObject::new_native("list", Box::new(native_list)));
+        builtins.insert("range".to_string(), CallableObject::new_native("range", Box::new(RangeObject::new_from_args)));
+        builtins.insert("bytearray".to_string(), CallableObject::new_native("bytearray", Box::new(ByteArrayObject::new_from_args)));
+        builtins.insert("tuple".to_string(), CallableObject::new_native("tuple", Box::new(native_tuple)));
+        builtins.insert("set".to_string(), CallableObject::new_native("set", Box::new(native_set)));
+        builtins.insert("frozenset".to_string(), CallableObject::new_native("frozenset", Box::new(native_frozenset)));
+        builtins.insert("object".to_string(), TypeObject::object());
+        builtins.insert("type".to_string(), TypeObject::type_type());
+        builtins.insert("property".to_string(), CallableObject::new_native_kw("property", Box::new(PropertyObject::new_from_args)));
+        builtins.insert("staticmethod".to_string(), CallableObject::new_native("staticmethod", Box::new(StaticMethodObject::new_from_args)));
+        builtins.insert("classmethod".to_string(), CallableObject::new_native("classmethod", Box::new(ClassMethodObject::new_from_args)));
+        builtins.insert("super".to_string(), CallableObject::new_native("super", Box::new(SuperObject::new_from_args)));
+        builtins.insert("isinstance".to_string(), CallableObject::new_native("isinstance", Box::new(native_isinstance)));
+        builtins.insert("issubclass".to_string(), CallableObject::new_native("issubclass", Box::new(native_issubclass)));
+        builtins.insert("iter".to_string(), CallableObject::new_native("iter", Box::new(native_iter)));
+        builtins.insert("next".to_string(), CallableObject::new_native("next", Box::new(native_next)));
+        builtins.insert("__build_class__".to_string(), CallableObject::new_native_kw("__build_class__", Box::new(native_build_class)));
+        for kind in Self::BUILTIN_EXCEPTIONS {
+            builtins.insert(kind.name().to_string(), exception_type(kind));
+        }
 
         builtins
     }
 
-    pub fn run(&mut self)  {
-        while let Some(mut cur_frame) = self.cur_frame.take() {
-            let mut next_frame = None;
-            while !cur_frame.finish() {
-                let bytecode = cur_frame.get_byte_code();
-                let mut arg = None;
-                if bytecode.have_arg() {
-                    arg = Some(cur_frame.get_arg());
-                }
-                cur_frame.skip_codes_of(bytecode.cache_num() as usize);
-                // println!("interpret bytecode: {:?}", bytecode);
-
-                match bytecode {
-                    CALL => {
-                        let arg = arg.unwrap();
-                        let mut fn_arg: Vec<PyObject> = vec![];
-                        for _ in 0..arg {
-                            fn_arg.push(cur_frame.pop())
+    const BUILTIN_EXCEPTIONS: [ExceptionKind; 21] = [
+        ExceptionKind::AssertionError,
+        ExceptionKind::AttributeError,
+        ExceptionKind::BaseException,
+        ExceptionKind::Exception,
+        ExceptionKind::GeneratorExit,
+        ExceptionKind::ImportError,
+        ExceptionKind::IndexError,
+        ExceptionKind::KeyError,
+        ExceptionKind::LookupError,
+        ExceptionKind::ModuleNotFoundError,
+        ExceptionKind::NameError,
+        ExceptionKind::OverflowError,
+        ExceptionKind::RecursionError,
+        ExceptionKind::RuntimeError,
+        ExceptionKind::StopAsyncIteration,
+        ExceptionKind::StopIteration,
+        ExceptionKind::TimeoutError,
+        ExceptionKind::TypeError,
+        ExceptionKind::UnboundLocalError,
+        ExceptionKind::ValueError,
+        ExceptionKind::ZeroDivisionError,
+    ];
+
+    /// the kind of the exceptions an exception type raises
+    pub fn exception_kind(obj: &PyObject) -> Option<ExceptionKind> {
+        EXCEPTION_TYPES.with(|types| {
+            types.borrow().iter().find(|(_, constructor)| Rc::ptr_eq(obj, constructor)).map(|(kind, _)| *kind)
+        })
+    }
+
+    /// whether the exception `exc` is caught by `except types`, refer to CPython(PyErr_GivenExceptionMatches)
+    fn exception_matches(exc: &PyObject, types: &PyObject) -> PyResult<bool> {
+        let types = match types.downcast_refcell::<TupleObject>() {
+            Some(tuple) => tuple.values().clone(),
+            None => vec![types.clone()]
+        };
+        let kind = exc.downcast_refcell::<ExceptionObject>().map(|exc| exc.kind());
+        let mut matches = false;
+        for class in types {
+            match Self::exception_kind(&class) {
+                Some(base) => matches |= kind.is_some_and(|kind| kind.is_subclass(base)),
+                None => return ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    "catching classes that do not inherit from BaseException is not allowed"
+                )
+            }
+        }
+        Ok(matches)
+    }
+
+    /// look up an attribute, functions found on the object's type are bound to the object
+    pub fn load_attr(obj: &PyObject, name: &str) -> PyResult {
+        // instances resolve their attributes themselves, as the instance dict shadows the class
+        if obj.downcast_refcell::<InstanceObject>().is_some() {
+            let attr = obj.borrow().get_attr(name.to_string());
+            return attr;
+        }
+        let method = obj.borrow().get_method(name);
+        if let Some(method) = method {
+            return Ok(MethodObject::new(method, obj.clone()));
+        }
+        let attr = obj.borrow().get_attr(name.to_string());
+        attr
+    }
+
+    /// look up a special method on the type of `obj`, bound to it, refer to CPython(_PyObject_LookupSpecial)
+    fn lookup_special(obj: &PyObject, name: &str) -> PyResult<Option<PyObject>> {
+        let method = obj.borrow().get_method(name);
+        match method {
+            Some(method) if obj.downcast_refcell::<InstanceObject>().is_some() => {
+                descriptor_get(method, Some(obj), &TypeObject::type_of(obj)).map(Some)
+            },
+            Some(method) => Ok(Some(MethodObject::new(method, obj.clone()))),
+            None => Ok(None)
+        }
+    }
+
+    /// whether `obj` is a coroutine, which `await` runs as it is
+    pub fn is_coroutine(obj: &PyObject) -> bool {
+        obj.downcast_refcell::<GeneratorObject>().is_some_and(|coro| coro.kind() == GeneratorKind::Coroutine)
+            || obj.downcast_refcell::<NativeCoroutineObject>().is_some()
+    }
+
+    /// the iterator `await obj` runs, `None` when `obj` has no `__await__`,
+    /// refer to CPython(_PyCoro_GetAwaitableIter)
+    pub fn awaitable_iter(obj: &PyObject) -> PyResult<Option<PyObject>> {
+        if Self::is_coroutine(obj) {
+            return Ok(Some(obj.clone()));
+        }
+        let res = match Self::lookup_special(obj, "__await__")? {
+            Some(await_) => Self::call(&await_, vec![])?,
+            None => return Ok(None)
+        };
+        if Self::is_coroutine(&res) {
+            return ExceptionObject::raise(ExceptionKind::TypeError, "__await__() returned a coroutine");
+        }
+        if Self::lookup_special(&res, "__next__")?.is_none() {
+            return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("__await__() returned non-iterator of type '{}'", res.borrow().type_name())
+            );
+        }
+        Ok(Some(res))
+    }
+
+    /// look up a method without binding it to the object, which saves the allocation of a bound
+    /// method for calls, the object is returned as well when it is to be passed as `self`,
+    /// refer to CPython(_PyObject_GetMethod)
+    fn load_method(obj: &PyObject, name: &str) -> PyResult<(PyObject, Option<PyObject>)> {
+        let method = match obj.downcast_refcell::<InstanceObject>() {
+            Some(instance) => instance.unbound_method(name),
+            None => obj.borrow().get_method(name)
+        };
+        match method {
+            Some(method) => Ok((method, Some(obj.clone()))),
+            None => Ok((Self::load_attr(obj, name)?, None))
+        }
+    }
+
+    pub fn run(&mut self) -> PyResult<()> {
+        let frame = self.cur_frame.take().expect("the module frame should only run once");
+        let return_value = Self::run_frame(frame)?;
+        self.return_value = Some(return_value);
+        Ok(())
+    }
+
+    fn builtin(name: &str) -> Option<PyObject> {
+        BUILTINS.with(|builtins| builtins.get(name).cloned())
+    }
+
+    fn is_builtin(obj: &PyObject, name: &str) -> bool {
+        Self::builtin(name).is_some_and(|builtin| Rc::ptr_eq(obj, &builtin))
+    }
+
+    /// the items of an iterable being unpacked into targets, refer to CPython(unpack_iterable)
+    fn unpack_values(seq: &PyObject) -> PyResult<Vec<PyObject>> {
+        if let Some(tuple) = seq.downcast_refcell::<TupleObject>() {
+            return Ok(tuple.values().clone());
+        }
+        if let Some(list) = seq.downcast_refcell::<ListObject>() {
+            return Ok(list.values().clone());
+        }
+        IterObject::collect(seq).or_else(|err| {
+            let not_iterable = ExceptionObject::is_exception(&err, ExceptionKind::TypeError);
+            if not_iterable {
+                return ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    &format!("cannot unpack non-iterable {} object", seq.borrow().type_name())
+                );
+            }
+            Err(err)
+        })
+    }
+
+    /// a callable in the messages about its arguments, e.g. `__main__.f()`, refer to CPython(_PyObject_FunctionStr)
+    fn function_str(func: &PyObject) -> PyResult<String> {
+        if let Some(method) = func.downcast_refcell::<MethodObject>() {
+            return Self::function_str(&method.func());
+        }
+        if let Some(func) = func.downcast_refcell::<CallableObject>() {
+            if func.is_native() {
+                return Ok(format!("{}()", func.name()));
+            }
+            let module = get_name(&func.globals(), "__name__").and_then(|module| StringObject::extract(&module).ok());
+            return Ok(match module {
+                Some(module) if module != "builtins" => format!("{}.{}()", module, func.name()),
+                _ => format!("{}()", func.name())
+            });
+        }
+        func.borrow().str()
+    }
+
+    /// call any callable object, bound methods get their object prepended to the arguments
+    pub fn call(callable: &PyObject, args: Vec<PyObject>) -> PyResult {
+        Self::call_kw(callable, args, vec![])
+    }
+
+    pub fn call_kw(callable: &PyObject, mut args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+        let mut callable = callable.clone();
+        let bound = callable.downcast_refcell::<MethodObject>().map(|method| (method.func(), method.self_obj()));
+        if let Some((func, self_obj)) = bound {
+            args.insert(0, self_obj);
+            callable = func;
+        }
+        // static methods are callable themselves since Python 3.10
+        let func = callable.downcast_refcell::<StaticMethodObject>().map(|method| method.func());
+        if let Some(func) = func {
+            callable = func;
+        }
+        // classes and instances are called through `__call__` of their class, refer to CPython(slot_tp_call)
+        let is_object = callable.downcast_refcell::<TypeObject>().is_some() || callable.downcast_refcell::<InstanceObject>().is_some();
+        let call = if is_object { TypeObject::lookup(&TypeObject::type_of(&callable), "__call__") } else { None };
+        if let Some(call) = call {
+            args.insert(0, callable);
+            return Self::call_kw(&call, args, kwargs);
+        }
+        let callable = match callable.downcast_refcell::<CallableObject>() {
+            Some(callable) => callable,
+            None => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("'{}' object is not callable", callable.borrow().type_name())
+            )
+        };
+        if callable.is_native() {
+            return callable.call_native_kw(args, kwargs);
+        }
+        let frame = Box::new(FrameRaw::new_from_callable(callable, args, kwargs)?);
+        Self::run_frame(frame)
+    }
+
+    /// evaluate a frame until it returns, the frame of a generator function becomes the generator
+    fn run_frame(mut frame: Box<FrameRaw>) -> PyResult {
+        match Self::resume_frame(&mut frame, None)? {
+            FrameExit::Return(value) => Ok(value),
+            FrameExit::Generator => Ok(GeneratorObject::new(frame)),
+            FrameExit::Yield(_) => unreachable!("a frame yields after RETURN_GENERATOR only")
+        }
+    }
+
+    /// evaluate a frame from where it stopped until it returns or yields, or raise `throw` where
+    /// it stopped, the exceptions raised by instructions covered by the exception table are handled
+    /// within the frame, refer to CPython(exception_unwind)
+    pub fn resume_frame(cur_frame: &mut FrameRaw, throw: Option<PyObject>) -> PyResult<FrameExit> {
+        // every frame being evaluated holds a part of the native stack
+        Self::enter_recursive_call("", || Self::unwind_frame(cur_frame, throw))
+    }
+
+    /// run `f` one level deeper, the frames and the native recursions through nested objects
+    /// sharing the depth, so that too deep a recursion raises RecursionError, with `context` added
+    /// to its message, before the native stack overflows, refer to CPython(_Py_EnterRecursiveCall)
+    pub fn enter_recursive_call<T>(context: &str, f: impl FnOnce() -> PyResult<T>) -> PyResult<T> {
+        if RECURSION_DEPTH.with(|depth| depth.get()) >= RECURSION_LIMIT {
+            return ExceptionObject::raise(
+                ExceptionKind::RecursionError,
+                &format!("maximum recursion depth exceeded{}", context)
+            );
+        }
+        RECURSION_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        let res = f();
+        RECURSION_DEPTH.with(|depth| depth.set(depth.get() - 1));
+        res
+    }
+
+    fn unwind_frame(cur_frame: &mut FrameRaw, throw: Option<PyObject>) -> PyResult<FrameExit> {
+        let mut exit = match throw {
+            Some(exc) => Err(exc),
+            None => Self::eval_frame(cur_frame)
+        };
+        loop {
+            let exc = match exit {
+                Err(exc) => exc,
+                exit => return exit
+            };
+            match cur_frame.exception_handler() {
+                Some(handler) => cur_frame.unwind(&handler, exc),
+                None => return Err(exc)
+            }
+            exit = Self::eval_frame(cur_frame);
+        }
+    }
+
+    /// run the instructions of a frame until it returns or yields, calls of Python functions
+    /// evaluate their frames recursively
+    fn eval_frame(cur_frame: &mut FrameRaw) -> PyResult<FrameExit> {
+        // the high bytes of the next argument, collected by EXTENDED_ARG
+        let mut extended_arg: u32 = 0;
+        while !cur_frame.finish() {
+            cur_frame.begin_instruction();
+            let bytecode = cur_frame.get_byte_code();
+            let mut arg = None;
+            if bytecode.have_arg() {
+                arg = Some(extended_arg << 8 | cur_frame.get_arg() as u32);
+                extended_arg = 0;
+            }
+            cur_frame.skip_codes_of(bytecode.cache_num() as usize);
+            // println!("interpret bytecode: {:?}", bytecode);
+
+            match bytecode {
+                EXTENDED_ARG => {
+                    extended_arg = arg.unwrap();
+                },
+                KW_NAMES => {
+                    let names = cur_frame.get_const(arg.unwrap() as usize);
+                    cur_frame.set_kw_names(names);
+                },
+                CALL => {
+                    let mut fn_arg = cur_frame.pop_n(arg.unwrap() as usize);
+                    // the keyword arguments are the last ones, named by a preceding KW_NAMES
+                    let mut kwargs: Kwargs = vec![];
+                    if let Some(names) = cur_frame.take_kw_names() {
+                        let names = names.downcast_refcell::<TupleObject>().expect("keyword names should be tuple").values().clone();
+                        let values = fn_arg.split_off(fn_arg.len() - names.len());
+                        for (name, value) in names.iter().zip(values) {
+                            kwargs.push((StringObject::extract(name).expect("keyword name should be str"), value));
+                        }
+                    }
+                    let obj = cur_frame.pop();
+                    let obj2 = cur_frame.pop();
+                    let mut callable = obj.clone();
+                    if !obj2.borrow().is_null() {
+                        // the method shape: [method, self, args...]
+                        fn_arg.insert(0, obj);
+                        callable = obj2;
+                    }
+                    if fn_arg.is_empty() && kwargs.is_empty() && Self::is_builtin(&callable, "super") {
+                        fn_arg = cur_frame.super_args()?;
+                    }
+                    cur_frame.push(Self::call_kw(&callable, fn_arg, kwargs)?);
+                },
+                CALL_FUNCTION_EX => {
+                    // `f(*args, **kwargs)` with the arguments in a tuple and a dict
+                    let kwargs = if arg.unwrap() & 0x01 != 0 { Some(cur_frame.pop()) } else { None };
+                    let args = cur_frame.pop();
+                    let func = cur_frame.pop();
+                    // the NULL pushed before the function
+                    cur_frame.pop();
+                    let args = match args.downcast_refcell::<TupleObject>() {
+                        Some(tuple) => tuple.values().clone(),
+                        None => match IterObject::get_iter(&args) {
+                            Ok(_) => IterObject::collect(&args)?,
+                            Err(_) => return ExceptionObject::raise(
+                                ExceptionKind::TypeError,
+                                &format!("{} argument after * must be an iterable, not {}", Self::function_str(&func)?, args.borrow().type_name())
+                            )
                         }
-                        let obj = cur_frame.pop();
-                        let obj2 = cur_frame.pop();
-                        if obj2.downcast_refcell::<NullObject>().is_some() {
-                            let callable = obj.downcast_refcell::<CallableObject>().expect("invalid callable object");
-                            if !callable.is_native() {
-                                next_frame = Some(Box::new(FrameRaw::new_from_callable(callable, fn_arg)));
-                                break
-                            } else {
-                                let return_value = callable.call_native(fn_arg);
-                                cur_frame.push(return_value);
+                    };
+                    let mut fn_kwargs: Kwargs = vec![];
+                    if let Some(kwargs) = kwargs {
+                        let items = kwargs.downcast_refcell::<DictObject>().expect("CALL_FUNCTION_EX expects a dict").items();
+                        for (key, value) in items {
+                            match StringObject::extract(&key) {
+                                Ok(key) => fn_kwargs.push((key, value)),
+                                Err(_) => return ExceptionObject::raise(ExceptionKind::TypeError, "keywords must be strings")
                             }
-                        } else {
-                            unimplemented!("method unimplemented")
                         }
-                    },
-                    MAKE_FUNCTION => {
-                        let code = cur_frame.pop();
-                        let code = code.downcast_refcell::<CodeObject>().expect("invalid code object");
-                        let arg = arg.unwrap();
-                        let mut defaults: Vec<PyObject> = vec![];
-                        for _ in 0..arg {
-                            defaults.push(cur_frame.pop());
+                    }
+                    cur_frame.push(Self::call_kw(&func, args, fn_kwargs)?);
+                },
+                MAKE_FUNCTION => {
+                    let code = cur_frame.pop();
+                    let arg = arg.unwrap();
+                    let mut closure: Vec<PyObject> = vec![];
+                    if arg & 0x08 != 0 {
+                        let tuple = cur_frame.pop();
+                        closure = tuple.downcast_refcell::<TupleObject>().expect("closure should be tuple").values().clone();
+                    }
+                    // annotations are not supported yet
+                    if arg & 0x04 != 0 {
+                        cur_frame.pop();
+                    }
+                    let mut kw_defaults: Kwargs = vec![];
+                    if arg & 0x02 != 0 {
+                        let dict = cur_frame.pop();
+                        for (key, value) in dict.downcast_refcell::<DictObject>().expect("keyword defaults should be dict").items() {
+                            kw_defaults.push((StringObject::extract(&key).expect("keyword names should be str"), value));
                         }
-                        let callable = CallableObject::new(code, defaults);
-                        cur_frame.push(callable);
-                    },
-                    GET_ITER => {
-                        let obj = cur_frame.pop();
-                        cur_frame.push(obj.borrow().to_iter());
-                    },
-                    FOR_ITER => {
-                        let mut fn_arg = vec![];
-                        let iter_obj = cur_frame.top();
-                        fn_arg.push(iter_obj.clone());
-                        let callable_fn = iter_obj.borrow().get_attr("next".to_string());
-                        let callable = callable_fn.downcast_refcell::<CallableObject>().expect("invalid callable next function");
-                        assert!(callable.is_native());
-                        let return_value = callable.call_native(fn_arg);
-                        cur_frame.push(return_value);
-
-                        if cur_frame.top().borrow().is_null() {
-                            let arg = arg.unwrap();
-                            cur_frame.jump_offset(Self::get_jump_offset(bytecode, arg));
+                    }
+                    let mut defaults: Vec<PyObject> = vec![];
+                    if arg & 0x01 != 0 {
+                        let tuple = cur_frame.pop();
+                        defaults = tuple.downcast_refcell::<TupleObject>().expect("defaults should be tuple").values().clone();
+                    }
+                    let callable = CallableObject::new(code, defaults, kw_defaults, closure, cur_frame.globals());
+                    cur_frame.push(callable);
+                },
+                GET_ITER => {
+                    let obj = cur_frame.pop();
+                    cur_frame.push(IterObject::get_iter(&obj)?);
+                },
+                FOR_ITER => {
+                    let iter = cur_frame.top();
+                    let value = IterObject::advance(&iter)?;
+                    match value {
+                        Some(value) => cur_frame.push(value),
+                        None => {
+                            // the exhausted iterator is popped
                             cur_frame.pop();
+                            cur_frame.jump_offset(Self::get_jump_offset(bytecode, arg.unwrap()));
                         }
-                    },
-                    BUILD_LIST => {
-                        let arg = arg.unwrap();
-                        let mut values = vec![];
-                        for _ in 0..arg {
-                            values.push(cur_frame.pop());
-                        }
-                        let list = ListObject::new_from_values(values);
-                        cur_frame.push(list);
-                    },
-                    LIST_EXTEND => {
-                        let arg = arg.unwrap();
-                        let tos = cur_frame.pop();
-                        if arg == 1 {
-                            let base = cur_frame.pop();
-                            cur_frame.push(ListObject::extend(base, tos));
+                    }
+                },
+                BUILD_LIST => {
+                    let values = cur_frame.pop_n(arg.unwrap() as usize);
+                    cur_frame.push(ListObject::new_from_values(values));
+                },
+                SWAP => {
+                    cur_frame.swap(arg.unwrap() as usize);
+                },
+                COPY => {
+                    let value = cur_frame.peek(arg.unwrap() as usize);
+                    cur_frame.push(value);
+                },
+                UNPACK_SEQUENCE => {
+                    let count = arg.unwrap() as usize;
+                    let seq = cur_frame.pop();
+                    let values = Self::unpack_values(&seq)?;
+                    if values.len() != count {
+                        let msg = if values.len() > count {
+                            format!("too many values to unpack (expected {})", count)
                         } else {
-                            unimplemented!()
+                            format!("not enough values to unpack (expected {}, got {})", count, values.len())
+                        };
+                        return ExceptionObject::raise(ExceptionKind::ValueError, &msg);
+                    }
+                    // the first item ends up on top of the stack
+                    for value in values.into_iter().rev() {
+                        cur_frame.push(value);
+                    }
+                },
+                UNPACK_EX => {
+                    // the counts of the targets before and after the starred one
+                    let (before, after) = ((arg.unwrap() & 0xff) as usize, (arg.unwrap() >> 8) as usize);
+                    let seq = cur_frame.pop();
+                    let mut values = Self::unpack_values(&seq)?;
+                    if values.len() < before + after {
+                        return ExceptionObject::raise(
+                            ExceptionKind::ValueError,
+                            &format!("not enough values to unpack (expected at least {}, got {})", before + after, values.len())
+                        );
+                    }
+                    let tail = values.split_off(values.len() - after);
+                    let starred = values.split_off(before);
+                    for value in tail.into_iter().rev() {
+                        cur_frame.push(value);
+                    }
+                    cur_frame.push(ListObject::new_from_values(starred));
+                    for value in values.into_iter().rev() {
+                        cur_frame.push(value);
+                    }
+                },
+                BUILD_TUPLE => {
+                    let values = cur_frame.pop_n(arg.unwrap() as usize);
+                    cur_frame.push(TupleObject::new_from_values(values));
+                },
+                LIST_TO_TUPLE => {
+                    let list = cur_frame.pop();
+                    let values = list.downcast_refcell::<ListObject>().expect("LIST_TO_TUPLE expects a list").values().clone();
+                    cur_frame.push(TupleObject::new_from_values(values));
+                },
+                LIST_APPEND => {
+                    let value = cur_frame.pop();
+                    let list = cur_frame.peek(arg.unwrap() as usize);
+                    list.downcast_refcell_mut::<ListObject>().expect("LIST_APPEND expects a list").append(value);
+                },
+                LIST_EXTEND => {
+                    let iterable = cur_frame.pop();
+                    let list = cur_frame.peek(arg.unwrap() as usize);
+                    ListObject::extend(&list, &iterable)?;
+                },
+                PRINT_EXPR => {
+                    // the echo of the interactive interpreter
+                    let obj = cur_frame.pop();
+                    if obj.borrow().object_type() != ObjectType::NONE {
+                        println!("{}", obj.borrow().repr()?);
+                    }
+                },
+                PUSH_NULL => {
+                    cur_frame.push(NullObject::new());
+                },
+                RETURN_VALUE => {
+                    return Ok(FrameExit::Return(cur_frame.pop()));
+                },
+                RETURN_GENERATOR => {
+                    return Ok(FrameExit::Generator);
+                },
+                YIELD_VALUE => {
+                    // the unused argument byte is skipped, so the suspended frame stands at the next instruction
+                    cur_frame.get_arg();
+                    return Ok(FrameExit::Yield(cur_frame.pop()));
+                },
+                SEND => {
+                    // refer to CPython(TARGET(SEND)) of Python 3.11
+                    let value = cur_frame.pop();
+                    let receiver = cur_frame.top();
+                    match GeneratorObject::send_to(&receiver, value)? {
+                        FrameExit::Yield(value) => cur_frame.push(value),
+                        FrameExit::Return(value) => {
+                            cur_frame.pop();
+                            cur_frame.push(value);
+                            cur_frame.jump_offset(Self::get_jump_offset(bytecode, arg.unwrap()));
+                        },
+                        FrameExit::Generator => unreachable!("a resumed frame does not return a generator")
+                    }
+                },
+                GET_YIELD_FROM_ITER => {
+                    // generators are delegated to as they are, to pass on send() and throw()
+                    let obj = cur_frame.pop();
+                    if obj.downcast_refcell::<GeneratorObject>().is_some() {
+                        cur_frame.push(obj);
+                    } else {
+                        cur_frame.push(IterObject::get_iter(&obj)?);
+                    }
+                },
+                GET_AWAITABLE => {
+                    // refer to CPython(TARGET(GET_AWAITABLE)) of Python 3.11
+                    let obj = cur_frame.pop();
+                    let iter = match Self::awaitable_iter(&obj)? {
+                        Some(iter) => iter,
+                        None => {
+                            let type_name = obj.borrow().type_name();
+                            let msg = match arg.unwrap() {
+                                1 => format!("'async with' received an object from __aenter__ that does not implement __await__: {}", type_name),
+                                2 => format!("'async with' received an object from __aexit__ that does not implement __await__: {}", type_name),
+                                _ => format!("object {} can't be used in 'await' expression", type_name)
+                            };
+                            return ExceptionObject::raise(ExceptionKind::TypeError, &msg);
                         }
-                    },
-                    PUSH_NULL => {
+                    };
+                    let awaited = iter.downcast_refcell::<GeneratorObject>().is_some_and(|coro| coro.kind() == GeneratorKind::Coroutine && coro.delegate().is_some());
+                    if awaited {
+                        return ExceptionObject::raise(ExceptionKind::RuntimeError, "coroutine is being awaited already");
+                    }
+                    cur_frame.push(iter);
+                },
+                GET_AITER => {
+                    let obj = cur_frame.pop();
+                    let iter = match Self::lookup_special(&obj, "__aiter__")? {
+                        Some(aiter) => Self::call(&aiter, vec![])?,
+                        None => return ExceptionObject::raise(
+                            ExceptionKind::TypeError,
+                            &format!("'async for' requires an object with __aiter__ method, got {}", obj.borrow().type_name())
+                        )
+                    };
+                    if Self::lookup_special(&iter, "__anext__")?.is_none() {
+                        return ExceptionObject::raise(
+                            ExceptionKind::TypeError,
+                            &format!("'async for' received an object from __aiter__ that does not implement __anext__: {}", iter.borrow().type_name())
+                        );
+                    }
+                    cur_frame.push(iter);
+                },
+                GET_ANEXT => {
+                    let aiter = cur_frame.top();
+                    let next = match Self::lookup_special(&aiter, "__anext__")? {
+                        Some(anext) => Self::call(&anext, vec![])?,
+                        None => return ExceptionObject::raise(
+                            ExceptionKind::TypeError,
+                            &format!("'async for' requires an iterator with __anext__ method, got {}", aiter.borrow().type_name())
+                        )
+                    };
+                    match Self::awaitable_iter(&next)? {
+                        Some(awaitable) => cur_frame.push(awaitable),
+                        None => return ExceptionObject::raise(
+                            ExceptionKind::TypeError,
+                            &format!("'async for' received an invalid object from __anext__: {}", next.borrow().type_name())
+                        )
+                    }
+                },
+                END_ASYNC_FOR => {
+                    // the handler of the loop, StopAsyncIteration ends it and anything else goes on
+                    let exc = cur_frame.pop();
+                    let stopped = exc.downcast_refcell::<ExceptionObject>().is_some_and(|exc| exc.kind().is_subclass(ExceptionKind::StopAsyncIteration));
+                    if !stopped {
+                        return Err(exc);
+                    }
+                    cur_frame.pop();
+                },
+                BEFORE_WITH => {
+                    let manager = cur_frame.pop();
+                    let type_name = manager.borrow().type_name();
+                    let enter = match Self::lookup_special(&manager, "__enter__")? {
+                        Some(enter) => enter,
+                        None => return ExceptionObject::raise(
+                            ExceptionKind::TypeError,
+                            &format!("'{}' object does not support the context manager protocol", type_name)
+                        )
+                    };
+                    let exit = match Self::lookup_special(&manager, "__exit__")? {
+                        Some(exit) => exit,
+                        None => return ExceptionObject::raise(
+                            ExceptionKind::TypeError,
+                            &format!("'{}' object does not support the context manager protocol (missed __exit__ method)", type_name)
+                        )
+                    };
+                    cur_frame.push(exit);
+                    let res = Self::call(&enter, vec![])?;
+                    cur_frame.push(res);
+                },
+                WITH_EXCEPT_START => {
+                    // the stack holds __exit__, the offset of the raising instruction, the exception
+                    // handled before and the exception, whose type, value and traceback go to
+                    // __exit__ while everything stays on the stack for the suppression or the reraise
+                    let exit = cur_frame.peek(4);
+                    let exc = cur_frame.peek(1);
+                    let exc_type = match exc.downcast_refcell::<ExceptionObject>() {
+                        Some(exc) => exception_type(exc.kind()),
+                        None => TypeObject::type_of(&exc)
+                    };
+                    let res = Self::call(&exit, vec![exc_type, exc, NoneObject::new()])?;
+                    cur_frame.push(res);
+                },
+                BEFORE_ASYNC_WITH => {
+                    let manager = cur_frame.pop();
+                    let type_name = manager.borrow().type_name();
+                    let enter = match Self::lookup_special(&manager, "__aenter__")? {
+                        Some(enter) => enter,
+                        None => return ExceptionObject::raise(
+                            ExceptionKind::TypeError,
+                            &format!("'{}' object does not support the asynchronous context manager protocol", type_name)
+                        )
+                    };
+                    let exit = match Self::lookup_special(&manager, "__aexit__")? {
+                        Some(exit) => exit,
+                        None => return ExceptionObject::raise(
+                            ExceptionKind::TypeError,
+                            &format!("'{}' object does not support the asynchronous context manager protocol (missed __aexit__ method)", type_name)
+                        )
+                    };
+                    cur_frame.push(exit);
+                    let res = Self::call(&enter, vec![])?;
+                    cur_frame.push(res);
+                },
+                ASYNC_GEN_WRAP => {
+                    let value = cur_frame.pop();
+                    cur_frame.push(AsyncGenValueObject::new(value));
+                },
+                IMPORT_NAME => {
+                    // only absolute imports of the built-in modules are supported
+                    let name = cur_frame.get_name(arg.unwrap() as usize);
+                    let _fromlist = cur_frame.pop();
+                    let _level = cur_frame.pop();
+                    cur_frame.push(import_module(&name)?);
+                },
+                IMPORT_FROM => {
+                    let name = cur_frame.get_name(arg.unwrap() as usize);
+                    let module = cur_frame.top();
+                    match Self::load_attr(&module, &name) {
+                        Ok(obj) => cur_frame.push(obj),
+                        Err(err) if ExceptionObject::is_exception(&err, ExceptionKind::AttributeError) => {
+                            let module_name = module.borrow().get_attr("__name__".to_string())?.borrow().str()?;
+                            return ExceptionObject::raise(
+                                ExceptionKind::ImportError,
+                                &format!("cannot import name '{}' from '{}' (unknown location)", name, module_name)
+                            );
+                        },
+                        Err(err) => return Err(err)
+                    }
+                },
+                LOAD_CONST => {
+                    let obj = cur_frame.get_const(arg.unwrap() as usize);
+                    cur_frame.push(obj);
+                },
+                LOAD_NAME => {
+                    // LEGB
+                    let name = cur_frame.get_name(arg.unwrap() as usize);
+                    if let Some(obj) = cur_frame.look_up_name(&name) {
+                        cur_frame.push(obj);
+                    } else if let Some(obj) = Self::builtin(&name) {
+                        cur_frame.push(obj);
+                    } else {
+                        // TODO: enclosing missing
+                        return ExceptionObject::raise(ExceptionKind::NameError, &format!("name '{}' is not defined", name));
+                    }
+                },
+                LOAD_GLOBAL => {
+                    let arg = arg.unwrap();
+                    if arg & 1 == 1 {
                         cur_frame.push(NullObject::new());
-                    },
-                    RETURN_VALUE => {
-                        self.return_value = Some(cur_frame.pop());
-                        next_frame = cur_frame.parent();
-                        break
-                    },
-                    LOAD_CONST => {
-                        let obj = cur_frame.get_const(arg.unwrap() as usize);
+                    }
+                    let arg = arg >> 1;
+                    let name = cur_frame.get_name(arg as usize);
+                    if let Some(obj) = cur_frame.look_up_global(&name) {
                         cur_frame.push(obj);
-                    },
-                    LOAD_NAME => {
-                        // LEGB
-                        let name = cur_frame.get_name(arg.unwrap() as usize);
-                        if let Some(obj) = cur_frame.look_up_name(name.clone()) {
-                            cur_frame.push(obj);
-                        } else if let Some(obj) = self.builtins.get(&name.borrow().hash_key()) {
-                            cur_frame.push(obj.clone());
-                        } else {
-                            // TODO: enclosing missing
-                            // cannot find name
-                            panic!("cannot find variable {:?}", name);
-                        }
-                    },
-                    LOAD_GLOBAL => {
-                        // FIXME: every Callable object should have its own global
-                        let arg = arg.unwrap();
-                        if arg & 1 == 1 {
-                            cur_frame.push(NullObject::new());
-                        }
-                        let arg = arg >> 1;
-                        let name = cur_frame.get_name(arg as usize);
-                        if let Some(obj) = cur_frame.look_up_global(name.clone()) {
-                            cur_frame.push(obj);
-                        } else if let Some(obj) = self.builtins.get(&name.borrow().hash_key()) {
-                            cur_frame.push(obj.clone());
-                        } else {
-                            // TODO: enclosing missing
-                            // cannot find name
-                            panic!("cannot find variable {:?}", name);
-                        }
+                    } else if let Some(obj) = Self::builtin(&name) {
+                        cur_frame.push(obj);
+                    } else {
+                        // TODO: enclosing missing
+                        return ExceptionObject::raise(ExceptionKind::NameError, &format!("name '{}' is not defined", name));
+                    }
 
-                    },
-                    LOAD_FAST => {
-                        let arg = arg.unwrap();
-                        cur_frame.push(cur_frame.load_fast(arg));
-                    },
-                    POP_TOP => {
-                        cur_frame.pop();
-                    },
-                    STORE_NAME => {
-                        let name = cur_frame.get_name(arg.unwrap() as usize);
-                        let tos = cur_frame.pop();
-                        cur_frame.set_local(name, tos);
-                    },
-                    COMPARE_OP => {
-                        let arg = arg.unwrap();
-                        let rhs = cur_frame.pop();
-                        let lhs = cur_frame.pop();
-                        // we suppose that lhs and rhs should be number
-                        let lhs = lhs
-                            .downcast_refcell::<IntObject>()
-                            .expect(&format!("{:?} cannot be compared", lhs.borrow().object_type()));
-                        let rhs = rhs.downcast_refcell::<IntObject>()
-                            .expect(&format!("{:?} cannot be compared", rhs.borrow().object_type()));
-                        let op = &Self::CMP_OP[arg as usize];
-                        match op {
-                            CmpOP::GT =>cur_frame.push(Self::new_bool_object(lhs.value() > rhs.value())),
-                            CmpOP::GE =>cur_frame.push(Self::new_bool_object(lhs.value() >= rhs.value())),
-                            CmpOP::LT =>cur_frame.push(Self::new_bool_object(lhs.value() < rhs.value())),
-                            CmpOP::LE =>cur_frame.push(Self::new_bool_object(lhs.value() <= rhs.value())),
-                            CmpOP::EQ =>cur_frame.push(Self::new_bool_object(lhs.value() == rhs.value())),
-                            CmpOP::NEQ =>cur_frame.push(Self::new_bool_object(lhs.value() != rhs.value())),
-                        }
-                    },
-                    POP_JUMP_BACKWARD_IF_NOT_NONE | POP_JUMP_FORWARD_IF_NOT_NONE => {
-                        let arg = arg.unwrap();
-                        let tos = cur_frame.pop();
-                        if tos.downcast_refcell::<NoneObject>().is_none() {
-                            cur_frame.jump_offset(Self::get_jump_offset(bytecode, arg));
-                        }
-                    },
-                    POP_JUMP_FORWARD_IF_NONE | POP_JUMP_BACKWARD_IF_NONE => {
-                        let arg = arg.unwrap();
-                        let tos = cur_frame.pop();
-                        if tos.downcast_refcell::<NoneObject>().is_some() {
-                            cur_frame.jump_offset(Self::get_jump_offset(bytecode, arg));
+                },
+                MAKE_CELL => {
+                    cur_frame.make_cell(arg.unwrap());
+                },
+                COPY_FREE_VARS => {
+                    cur_frame.copy_free_vars(arg.unwrap() as usize);
+                },
+                LOAD_CLOSURE => {
+                    let cell = cur_frame.load_closure(arg.unwrap());
+                    cur_frame.push(cell);
+                },
+                LOAD_DEREF => {
+                    let value = cur_frame.load_deref(arg.unwrap())?;
+                    cur_frame.push(value);
+                },
+                LOAD_CLASSDEREF => {
+                    let value = cur_frame.load_class_deref(arg.unwrap())?;
+                    cur_frame.push(value);
+                },
+                STORE_DEREF => {
+                    let value = cur_frame.pop();
+                    cur_frame.store_deref(arg.unwrap(), Some(value));
+                },
+                DELETE_DEREF => {
+                    cur_frame.delete_deref(arg.unwrap())?;
+                },
+                LOAD_FAST => {
+                    let arg = arg.unwrap();
+                    cur_frame.push(cur_frame.load_fast(arg));
+                },
+                STORE_FAST => {
+                    let arg = arg.unwrap();
+                    let tos = cur_frame.pop();
+                    cur_frame.store_fast(arg, tos);
+                },
+                POP_TOP => {
+                    cur_frame.pop();
+                },
+                STORE_NAME => {
+                    let name = cur_frame.get_name(arg.unwrap() as usize);
+                    let tos = cur_frame.pop();
+                    cur_frame.set_local(name, tos);
+                },
+                DELETE_NAME => {
+                    let name = cur_frame.get_name(arg.unwrap() as usize);
+                    if cur_frame.delete_local(&name).is_none() {
+                        return ExceptionObject::raise(ExceptionKind::NameError, &format!("name '{}' is not defined", name));
+                    }
+                },
+                DELETE_FAST => {
+                    cur_frame.delete_fast(arg.unwrap())?;
+                },
+                COMPARE_OP => {
+                    let arg = arg.unwrap();
+                    let rhs = cur_frame.pop();
+                    let lhs = cur_frame.pop();
+                    let op = &Self::CMP_OP[arg as usize];
+                    cur_frame.push(Self::rich_compare(&lhs, &rhs, op)?);
+                },
+                IS_OP => {
+                    let invert = arg.unwrap() == 1;
+                    let rhs = cur_frame.pop();
+                    let lhs = cur_frame.pop();
+                    cur_frame.push(Self::new_bool_object(Rc::ptr_eq(&lhs, &rhs) != invert));
+                },
+                CONTAINS_OP => {
+                    let invert = arg.unwrap() == 1;
+                    let container = cur_frame.pop();
+                    let item = cur_frame.pop();
+                    let res = Self::contains(&container, &item)?;
+                    cur_frame.push(Self::new_bool_object(res != invert));
+                },
+                LOAD_ATTR => {
+                    let name = cur_frame.get_name(arg.unwrap() as usize);
+                    let obj = cur_frame.pop();
+                    cur_frame.push(Self::load_attr(&obj, &name)?);
+                },
+                LOAD_METHOD => {
+                    // push [function, self] for CALL to pass the object as the first argument, or
+                    // [NULL, attribute] when the attribute is no plain method
+                    let name = cur_frame.get_name(arg.unwrap() as usize);
+                    let obj = cur_frame.pop();
+                    match Self::load_method(&obj, &name)? {
+                        (method, Some(self_obj)) => {
+                            cur_frame.push(method);
+                            cur_frame.push(self_obj);
+                        },
+                        (attr, None) => {
+                            cur_frame.push(NullObject::new());
+                            cur_frame.push(attr);
                         }
-                    },
-                    POP_JUMP_BACKWARD_IF_TRUE | POP_JUMP_FORWARD_IF_TRUE =>{
-                        let arg = arg.unwrap() ;
-                        let tos = cur_frame.pop();
-                        if tos.downcast_refcell::<TrueObject>().is_some() {
-                            cur_frame.jump_offset(Self::get_jump_offset(bytecode, arg));
+                    }
+                },
+                BINARY_SUBSCR => {
+                    let key = cur_frame.pop();
+                    let container = cur_frame.pop();
+                    // like STORE_SUBSCR, a method is called without the container being borrowed
+                    let method = container.borrow().get_method("__getitem__");
+                    let res = match method {
+                        Some(method) => Self::call(&method, vec![container, key])?,
+                        None => container.borrow().get_item(&key)?
+                    };
+                    cur_frame.push(res);
+                },
+                STORE_SUBSCR => {
+                    let key = cur_frame.pop();
+                    let container = cur_frame.pop();
+                    let value = cur_frame.pop();
+                    // a method defined in Python may use the container, which must not be borrowed mutably then
+                    let method = container.borrow().get_method("__setitem__");
+                    match method {
+                        Some(method) => {
+                            Self::call(&method, vec![container, key, value])?;
+                        },
+                        None => container.borrow_mut().set_item(key, value)?
+                    }
+                },
+                DELETE_SUBSCR => {
+                    let key = cur_frame.pop();
+                    let container = cur_frame.pop();
+                    let method = container.borrow().get_method("__delitem__");
+                    match method {
+                        Some(method) => {
+                            Self::call(&method, vec![container, key])?;
+                        },
+                        None => container.borrow_mut().del_item(&key)?
+                    }
+                },
+                STORE_ATTR => {
+                    let name = cur_frame.get_name(arg.unwrap() as usize);
+                    let obj = cur_frame.pop();
+                    let value = cur_frame.pop();
+                    obj.borrow().set_attr(name, value)?;
+                },
+                DELETE_ATTR => {
+                    let name = cur_frame.get_name(arg.unwrap() as usize);
+                    let obj = cur_frame.pop();
+                    obj.borrow().del_attr(name)?;
+                },
+                LOAD_BUILD_CLASS => {
+                    cur_frame.push(Self::builtin("__build_class__").expect("__build_class__ should be a builtin"));
+                },
+                BUILD_MAP => {
+                    let arg = arg.unwrap();
+                    let mut items = vec![];
+                    for _ in 0..arg {
+                        let value = cur_frame.pop();
+                        let key = cur_frame.pop();
+                        items.push((key, value));
+                    }
+                    items.reverse();
+                    cur_frame.push(DictObject::new_from_items(items)?);
+                },
+                BUILD_CONST_KEY_MAP => {
+                    let arg = arg.unwrap();
+                    let keys = cur_frame.pop();
+                    let keys = keys.downcast_refcell::<TupleObject>().expect("keys should be tuple").values().clone();
+                    let mut values = vec![];
+                    for _ in 0..arg {
+                        values.push(cur_frame.pop());
+                    }
+                    values.reverse();
+                    cur_frame.push(DictObject::new_from_items(keys.into_iter().zip(values).collect())?);
+                },
+                MAP_ADD => {
+                    let value = cur_frame.pop();
+                    let key = cur_frame.pop();
+                    let dict = cur_frame.peek(arg.unwrap() as usize);
+                    DictObject::insert(&dict, key, value)?;
+                },
+                DICT_UPDATE => {
+                    let update = cur_frame.pop();
+                    let dict = cur_frame.peek(arg.unwrap() as usize);
+                    if update.downcast_refcell::<DictObject>().is_none() {
+                        return ExceptionObject::raise(
+                            ExceptionKind::TypeError,
+                            &format!("'{}' object is not a mapping", update.borrow().type_name())
+                        );
+                    }
+                    DictObject::update(&dict, &update)?;
+                },
+                DICT_MERGE => {
+                    // `f(**mapping)`: unlike DICT_UPDATE a key given twice is an error, the function
+                    // being called is below the positional arguments, refer to CPython(format_kwargs_error)
+                    let update = cur_frame.pop();
+                    let dict = cur_frame.peek(arg.unwrap() as usize);
+                    let items = match update.downcast_refcell::<DictObject>() {
+                        Some(update) => update.items(),
+                        None => {
+                            let func = cur_frame.peek(arg.unwrap() as usize + 2);
+                            return ExceptionObject::raise(
+                                ExceptionKind::TypeError,
+                                &format!("{} argument after ** must be a mapping, not {}", Self::function_str(&func)?, update.borrow().type_name())
+                            );
                         }
-                    },
-                    POP_JUMP_BACKWARD_IF_FALSE | POP_JUMP_FORWARD_IF_FALSE =>{
-                        let arg = arg.unwrap();
-                        let tos = cur_frame.pop();
-                        if tos.downcast_refcell::<FalseObject>().is_some() {
-                            cur_frame.jump_offset(Self::get_jump_offset(bytecode, arg));
+                    };
+                    for (key, value) in items {
+                        if DictObject::get(&dict, &key)?.is_some() {
+                            let func = cur_frame.peek(arg.unwrap() as usize + 2);
+                            let key = match StringObject::extract(&key) {
+                                Ok(key) => key,
+                                Err(_) => return ExceptionObject::raise(ExceptionKind::TypeError, "keywords must be strings")
+                            };
+                            return ExceptionObject::raise(
+                                ExceptionKind::TypeError,
+                                &format!("{} got multiple values for keyword argument '{}'", Self::function_str(&func)?, key)
+                            );
                         }
-                    },
-                    JUMP_BACKWARD | JUMP_FORWARD => {
-                        let arg = arg.unwrap();
+                        DictObject::insert(&dict, key, value)?;
+                    }
+                },
+                BUILD_SET => {
+                    let arg = arg.unwrap();
+                    let mut values = vec![];
+                    for _ in 0..arg {
+                        values.push(cur_frame.pop());
+                    }
+                    let set = SetObject::new_from_iterable(None, false)?;
+                    for value in values.into_iter().rev() {
+                        set.downcast_refcell_mut::<SetObject>().unwrap().add(value)?;
+                    }
+                    cur_frame.push(set);
+                },
+                SET_ADD => {
+                    let value = cur_frame.pop();
+                    let set = cur_frame.peek(arg.unwrap() as usize);
+                    set.downcast_refcell_mut::<SetObject>().expect("SET_ADD expects a set").add(value)?;
+                },
+                SET_UPDATE => {
+                    let iterable = cur_frame.pop();
+                    let set = cur_frame.peek(arg.unwrap() as usize);
+                    SetObject::update(&set, &iterable)?;
+                },
+                BUILD_SLICE => {
+                    let arg = arg.unwrap();
+                    let step = if arg == 3 { cur_frame.pop() } else { NoneObject::new() };
+                    let stop = cur_frame.pop();
+                    let start = cur_frame.pop();
+                    cur_frame.push(SliceObject::new(start, stop, step));
+                },
+                LOAD_ASSERTION_ERROR => {
+                    cur_frame.push(exception_type(ExceptionKind::AssertionError));
+                },
+                RAISE_VARARGS => {
+                    let arg = arg.unwrap();
+                    if arg == 0 {
+                        let handled = HANDLED_EXCEPTION.with(|handled| handled.borrow().clone());
+                        return match handled {
+                            Some(exc) => Err(exc),
+                            None => ExceptionObject::raise(ExceptionKind::RuntimeError, "No active exception to reraise")
+                        };
+                    }
+                    if arg == 2 {
+                        // the cause is dropped
+                        cur_frame.pop();
+                    }
+                    let exc = cur_frame.pop();
+                    // `raise ValueError` raises an instance created without arguments
+                    let instance = exc.downcast_refcell::<CallableObject>()
+                        .filter(|callable| callable.is_native())
+                        .map(|callable| callable.call_native(vec![]));
+                    let exc = match instance {
+                        Some(instance) => instance?,
+                        None => exc
+                    };
+                    if exc.borrow().object_type() != ObjectType::EXCEPTION {
+                        return ExceptionObject::raise(ExceptionKind::TypeError, "exceptions must derive from BaseException");
+                    }
+                    return Err(exc);
+                },
+                PUSH_EXC_INFO => {
+                    // the exception handled before is kept below the new one, for POP_EXCEPT to restore
+                    let exc = cur_frame.pop();
+                    let previous = HANDLED_EXCEPTION.with(|handled| handled.replace(Some(exc.clone())));
+                    cur_frame.push(previous.unwrap_or_else(NoneObject::new));
+                    cur_frame.push(exc);
+                },
+                POP_EXCEPT => {
+                    let previous = cur_frame.pop();
+                    let previous = if previous.borrow().object_type() == ObjectType::NONE { None } else { Some(previous) };
+                    HANDLED_EXCEPTION.with(|handled| *handled.borrow_mut() = previous);
+                },
+                CHECK_EXC_MATCH => {
+                    let types = cur_frame.pop();
+                    let exc = cur_frame.top();
+                    cur_frame.push(Self::new_bool_object(Self::exception_matches(&exc, &types)?));
+                },
+                RERAISE => {
+                    // the offset of the original raise below the exception only serves tracebacks
+                    return Err(cur_frame.pop());
+                },
+                POP_JUMP_BACKWARD_IF_NOT_NONE | POP_JUMP_FORWARD_IF_NOT_NONE => {
+                    let arg = arg.unwrap();
+                    let tos = cur_frame.pop();
+                    if tos.downcast_refcell::<NoneObject>().is_none() {
                         cur_frame.jump_offset(Self::get_jump_offset(bytecode, arg));
                     }
-                    BINARY_OP => {
-                        let op:BinaryOp = arg.unwrap().into();
-                        let tos = cur_frame.pop();
-                        let tos1 = cur_frame.pop();
-                        cur_frame.push(op.handle(tos1, tos));
-                    },
-                    RESUME | PRECALL | CACHE | KW_NAMES => {
-                        // nop
+                },
+                POP_JUMP_FORWARD_IF_NONE | POP_JUMP_BACKWARD_IF_NONE => {
+                    let arg = arg.unwrap();
+                    let tos = cur_frame.pop();
+                    if tos.downcast_refcell::<NoneObject>().is_some() {
+                        cur_frame.jump_offset(Self::get_jump_offset(bytecode, arg));
+                    }
+                },
+                POP_JUMP_BACKWARD_IF_TRUE | POP_JUMP_FORWARD_IF_TRUE =>{
+                    let arg = arg.unwrap() ;
+                    let tos = cur_frame.pop();
+                    if Self::is_true(&tos)? {
+                        cur_frame.jump_offset(Self::get_jump_offset(bytecode, arg));
                     }
-                    _ => {unimplemented!("command {:?} unimplemented", bytecode)}
+                },
+                POP_JUMP_BACKWARD_IF_FALSE | POP_JUMP_FORWARD_IF_FALSE =>{
+                    let arg = arg.unwrap();
+                    let tos = cur_frame.pop();
+                    if !Self::is_true(&tos)? {
+                        cur_frame.jump_offset(Self::get_jump_offset(bytecode, arg));
+                    }
+                },
+                JUMP_IF_TRUE_OR_POP | JUMP_IF_FALSE_OR_POP => {
+                    // `a or b` and `a and b` keep the deciding operand on the stack
+                    let arg = arg.unwrap();
+                    let jump_if = bytecode == JUMP_IF_TRUE_OR_POP;
+                    if Self::is_true(&cur_frame.top())? == jump_if {
+                        cur_frame.jump_offset(Self::get_jump_offset(bytecode, arg));
+                    } else {
+                        cur_frame.pop();
+                    }
+                },
+                UNARY_POSITIVE | UNARY_NEGATIVE | UNARY_INVERT => {
+                    let op = match bytecode {
+                        UNARY_POSITIVE => UnaryOp::Positive,
+                        UNARY_NEGATIVE => UnaryOp::Negative,
+                        _ => UnaryOp::Invert
+                    };
+                    let tos = cur_frame.pop();
+                    cur_frame.push(op.handle(tos)?);
+                },
+                UNARY_NOT => {
+                    let tos = cur_frame.pop();
+                    cur_frame.push(Self::new_bool_object(!Self::is_true(&tos)?));
+                },
+                JUMP_BACKWARD | JUMP_FORWARD | JUMP_BACKWARD_NO_INTERRUPT => {
+                    let arg = arg.unwrap();
+                    cur_frame.jump_offset(Self::get_jump_offset(bytecode, arg));
                 }
-            }
-            if next_frame.is_some() {
-                let mut next_frame = next_frame.take().unwrap();
-                next_frame.set_parent(Some(cur_frame));
-                if self.return_value.is_some() {
-                    next_frame.push(self.return_value.clone().unwrap());
+                BINARY_OP => {
+                    let op: BinaryOp = (arg.unwrap() as u8).into();
+                    let tos = cur_frame.pop();
+                    let tos1 = cur_frame.pop();
+                    cur_frame.push(op.handle(tos1, tos)?);
+                },
+                RESUME | PRECALL | CACHE | NOP => {
+                    // nop
                 }
-                self.cur_frame = Some(next_frame);
+                _ => {unimplemented!("command {:?} unimplemented", bytecode)}
             }
         }
+        Ok(FrameExit::Return(NoneObject::new()))
+    }
+
+    /// the rich comparison `lhs <op> rhs`, the reflected operation of `rhs` is tried when `lhs` does not
+    /// support it, or first when `rhs` is an instance of a subclass, refer to CPython(do_richcompare)
+    pub fn rich_compare(lhs: &PyObject, rhs: &PyObject, op: &CmpOP) -> PyResult {
+        // the items of nested containers are compared recursively
+        Self::enter_recursive_call(" in comparison", || Self::do_rich_compare(lhs, rhs, op))
+    }
+
+    fn do_rich_compare(lhs: &PyObject, rhs: &PyObject, op: &CmpOP) -> PyResult {
+        if let Some(res) = ListObject::compare_values(lhs, rhs, op)? {
+            return Ok(Self::new_bool_object(res));
+        }
+        let reflected_first = rhs.borrow().is_proper_subtype_of(lhs);
+        if reflected_first {
+            if let Some(res) = rhs.borrow().rich_compare(lhs, &op.reflected())? {
+                return Ok(res);
+            }
+        }
+        if let Some(res) = lhs.borrow().rich_compare(rhs, op)? {
+            return Ok(res);
+        }
+        if !reflected_first {
+            if let Some(res) = rhs.borrow().rich_compare(lhs, &op.reflected())? {
+                return Ok(res);
+            }
+        }
+        match op {
+            CmpOP::EQ => Ok(Self::new_bool_object(Rc::ptr_eq(lhs, rhs))),
+            CmpOP::NEQ => Ok(Self::new_bool_object(!Rc::ptr_eq(lhs, rhs))),
+            op => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("'{:?}' not supported between instances of '{}' and '{}'", op, lhs.borrow().type_name(), rhs.borrow().type_name())
+            )
+        }
+    }
+
+    /// the truth of a rich comparison, what sorting and the builtin containers need
+    pub fn compare(lhs: &PyObject, rhs: &PyObject, op: &CmpOP) -> PyResult<bool> {
+        let res = Self::rich_compare(lhs, rhs, op)?;
+        Self::is_true(&res)
+    }
+
+    /// the equality used by containers, identity implies equality like CPython(PyObject_RichCompareBool)
+    pub fn equals(lhs: &PyObject, rhs: &PyObject) -> PyResult<bool> {
+        if Rc::ptr_eq(lhs, rhs) {
+            return Ok(true);
+        }
+        // two str are compared directly, so that the names of a namespace are still found at the
+        // recursion limit, refer to CPython(unicode_eq)
+        if let (Some(lhs), Some(rhs)) = (lhs.downcast_refcell::<StringObject>(), rhs.downcast_refcell::<StringObject>()) {
+            return Ok(lhs.as_str() == rhs.as_str());
+        }
+        Self::compare(lhs, rhs, &CmpOP::EQ)
+    }
+
+    /// repr() of any object, a list or dict is not borrowed while the reprs of its items are built,
+    /// refer to CPython(PyObject_Repr)
+    pub fn repr(obj: &PyObject) -> PyResult<String> {
+        if obj.downcast_refcell::<ListObject>().is_some() {
+            return ListObject::repr_of(obj);
+        }
+        if obj.downcast_refcell::<DictObject>().is_some() {
+            return DictObject::repr_of(obj);
+        }
+        obj.borrow().repr()
     }
 
+    /// str() of any object, which is the repr of a list or dict, refer to CPython(PyObject_Str)
+    pub fn str(obj: &PyObject) -> PyResult<String> {
+        if obj.downcast_refcell::<ListObject>().is_some() || obj.downcast_refcell::<DictObject>().is_some() {
+            return Self::repr(obj);
+        }
+        obj.borrow().str()
+    }
+
+    /// `item in container`, a `__contains__` method is called without the container being borrowed
+    pub fn contains(container: &PyObject, item: &PyObject) -> PyResult<bool> {
+        let method = container.borrow().get_method("__contains__");
+        match method {
+            Some(method) => Self::is_true(&Self::call(&method, vec![container.clone(), item.clone()])?),
+            None => container.borrow().contains(item)
+        }
+    }
+
+    /// the truth test shared by conditions, `not`, `and` and `or`
+    pub fn is_true(obj: &PyObject) -> PyResult<bool> {
+        obj.borrow().is_true()
+    }
 
     pub fn new_bool_object(val: bool) -> PyObject {
         if val {
@@ -278,7 +1568,7 @@ impl Interpreter {
         POP_JUMP_BACKWARD_IF_NOT_NONE,
         JUMP_BACKWARD_NO_INTERRUPT
     ];
-    fn get_jump_offset(bytecode: ByteCode, arg: u8) -> i64 {
+    fn get_jump_offset(bytecode: ByteCode, arg: u32) -> i64 {
         let caches = bytecode.cache_num() as i64;
         let mut arg = arg as i64;
 
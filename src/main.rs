@@ -2,6 +2,7 @@ mod object;
 mod utils;
 mod interpreter;
 mod frame;
+mod asyncio;
 
 use core::fmt;
 use std::{fs::File, io::{Cursor, Read, Seek, self}, mem};
@@ -14,7 +15,7 @@ use chrono::NaiveDateTime;
 use clap::Parser;
 use object::IntObject;
 use crate::interpreter::Interpreter;
-use crate::object::{CodeObject, DictObject, FalseObject, IntLongObject, ListObject, NoneObject, NullObject, ObjectType, SetObject, StringObject, TrueObject, TupleObject};
+use crate::object::{BytesObject, CodeObject, FLAG_REF, DictObject, EllipsisObject, FalseObject, FloatObject, IntLongObject, ListObject, NoneObject, NullObject, ObjectType, SetObject, StringObject, TrueObject, TupleObject};
 use crate::utils::{DowncastTrait, Magic, PyObject};
 
 
@@ -77,6 +78,14 @@ impl InputStream {
     pub fn jump_offset(&mut self, n: i64) {
         self.cursor.seek(io::SeekFrom::Current(n)).unwrap();
     }
+    pub fn position(&self) -> usize {
+        self.cursor.position() as usize
+    }
+    /// the byte `offset` bytes away from the current position, which stays where it is
+    pub fn peek(&self, offset: i64) -> Option<u8> {
+        let position = self.cursor.position() as i64 + offset;
+        usize::try_from(position).ok().and_then(|position| self.cursor.get_ref().get(position).copied())
+    }
     #[allow(dead_code)]
     pub fn forward(&mut self, n: usize) {
         self.cursor.seek(io::SeekFrom::Current(n as i64)).unwrap();
@@ -99,12 +108,6 @@ impl InputStream {
         self.depths
     }
 
-    pub fn push_ref(&mut self, r: PyObject) {
-        #[cfg(feature = "debug_marshal")]
-        println!("[DEBUG] push ref {}:{r:?}", self.refs.len());
-        self.refs.push(r);
-    }
-
     pub fn reserve_ref(&mut self) ->usize {
         let res = self.refs.len();
         self.refs.push(NullObject::new());
@@ -176,15 +179,20 @@ fn main() {
 
 
 
-    let file = File::open(pyc_path).expect("Failed to open file");
-    let parser = PycParser::new(file);
-    let mut interpreter = Interpreter::new(parser.code_object.clone());
-    if !args.no_run {
-        interpreter.run();
-    }
-    if args.info {
-        parser.print_info();
-    }
+    interpreter::with_interpreter_stack(move || {
+        let file = File::open(pyc_path).expect("Failed to open file");
+        let parser = PycParser::new(file);
+        let mut interpreter = Interpreter::new(parser.code_object.clone());
+        if !args.no_run {
+            if let Err(exc) = interpreter.run() {
+                eprintln!("{:?}", exc.borrow());
+                std::process::exit(1);
+            }
+        }
+        if args.info {
+            parser.print_info();
+        }
+    });
 }
 
 impl PycParser {
@@ -231,74 +239,44 @@ impl PycParser {
     }
 
     pub fn marshal_object(stream: &mut InputStream, magic: Magic) -> PyObject {
-        let object_type: ObjectType = (stream.read().unwrap() as char).into();
+        let code = stream.read().unwrap();
+        let object_type: ObjectType = (code as char).into();
         stream.inc_depth();
+        // only objects flagged with FLAG_REF are recorded, the slot is reserved before
+        // the object's children are read, refer to CPython(Python/marshal.c:r_ref_reserve)
+        let index = if code & FLAG_REF != 0 && object_type != ObjectType::REF {
+            let index = stream.reserve_ref();
+            #[cfg(feature = "debug_marshal")]
+            println!("[DEBUG] reserved index: {index}");
+            Some(index)
+        } else {
+            None
+        };
 
         let ret: PyObject = match object_type {
             ObjectType::NULL => NullObject::new(),
             ObjectType::NONE => NoneObject::new(),
             ObjectType::FALSE => FalseObject::new(),
             ObjectType::TRUE => TrueObject::new(),
-            ObjectType::INT => {
-                let ret = IntObject::new(stream);
-                stream.push_ref(ret.clone());
-                ret
-            },
-            ObjectType::INT64 => {
-                let ret = IntLongObject::new(stream);
-                stream.push_ref(ret.clone());
-                ret
-            },
-            ObjectType::STRING
-             | ObjectType::ASCII
-             | ObjectType::ASCII_INTERNED => {
-                let ret = StringObject::new(stream);
-                // FIXME: why we cannot add ref to STRING, but SHORT_ASCII is ok?
-                // stream.push_ref(ret.clone());
-                ret
-            },
+            ObjectType::ELLIPSIS => EllipsisObject::new(),
+            ObjectType::INT => IntObject::new(stream),
+            ObjectType::INT64 => IntLongObject::new(stream),
+            ObjectType::BINARY_FLOAT => FloatObject::new(stream),
+            ObjectType::FLOAT => FloatObject::new_from_text(stream),
+            // since Python 3, TYPE_STRING is only used for bytes
+            ObjectType::STRING => BytesObject::new(stream),
+            ObjectType::ASCII
+             | ObjectType::ASCII_INTERNED
+             | ObjectType::INTERNED => StringObject::new(stream),
             ObjectType::SHORT_ASCII
-             | ObjectType::SHORT_ASCII_INTERNED => {
-                let ret = StringObject::new_from_short(stream);
-                stream.push_ref(ret.clone());
-                ret
-            },
-            ObjectType::UNICODE => {
-                let ret = StringObject::new_from_unicode(stream);
-                stream.push_ref(ret.clone());
-                ret
-            },
-            ObjectType::DICT => {
-                let ret = DictObject::new(stream, magic);
-                stream.push_ref(ret.clone());
-                ret
-            },
-            ObjectType::LIST => {
-                let ret = ListObject::new(stream, magic);
-                stream.push_ref(ret.clone());
-                ret
-            },
-            ObjectType::TUPLE => {
-                let index = stream.reserve_ref();
-                #[cfg(feature = "debug_marshal")]
-                println!("[DEBUG] reserved index: {index}");
-                let ret = TupleObject::new(stream, magic);
-                stream.set_ref(index, ret.clone());
-                ret
-            },
-            ObjectType::SMALL_TUPLE => {
-                let index = stream.reserve_ref();
-                #[cfg(feature = "debug_marshal")]
-                println!("[DEBUG] reserved index: {index}");
-                let ret = TupleObject::new_from_short(stream, magic);
-                stream.set_ref(index, ret.clone());
-                ret
-            },
-            ObjectType::SET => {
-                let ret = SetObject::new(stream, magic);
-                stream.push_ref(ret.clone());
-                ret
-            },
+             | ObjectType::SHORT_ASCII_INTERNED => StringObject::new_from_short(stream),
+            ObjectType::UNICODE => StringObject::new_from_unicode(stream),
+            ObjectType::DICT => DictObject::new(stream, magic),
+            ObjectType::LIST => ListObject::new(stream, magic),
+            ObjectType::TUPLE => TupleObject::new(stream, magic),
+            ObjectType::SMALL_TUPLE => TupleObject::new_from_short(stream, magic),
+            ObjectType::SET => SetObject::new(stream, magic),
+            ObjectType::FROZENSET => SetObject::new_frozen(stream, magic),
             ObjectType::REF => {
                 let index = stream.read_u32().unwrap(); // index
                 #[cfg(feature = "debug_marshal")]
@@ -308,6 +286,9 @@ impl PycParser {
             ObjectType::CODE => CodeObject::new(stream, magic),
             _ => unimplemented!()
         };
+        if let Some(index) = index {
+            stream.set_ref(index, ret.clone());
+        }
         stream.dec_depth();
         ret
     }
@@ -317,6 +298,7 @@ impl PycParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::{hash, sort};
 
     #[test]
     fn test_input_stream() {
@@ -328,12 +310,23 @@ mod tests {
         assert_eq!(stream.read().unwrap(), 5);
     }
 
+    fn run_pyc(name: &'static str) {
+        interpreter::with_interpreter_stack(move || {
+            let file = File::open(format!("./tests/__pycache__/{name}.cpython-311.pyc")).expect("Failed to open file");
+            let parser = PycParser::new(file);
+            let mut interpreter = Interpreter::new(parser.code_object);
+            if let Err(exc) = interpreter.run() {
+                panic!("{name}.py raised {:?}", exc.borrow());
+            }
+        });
+    }
+
     #[test]
     fn test_one_plus_one() {
         let file = File::open("./tests/__pycache__/one_plus_one.cpython-311.pyc").expect("Failed to open file");
         let parser = PycParser::new(file);
         let mut interpreter = Interpreter::new(parser.code_object);
-        interpreter.run();
+        interpreter.run().expect("uncaught exception");
         let return_value = interpreter.return_value();
         assert!(return_value.is_some());
         let return_value = return_value.unwrap();
@@ -341,6 +334,189 @@ mod tests {
         assert_eq!(*return_value, NoneObject::new_raw());
     }
 
+    #[test]
+    fn test_str() {
+        run_pyc("str");
+    }
+
+    #[test]
+    fn test_repr() {
+        run_pyc("repr");
+    }
+
+    #[test]
+    fn test_hash() {
+        // the values of CPython with PYTHONHASHSEED=0
+        assert_eq!(hash::hash_str(""), 0);
+        assert_eq!(hash::hash_str("a"), 4644417185603328019);
+        assert_eq!(hash::hash_str("abcdefghijk"), 1450545860578130900);
+        assert_eq!(hash::hash_str("héllo"), 6395329678795984700);
+        assert_eq!(hash::hash_str("日本"), 6243316497235261705);
+        assert_eq!(hash::hash_str("😀"), -3536540696076613844);
+        assert_eq!(hash::hash_bytes(b"abc"), -4594863902769663758);
+        assert_eq!(hash::hash_int(-1), -2);
+        assert_eq!(hash::hash_int(-5), -5);
+        assert_eq!(hash::hash_int(1 << 61), 1);
+
+        let frozenset = |values: Vec<i32>| {
+            let values = ListObject::new_from_values(values.into_iter().map(IntObject::new_from_i32).collect());
+            SetObject::new_from_iterable(Some(&values), true).unwrap()
+        };
+        assert_eq!(frozenset(vec![]).borrow().hash().unwrap(), 133146708735736);
+        assert_eq!(frozenset(vec![3, 1, 2]).borrow().hash().unwrap(), -272375401224217160);
+
+        let tuple = |values: Vec<PyObject>| TupleObject::new_from_values(values);
+        let int = IntObject::new_from_i32;
+        assert_eq!(tuple(vec![]).borrow().hash().unwrap(), 5740354900026072187);
+        assert_eq!(tuple(vec![int(1), int(2)]).borrow().hash().unwrap(), -3550055125485641917);
+        let nested = tuple(vec![int(1), tuple(vec![int(2), int(3)])]);
+        assert_eq!(nested.borrow().hash().unwrap(), 7267574591690527098);
+
+        let float = |value: f64| FloatObject::new_from_f64(value).borrow().hash().unwrap();
+        assert_eq!(float(2.5), 1152921504606846978);
+        assert_eq!(float(-2.5), -1152921504606846978);
+        assert_eq!(float(1e300), 1224995262755759164);
+        assert_eq!(float(0.1), 230584300921369408);
+        assert_eq!(float(5e-324), 16777216);
+        assert_eq!(float(-1.0), -2);
+    }
+
+    #[test]
+    fn test_set() {
+        run_pyc("set");
+    }
+
+    #[test]
+    fn test_list() {
+        run_pyc("list");
+    }
+
+    #[test]
+    fn test_timsort() {
+        // pairs of (key, position) sorted by key only, equal keys must keep their positions in order
+        let mut values: Vec<(u64, usize)> = (0..5000).map(|i| ((i as u64 * 2654435761) % 97, i)).collect();
+        values[1000..3000].reverse();
+        let mut expected = values.clone();
+        expected.sort_by_key(|(key, _)| *key);
+        sort::timsort(&mut values, |a, b| Ok(a.0 < b.0)).unwrap();
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn test_subscript() {
+        run_pyc("subscript");
+    }
+
+    #[test]
+    fn test_truth() {
+        run_pyc("truth");
+    }
+
+    #[test]
+    fn test_compare() {
+        run_pyc("compare");
+    }
+
+    #[test]
+    fn test_identity() {
+        run_pyc("identity");
+    }
+
+    #[test]
+    fn test_arith() {
+        run_pyc("arith");
+    }
+
+    #[test]
+    fn test_inplace() {
+        run_pyc("inplace");
+    }
+
+    #[test]
+    fn test_unpack() {
+        run_pyc("unpack");
+    }
+
+    #[test]
+    fn test_classes() {
+        run_pyc("classes");
+    }
+
+    #[test]
+    fn test_inheritance() {
+        run_pyc("inheritance");
+    }
+
+    #[test]
+    fn test_iterators() {
+        run_pyc("iterators");
+    }
+
+    #[test]
+    fn test_generators() {
+        run_pyc("generators");
+    }
+
+    #[test]
+    fn test_descriptors() {
+        run_pyc("descriptors");
+    }
+
+    #[test]
+    fn test_methods() {
+        run_pyc("methods");
+    }
+
+    #[test]
+    fn test_metaclasses() {
+        run_pyc("metaclasses");
+    }
+
+    #[test]
+    fn test_operators() {
+        run_pyc("operators");
+    }
+
+    #[test]
+    fn test_reflected() {
+        run_pyc("reflected");
+    }
+
+    #[test]
+    fn test_slots() {
+        run_pyc("slots");
+    }
+
+    #[test]
+    fn test_contains() {
+        run_pyc("contains");
+    }
+
+    #[test]
+    fn test_tuple() {
+        run_pyc("tuple");
+    }
+
+    #[test]
+    fn test_dict() {
+        run_pyc("dict");
+    }
+
+    #[test]
+    fn test_coroutines() {
+        run_pyc("coroutines");
+    }
+
+    #[test]
+    fn test_with() {
+        run_pyc("with");
+    }
+
+    #[test]
+    fn test_recursion() {
+        run_pyc("recursion");
+    }
+
     // #[test]
     // fn test_function() {
     //     let file = File::open("./tests/__pycache__/function.cpython-311.pyc").expect("Failed to open file");
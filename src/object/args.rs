@@ -0,0 +1,69 @@
+// argument helpers shared by native methods, which receive `self` as their first argument
+use crate::object::{ExceptionKind, ExceptionObject, IntObject, ObjectType};
+use crate::utils::{PyObject, PyResult};
+
+/// an optional positional argument, `None` is treated as missing like CPython does
+pub fn opt_arg(args: &[PyObject], index: usize) -> Option<PyObject> {
+    match args.get(index) {
+        Some(arg) if arg.borrow().object_type() != ObjectType::NONE => Some(arg.clone()),
+        _ => None
+    }
+}
+
+pub fn int_arg(args: &[PyObject], index: usize, default: i64) -> PyResult<i64> {
+    match opt_arg(args, index) {
+        None => Ok(default),
+        Some(arg) => match IntObject::extract(&arg) {
+            Some(value) => Ok(value as i64),
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("'{}' object cannot be interpreted as an integer", arg.borrow().type_name())
+            )
+        }
+    }
+}
+
+/// check the number of positional arguments given to a native method, refer to CPython(PyArg_UnpackTuple)
+pub fn check_args(args: &[PyObject], name: &str, min: usize, max: usize) -> PyResult<()> {
+    // args[0] is self
+    let given = args.len() - 1;
+    let msg = if min == max && given != min {
+        match min {
+            0 => format!("{}() takes no arguments ({} given)", name, given),
+            1 => format!("{}() takes exactly one argument ({} given)", name, given),
+            n => format!("{}() takes exactly {} arguments ({} given)", name, n, given)
+        }
+    } else if given < min {
+        format!("{}() takes at least {} argument{} ({} given)", name, min, if min == 1 { "" } else { "s" }, given)
+    } else if given > max {
+        format!("{}() takes at most {} argument{} ({} given)", name, max, if max == 1 { "" } else { "s" }, given)
+    } else {
+        return Ok(());
+    };
+    ExceptionObject::raise(ExceptionKind::TypeError, &msg)
+}
+
+/// clamp the optional `start`/`end` arguments at `index` like slice indices, refer to CPython(ADJUST_INDICES)
+pub fn range_args(args: &[PyObject], index: usize, length: usize) -> PyResult<(i64, i64)> {
+    let length = length as i64;
+    let mut start = int_arg(args, index, 0)?;
+    let mut end = int_arg(args, index + 1, length)?;
+    if end > length {
+        end = length;
+    } else if end < 0 {
+        end = (end + length).max(0);
+    }
+    if start < 0 {
+        start = (start + length).max(0);
+    }
+    Ok((start, end))
+}
+
+/// resolve a possibly negative `index` into a sequence of `length` items, `None` when it is out of range
+pub fn normalize_index(index: i64, length: usize) -> Option<usize> {
+    let index = if index < 0 { index + length as i64 } else { index };
+    if index < 0 || index >= length as i64 {
+        return None;
+    }
+    Some(index as usize)
+}
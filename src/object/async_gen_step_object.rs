@@ -0,0 +1,375 @@
+use crate::object::{check_args, make_exception, object_address, stop_iteration, BasePycObject, CallableObject, ExceptionKind, ExceptionObject, GeneratorObject, NoneObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::cell::{Cell, Ref, RefCell};
+use std::fmt;
+use std::rc::{Rc, Weak};
+use crate::interpreter::FrameExit;
+use crate::utils::DowncastTrait;
+
+use crate::utils::{PyObject, PyResult};
+
+/// a value yielded by an async generator, which ASYNC_GEN_WRAP sets apart from the awaitables the
+/// frame yields while it awaits, refer to CPython(_PyAsyncGenWrappedValue)
+pub struct AsyncGenValueObject {
+    base: BasePycObject,
+    value: PyObject
+}
+
+impl AsyncGenValueObject {
+    pub fn new(value: PyObject) -> PyObject {
+        Rc::new(RefCell::new(Self {
+            base: BasePycObject::new(ObjectType::ASYNC_GEN_VALUE),
+            value
+        }))
+    }
+}
+
+impl PartialEq<Self> for AsyncGenValueObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for AsyncGenValueObject {}
+
+impl PyObjectTrait for AsyncGenValueObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        Ok(format!("<async_generator_wrapped_value object at {:#x}>", object_address(self)))
+    }
+}
+
+impl fmt::Display for AsyncGenValueObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for AsyncGenValueObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "AsyncGenValueObject({:?})", self.value.borrow())
+    }
+}
+
+/// what an awaitable of an async generator does to it
+enum AsyncGenOp {
+    /// `__anext__()` and `asend(value)`
+    Send(PyObject),
+    /// `athrow(exc)`
+    Throw(PyObject),
+    /// `aclose()`, a GeneratorExit thrown in which must not be answered by a yield
+    Close
+}
+
+/// refer to CPython(AwaitableState)
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum AwaitableState {
+    Init,
+    Iter,
+    Closed
+}
+
+/// the awaitable `__anext__`, `asend`, `athrow` and `aclose` of an async generator return, which
+/// runs the generator until it yields a value, refer to CPython(PyAsyncGenASend, PyAsyncGenAThrow)
+pub struct AsyncGenStepObject {
+    base: BasePycObject,
+    generator: PyObject,
+    op: AsyncGenOp,
+    state: Cell<AwaitableState>,
+    this: Weak<RefCell<AsyncGenStepObject>>
+}
+
+fn step(obj: &PyObject) -> Ref<'_, AsyncGenStepObject> {
+    obj.downcast_refcell::<AsyncGenStepObject>().expect("invalid async generator awaitable")
+}
+
+fn step_send(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "send", 1, 1)?;
+    step(&args[0]).send(args[1].clone())
+}
+
+fn step_throw(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "throw", 1, 3)?;
+    let exc = make_exception(&args[1], args.get(2))?;
+    step(&args[0]).throw(exc)
+}
+
+fn step_close(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "close", 0, 0)?;
+    step(&args[0]).state.set(AwaitableState::Closed);
+    Ok(NoneObject::new())
+}
+
+fn step_next(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__next__", 0, 0)?;
+    step(&args[0]).send(NoneObject::new())
+}
+
+fn step_iter(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__iter__", 0, 0)?;
+    Ok(args[0].clone())
+}
+
+
+fn wrapped_value(value: &PyObject) -> Option<PyObject> {
+    value.downcast_refcell::<AsyncGenValueObject>().map(|value| value.value.clone())
+}
+
+impl AsyncGenStepObject {
+    fn new(generator: PyObject, op: AsyncGenOp) -> PyObject {
+        let throw = !matches!(op, AsyncGenOp::Send(_));
+        Rc::new_cyclic(|this| RefCell::new(Self {
+            base: BasePycObject::new(if throw { ObjectType::ASYNC_GEN_ATHROW } else { ObjectType::ASYNC_GEN_ASEND }),
+            generator,
+            op,
+            state: Cell::new(AwaitableState::Init),
+            this: this.clone()
+        }))
+    }
+
+    pub fn new_send(generator: PyObject, value: PyObject) -> PyObject {
+        Self::new(generator, AsyncGenOp::Send(value))
+    }
+
+    /// `athrow(exc)`, or `aclose()` without an exception
+    pub fn new_throw(generator: PyObject, exc: Option<PyObject>) -> PyObject {
+        match exc {
+            Some(exc) => Self::new(generator, AsyncGenOp::Throw(exc)),
+            None => Self::new(generator, AsyncGenOp::Close)
+        }
+    }
+
+    fn generator(&self) -> Ref<'_, GeneratorObject> {
+        self.generator.downcast_refcell::<GeneratorObject>().expect("invalid async generator object")
+    }
+
+    /// the end of an async generator is StopAsyncIteration rather than a return
+    fn yielded(result: PyResult<FrameExit>) -> PyResult {
+        match result? {
+            FrameExit::Yield(value) => Ok(value),
+            _ => Err(ExceptionObject::new(ExceptionKind::StopAsyncIteration, vec![]))
+        }
+    }
+
+    /// a value the generator yields ends the awaitable with StopIteration, the awaitables it
+    /// yields while awaiting go up to the event loop, refer to CPython(async_gen_unwrap_value)
+    fn unwrap_value(&self, result: PyResult<FrameExit>) -> PyResult {
+        let generator = self.generator();
+        match Self::yielded(result) {
+            Err(err) => {
+                if ExceptionObject::is_exception(&err, ExceptionKind::StopAsyncIteration) || ExceptionObject::is_exception(&err, ExceptionKind::GeneratorExit) {
+                    generator.set_closed_async();
+                }
+                generator.set_running_async(false);
+                Err(err)
+            },
+            Ok(value) => match wrapped_value(&value) {
+                Some(value) => {
+                    generator.set_running_async(false);
+                    Err(stop_iteration(value))
+                },
+                None => Ok(value)
+            }
+        }
+    }
+
+    fn send(&self, value: PyObject) -> PyResult {
+        match self.op {
+            AsyncGenOp::Send(_) => self.asend_send(value),
+            _ => self.athrow_send(value)
+        }
+    }
+
+    fn throw(&self, exc: PyObject) -> PyResult {
+        match self.op {
+            AsyncGenOp::Send(_) => self.asend_throw(exc),
+            _ => self.athrow_throw(exc)
+        }
+    }
+
+    /// refer to CPython(async_gen_asend_send)
+    fn asend_send(&self, mut value: PyObject) -> PyResult {
+        if self.state.get() == AwaitableState::Closed {
+            return ExceptionObject::raise(ExceptionKind::RuntimeError, "cannot reuse already awaited __anext__()/asend()");
+        }
+        if self.state.get() == AwaitableState::Init {
+            if self.generator().running_async() {
+                return ExceptionObject::raise(ExceptionKind::RuntimeError, "anext(): asynchronous generator is already running");
+            }
+            if let AsyncGenOp::Send(initial) = &self.op {
+                if value.borrow().object_type() == ObjectType::NONE {
+                    value = initial.clone();
+                }
+            }
+            self.state.set(AwaitableState::Iter);
+        }
+        self.generator().set_running_async(true);
+        let result = self.generator().send(value);
+        let result = self.unwrap_value(result);
+        if result.is_err() {
+            self.state.set(AwaitableState::Closed);
+        }
+        result
+    }
+
+    /// refer to CPython(async_gen_asend_throw)
+    fn asend_throw(&self, exc: PyObject) -> PyResult {
+        if self.state.get() == AwaitableState::Closed {
+            return ExceptionObject::raise(ExceptionKind::RuntimeError, "cannot reuse already awaited __anext__()/asend()");
+        }
+        let result = self.generator().throw(exc);
+        let result = self.unwrap_value(result);
+        if result.is_err() {
+            self.state.set(AwaitableState::Closed);
+        }
+        result
+    }
+
+    /// refer to CPython(async_gen_athrow_send)
+    fn athrow_send(&self, value: PyObject) -> PyResult {
+        if self.state.get() == AwaitableState::Closed {
+            return ExceptionObject::raise(ExceptionKind::RuntimeError, "cannot reuse already awaited aclose()/athrow()");
+        }
+        if self.generator().is_finished() {
+            self.state.set(AwaitableState::Closed);
+            return Err(ExceptionObject::new(ExceptionKind::StopIteration, vec![]));
+        }
+        let result = if self.state.get() == AwaitableState::Init {
+            if self.generator().running_async() {
+                self.state.set(AwaitableState::Closed);
+                let method = if matches!(self.op, AsyncGenOp::Close) { "aclose" } else { "athrow" };
+                return ExceptionObject::raise(
+                    ExceptionKind::RuntimeError,
+                    &format!("{}(): asynchronous generator is already running", method)
+                );
+            }
+            if self.generator().closed_async() {
+                self.state.set(AwaitableState::Closed);
+                return Err(ExceptionObject::new(ExceptionKind::StopAsyncIteration, vec![]));
+            }
+            if value.borrow().object_type() != ObjectType::NONE {
+                return ExceptionObject::raise(ExceptionKind::RuntimeError, "can't send non-None value to a just-started coroutine");
+            }
+            self.state.set(AwaitableState::Iter);
+            self.generator().set_running_async(true);
+            match &self.op {
+                AsyncGenOp::Throw(exc) => {
+                    let result = self.generator().throw(exc.clone());
+                    let result = self.unwrap_value(result);
+                    if result.is_err() {
+                        self.state.set(AwaitableState::Closed);
+                    }
+                    return result;
+                },
+                _ => {
+                    self.generator().set_closed_async();
+                    let exc = ExceptionObject::new(ExceptionKind::GeneratorExit, vec![]);
+                    let result = self.generator().throw(exc);
+                    Self::yielded(result)
+                }
+            }
+        } else {
+            let result = self.generator().send(value);
+            if let AsyncGenOp::Throw(_) = self.op {
+                return self.unwrap_value(result);
+            }
+            Self::yielded(result)
+        };
+        self.closing_result(result)
+    }
+
+    /// refer to CPython(async_gen_athrow_throw)
+    fn athrow_throw(&self, exc: PyObject) -> PyResult {
+        if self.state.get() == AwaitableState::Closed {
+            return ExceptionObject::raise(ExceptionKind::RuntimeError, "cannot reuse already awaited aclose()/athrow()");
+        }
+        let result = self.generator().throw(exc);
+        if let AsyncGenOp::Throw(_) = self.op {
+            return self.unwrap_value(result);
+        }
+        self.closing_result(Self::yielded(result))
+    }
+
+    /// a step of `aclose()`: the generator may await on its way out but must not yield a value,
+    /// and its end is the end of the awaitable
+    fn closing_result(&self, result: PyResult) -> PyResult {
+        match result {
+            Ok(value) if wrapped_value(&value).is_some() => {
+                self.generator().set_running_async(false);
+                self.state.set(AwaitableState::Closed);
+                ExceptionObject::raise(ExceptionKind::RuntimeError, "async generator ignored GeneratorExit")
+            },
+            Ok(value) => Ok(value),
+            Err(err) => {
+                self.generator().set_running_async(false);
+                self.state.set(AwaitableState::Closed);
+                if ExceptionObject::is_exception(&err, ExceptionKind::StopAsyncIteration) || ExceptionObject::is_exception(&err, ExceptionKind::GeneratorExit) {
+                    return Err(ExceptionObject::new(ExceptionKind::StopIteration, vec![]));
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+impl PartialEq<Self> for AsyncGenStepObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for AsyncGenStepObject {}
+
+impl PyObjectTrait for AsyncGenStepObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+
+    fn to_iter(&self) -> PyResult {
+        Ok(self.this.upgrade().expect("awaitable should be alive"))
+    }
+
+    fn next(&self) -> PyResult<Option<PyObject>> {
+        match self.send(NoneObject::new()) {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if ExceptionObject::is_exception(&err, ExceptionKind::StopIteration) => Ok(None),
+            Err(err) => Err(err)
+        }
+    }
+
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "send" => step_send,
+            "throw" => step_throw,
+            "close" => step_close,
+            "__next__" => step_next,
+            "__iter__" | "__await__" => step_iter,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        Ok(format!("<{} object at {:#x}>", self.type_name(), object_address(self)))
+    }
+}
+
+impl fmt::Display for AsyncGenStepObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for AsyncGenStepObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "AsyncGenStepObject({} at {:#x})", self.type_name(), object_address(self))
+    }
+}
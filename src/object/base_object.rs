@@ -4,7 +4,9 @@ use std::fmt::{Debug, Display};
 use std::rc::Rc;
 use downcast_rs::{Downcast, impl_downcast};
 use dyn_eq::DynEq;
-use crate::utils::PyObject;
+use crate::object::{ExceptionKind, ExceptionObject};
+use crate::interpreter::Interpreter;
+use crate::utils::{hash, CmpOP, PyObject, PyResult};
 
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
 #[allow(unused, non_camel_case_types)]
@@ -42,7 +44,31 @@ pub enum ObjectType{
 
     // runtime object
     CALLABLE,
-    ITER
+    ITER,
+    METHOD,
+    SLICE,
+    EXCEPTION,
+    DICT_VIEW,
+    NOT_IMPLEMENTED,
+    RANGE,
+    TYPE,
+    INSTANCE,
+    CELL,
+    SUPER,
+    PROPERTY,
+    STATIC_METHOD,
+    CLASS_METHOD,
+    MEMBER_DESCRIPTOR,
+    GENERATOR,
+    ASYNC_GEN_VALUE,
+    ASYNC_GEN_ASEND,
+    ASYNC_GEN_ATHROW,
+    NATIVE_COROUTINE,
+    FUTURE,
+    QUEUE,
+    EVENT,
+    MODULE,
+    BYTE_ARRAY
 }
 impl Default for ObjectType {
     fn default() -> Self {
@@ -50,7 +76,63 @@ impl Default for ObjectType {
     }
 }
 
-const FLAG_REF: u8 = 0x80;
+impl ObjectType {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ObjectType::NULL => "NULL",
+            ObjectType::NONE => "NoneType",
+            ObjectType::FALSE | ObjectType::TRUE => "bool",
+            ObjectType::STOPITER => "StopIteration",
+            ObjectType::ELLIPSIS => "ellipsis",
+            ObjectType::INT | ObjectType::INT64 | ObjectType::LONG => "int",
+            ObjectType::FLOAT | ObjectType::BINARY_FLOAT => "float",
+            ObjectType::COMPLEX | ObjectType::BINARY_COMPLEX => "complex",
+            // since Python 3, TYPE_STRING is only used for bytes
+            ObjectType::STRING => "bytes",
+            ObjectType::INTERNED
+             | ObjectType::UNICODE
+             | ObjectType::ASCII
+             | ObjectType::ASCII_INTERNED
+             | ObjectType::SHORT_ASCII
+             | ObjectType::SHORT_ASCII_INTERNED => "str",
+            ObjectType::TUPLE | ObjectType::SMALL_TUPLE => "tuple",
+            ObjectType::LIST => "list",
+            ObjectType::DICT => "dict",
+            ObjectType::CODE => "code",
+            ObjectType::SET => "set",
+            ObjectType::FROZENSET => "frozenset",
+            ObjectType::CALLABLE => "function",
+            ObjectType::ITER => "iterator",
+            ObjectType::METHOD => "method",
+            ObjectType::SLICE => "slice",
+            ObjectType::EXCEPTION => "Exception",
+            ObjectType::DICT_VIEW => "dict_view",
+            ObjectType::NOT_IMPLEMENTED => "NotImplementedType",
+            ObjectType::RANGE => "range",
+            ObjectType::TYPE => "type",
+            ObjectType::INSTANCE => "object",
+            ObjectType::CELL => "cell",
+            ObjectType::SUPER => "super",
+            ObjectType::PROPERTY => "property",
+            ObjectType::STATIC_METHOD => "staticmethod",
+            ObjectType::CLASS_METHOD => "classmethod",
+            ObjectType::MEMBER_DESCRIPTOR => "member_descriptor",
+            ObjectType::GENERATOR => "generator",
+            ObjectType::ASYNC_GEN_VALUE => "async_generator_wrapped_value",
+            ObjectType::ASYNC_GEN_ASEND => "async_generator_asend",
+            ObjectType::ASYNC_GEN_ATHROW => "async_generator_athrow",
+            ObjectType::NATIVE_COROUTINE => "coroutine",
+            ObjectType::FUTURE => "Future",
+            ObjectType::QUEUE => "Queue",
+            ObjectType::EVENT => "Event",
+            ObjectType::MODULE => "module",
+            ObjectType::BYTE_ARRAY => "bytearray",
+            ObjectType::REF | ObjectType::STRINGREF | ObjectType::UNKNOWN => "unknown",
+        }
+    }
+}
+
+pub(crate) const FLAG_REF: u8 = 0x80;
 
 impl From<char> for ObjectType {
     fn from(value: char) -> Self {
@@ -95,19 +177,123 @@ impl From<char> for ObjectType {
 pub trait PyObjectTrait: Debug + Display + Downcast + DynEq {
     fn object_type(&self) -> ObjectType;
     fn base_object(&self) -> &BasePycObject;
-    fn hash_key(&self) -> String {
-        panic!("{}", format!("cannot hash {:?}", self.object_type()))
+    fn type_name(&self) -> String {
+        self.object_type().type_name().to_string()
+    }
+    /// hash() of the object, identity based by default like `object.__hash__`
+    fn hash(&self) -> PyResult<i64> {
+        Ok(hash::hash_pointer(object_address(self)))
     }
 
-    fn to_iter(&self) -> PyObject {
-        panic!("{}", format!("cannot convert {:?} to iter object", self.object_type()))
+    /// the rich comparison `self <op> other`, `None` plays the role of NotImplemented
+    fn compare(&self, _other: &PyObject, _op: &CmpOP) -> PyResult<Option<bool>> {
+        Ok(None)
     }
 
-    fn get_attr(&self, name: String) -> PyObject {
-        if let Some(attr) = self.base_object().method.get(&("$String_".to_string() + &name)) {
-            return attr.clone()
-        }
-        panic!("{}", format!("object {:?} doesn't have attribute {}", self.object_type(), name))
+    /// the rich comparison giving the object it evaluates to, which is not a bool when a user
+    /// method like `__eq__` returns something else, refer to CPython(PyObject_RichCompare)
+    fn rich_compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<PyObject>> {
+        Ok(self.compare(other, op)?.map(Interpreter::new_bool_object))
+    }
+
+    /// repr() of the object, refer to CPython(PyObject_Repr)
+    fn repr(&self) -> PyResult<String> {
+        Ok(self.to_string())
+    }
+
+    /// str() of the object, falls back to repr() like `object.__str__`
+    fn str(&self) -> PyResult<String> {
+        self.repr()
+    }
+
+    /// iter() of the object, refer to CPython(PyObject_GetIter)
+    fn to_iter(&self) -> PyResult {
+        ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("'{}' object is not iterable", self.type_name())
+        )
+    }
+
+    /// the next item of an iterator, `None` once it is exhausted, refer to CPython(tp_iternext)
+    fn next(&self) -> PyResult<Option<PyObject>> {
+        ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("'{}' object is not an iterator", self.type_name())
+        )
+    }
+
+    /// look up a function defined on the object's type, the returned callable expects `self` as its first argument
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        self.base_object().method.as_ref()?.get(name).cloned()
+    }
+
+    fn get_attr(&self, name: String) -> PyResult {
+        ExceptionObject::raise(
+            ExceptionKind::AttributeError,
+            &format!("'{}' object has no attribute '{}'", self.type_name(), name)
+        )
+    }
+
+    /// `obj.name = value`, objects have no writable attributes by default
+    fn set_attr(&self, name: String, _value: PyObject) -> PyResult<()> {
+        ExceptionObject::raise(
+            ExceptionKind::AttributeError,
+            &format!("'{}' object has no attribute '{}'", self.type_name(), name)
+        )
+    }
+
+    fn del_attr(&self, name: String) -> PyResult<()> {
+        ExceptionObject::raise(
+            ExceptionKind::AttributeError,
+            &format!("'{}' object has no attribute '{}'", self.type_name(), name)
+        )
+    }
+
+    fn get_item(&self, _key: &PyObject) -> PyResult {
+        ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("'{}' object is not subscriptable", self.type_name())
+        )
+    }
+
+    fn set_item(&mut self, _key: PyObject, _value: PyObject) -> PyResult<()> {
+        ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("'{}' object does not support item assignment", self.type_name())
+        )
+    }
+
+    fn del_item(&mut self, _key: &PyObject) -> PyResult<()> {
+        ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("'{}' object does not support item deletion", self.type_name())
+        )
+    }
+
+    /// the `in` operator, refer to CPython(PySequence_Contains)
+    fn contains(&self, _item: &PyObject) -> PyResult<bool> {
+        ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("argument of type '{}' is not iterable", self.type_name())
+        )
+    }
+
+    fn length(&self) -> PyResult<usize> {
+        ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("object of type '{}' has no len()", self.type_name())
+        )
+    }
+
+    /// the truth value used by conditions and `not`, objects are true by default, refer to CPython(PyObject_IsTrue)
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(true)
+    }
+
+    /// whether the type of the object is a proper subclass of the type of `other`, which gives
+    /// the object's reflected operator methods priority, refer to CPython(binary_op1)
+    fn is_proper_subtype_of(&self, _other: &PyObject) -> bool {
+        false
     }
 
     fn is_null(&self) -> bool {
@@ -115,19 +301,86 @@ pub trait PyObjectTrait: Debug + Display + Downcast + DynEq {
     }
 }
 impl_downcast!(PyObjectTrait);
+
+thread_local! {
+    static REPR_IN_PROGRESS: RefCell<Vec<usize>> = const { RefCell::new(vec![]) };
+}
+
+/// the identity of an object, which is also the address printed by default reprs
+pub fn object_address<T: ?Sized>(obj: &T) -> usize {
+    obj as *const T as *const () as usize
+}
+
+/// run `f` to build the repr of the object at `address`, unless the object is already being
+/// printed further up the stack, in which case `placeholder` is used, refer to CPython(Py_ReprEnter)
+pub fn repr_guard(address: usize, placeholder: &str, f: impl FnOnce() -> PyResult<String>) -> PyResult<String> {
+    let entered = REPR_IN_PROGRESS.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        if stack.contains(&address) {
+            return false;
+        }
+        stack.push(address);
+        true
+    });
+    if !entered {
+        return Ok(placeholder.to_string());
+    }
+    // the reprs of nested containers are built recursively
+    let res = Interpreter::enter_recursive_call(" while getting the repr of an object", f);
+    REPR_IN_PROGRESS.with(|stack| stack.borrow_mut().retain(|item| *item != address));
+    res
+}
+
+/// an unhashable type raises TypeError from hash(), refer to CPython(PyObject_HashNotImplemented)
+pub fn unhashable<T>(type_name: &str) -> PyResult<T> {
+    ExceptionObject::raise(ExceptionKind::TypeError, &format!("unhashable type: '{}'", type_name))
+}
+
+/// lexicographic comparison of two sequences, refer to CPython(tuplerichcompare)
+pub fn compare_sequences(lhs: &[PyObject], rhs: &[PyObject], op: &CmpOP) -> PyResult<bool> {
+    // find the first index where the items differ
+    let mut i = 0;
+    while i < lhs.len() && i < rhs.len() {
+        if !Interpreter::equals(&lhs[i], &rhs[i])? {
+            break;
+        }
+        i += 1;
+    }
+    if i >= lhs.len() || i >= rhs.len() {
+        // no more items to compare, the longer one is greater
+        return Ok(op.matches(lhs.len().cmp(&rhs.len())));
+    }
+    match op {
+        CmpOP::EQ => Ok(false),
+        CmpOP::NEQ => Ok(true),
+        op => Interpreter::compare(&lhs[i], &rhs[i], op)
+    }
+}
+
+/// the comma separated reprs of `values`
+pub fn join_repr(values: &[PyObject]) -> PyResult<String> {
+    let mut res = vec![];
+    for value in values {
+        res.push(Interpreter::repr(value)?);
+    }
+    Ok(res.join(", "))
+}
 dyn_eq::eq_trait_object!(PyObjectTrait);
 
 #[derive(Default)]
 pub struct BasePycObject {
     _type: ObjectType,
-    method: HashMap<String, PyObject>
+    /// allocated on the first insertion, most objects have no methods of their own and keep
+    /// just the pointer instead of an empty table inline
+    #[allow(clippy::box_collection)]
+    method: Option<Box<HashMap<String, PyObject>>>
 }
 
 impl BasePycObject {
     pub fn new(_type: ObjectType) -> Self {
         Self {
             _type,
-            method: HashMap::new()
+            method: None
         }
     }
 
@@ -137,7 +390,7 @@ impl BasePycObject {
     }
 
     pub fn insert_method(&mut self, key: &str, callable: PyObject) {
-        self.method.insert("$String_".to_string() + key, callable);
+        self.method.get_or_insert_with(Default::default).insert(key.to_string(), callable);
     }
 
     pub fn new_py_object<T>(obj: T) -> Rc<RefCell<T>> {
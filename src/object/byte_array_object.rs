@@ -0,0 +1,184 @@
+use crate::object::{bytes_contains, bytes_get_item, bytes_repr, check_args, unhashable, BasePycObject, BytesObject, CallableObject, ExceptionKind, ExceptionObject, IntObject, IterObject, NoneObject, StringObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::utils::{CmpOP, DowncastTrait, PyObject, PyResult};
+
+/// the mutable counterpart of bytes
+pub struct ByteArrayObject {
+    base: BasePycObject,
+    data: Vec<u8>
+}
+
+fn this_mut(args: &[PyObject]) -> std::cell::RefMut<'_, ByteArrayObject> {
+    args[0].downcast_refcell_mut::<ByteArrayObject>().expect("invalid bytearray object")
+}
+
+/// the bytes of an int iterable, refer to CPython(bytearray_extend)
+fn iterable_data(iterable: &PyObject) -> PyResult<Vec<u8>> {
+    if let Some(data) = BytesObject::extract(iterable) {
+        return Ok(data);
+    }
+    let mut data = vec![];
+    for item in IterObject::collect(iterable)? {
+        match IntObject::extract(&item) {
+            Some(value) => data.push(BytesObject::byte(value as i64)?),
+            None => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("'{}' object cannot be interpreted as an integer", item.borrow().type_name())
+            )
+        }
+    }
+    Ok(data)
+}
+
+fn bytearray_append(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "append", 1, 1)?;
+    let byte = match IntObject::extract(&args[1]) {
+        Some(value) => BytesObject::byte(value as i64)?,
+        None => return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("'{}' object cannot be interpreted as an integer", args[1].borrow().type_name())
+        )
+    };
+    this_mut(&args).data.push(byte);
+    Ok(NoneObject::new())
+}
+
+fn bytearray_extend(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "extend", 1, 1)?;
+    let data = iterable_data(&args[1])?;
+    this_mut(&args).data.extend(data);
+    Ok(NoneObject::new())
+}
+
+/// `+=` appends the data of a bytes-like object in place, refer to CPython(bytearray_iconcat)
+fn bytearray_iadd(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__iadd__", 1, 1)?;
+    let data = ByteArrayObject::concat_data(&args[1])?;
+    this_mut(&args).data.extend(data);
+    Ok(args[0].clone())
+}
+
+/// `*=` repeats the data in place, refer to CPython(bytearray_irepeat)
+fn bytearray_imul(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__imul__", 1, 1)?;
+    let times = match IntObject::extract(&args[1]) {
+        Some(times) => times.max(0) as usize,
+        None => return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("can't multiply sequence by non-int of type '{}'", args[1].borrow().type_name())
+        )
+    };
+    let mut bytes = this_mut(&args);
+    bytes.data = bytes.data.repeat(times);
+    drop(bytes);
+    Ok(args[0].clone())
+}
+
+impl ByteArrayObject {
+    pub fn new_from_vec(data: Vec<u8>) -> PyObject {
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::BYTE_ARRAY),
+            data
+        })
+    }
+
+    /// bytearray(), of a size, of bytes or of an int iterable, refer to CPython(bytearray___init___impl)
+    pub fn new_from_args(args: Vec<PyObject>) -> PyResult {
+        let source = match args.as_slice() {
+            [] => return Ok(Self::new_from_vec(vec![])),
+            [source] => source,
+            _ => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("bytearray() takes at most 1 argument ({} given)", args.len())
+            )
+        };
+        if source.downcast_refcell::<StringObject>().is_some() {
+            return ExceptionObject::raise(ExceptionKind::TypeError, "string argument without an encoding");
+        }
+        if let Some(size) = IntObject::extract(source) {
+            if size < 0 {
+                return ExceptionObject::raise(ExceptionKind::ValueError, "negative count");
+            }
+            return Ok(Self::new_from_vec(vec![0; size as usize]));
+        }
+        Ok(Self::new_from_vec(iterable_data(source)?))
+    }
+
+    pub fn data(&self) -> &Vec<u8> {
+        &self.data
+    }
+
+    /// the data of the right operand of a concatenation, which must be bytes-like
+    pub fn concat_data(other: &PyObject) -> PyResult<Vec<u8>> {
+        match BytesObject::extract(other) {
+            Some(data) => Ok(data),
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("can't concat {} to bytearray", other.borrow().type_name())
+            )
+        }
+    }
+}
+
+impl PartialEq<Self> for ByteArrayObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for ByteArrayObject {}
+
+impl PyObjectTrait for ByteArrayObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(!self.data.is_empty())
+    }
+    fn hash(&self) -> PyResult<i64> {
+        unhashable(&self.type_name())
+    }
+    /// compared by content with bytes and bytearrays alike
+    fn compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        Ok(BytesObject::extract(other).map(|other| op.matches(self.data.cmp(&other))))
+    }
+    fn repr(&self) -> PyResult<String> {
+        Ok(format!("bytearray({})", bytes_repr(&self.data)))
+    }
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "append" => bytearray_append,
+            "extend" => bytearray_extend,
+            "__iadd__" => bytearray_iadd,
+            "__imul__" => bytearray_imul,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+    fn get_item(&self, key: &PyObject) -> PyResult {
+        bytes_get_item(&self.data, key, Self::new_from_vec)
+    }
+    fn length(&self) -> PyResult<usize> {
+        Ok(self.data.len())
+    }
+    fn contains(&self, item: &PyObject) -> PyResult<bool> {
+        bytes_contains(&self.data, item)
+    }
+}
+
+impl fmt::Display for ByteArrayObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+
+impl fmt::Debug for ByteArrayObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "ByteArrayObject({})", self)
+    }
+}
@@ -0,0 +1,176 @@
+use crate::object::{normalize_index, BasePycObject, ByteArrayObject, CallableObject, ExceptionKind, ExceptionObject, IntObject, Real, SliceObject, StringObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::InputStream;
+use crate::utils::{hash, CmpOP, DowncastTrait, PyObject, PyResult};
+
+pub struct BytesObject {
+    base: BasePycObject,
+    data: Vec<u8>
+}
+
+fn bytes_decode(args: Vec<PyObject>) -> PyResult {
+    let bytes = args[0].downcast_refcell::<BytesObject>().expect("invalid bytes object");
+    let encoding = match args.get(1) {
+        Some(encoding) => StringObject::extract(encoding)?.to_lowercase(),
+        None => "utf-8".to_string()
+    };
+    match encoding.as_str() {
+        "utf-8" | "utf8" => match String::from_utf8(bytes.data.clone()) {
+            Ok(s) => Ok(StringObject::new_from_str(&s)),
+            Err(_) => ExceptionObject::raise(ExceptionKind::ValueError, "'utf-8' codec can't decode bytes")
+        },
+        "ascii" if bytes.data.is_ascii() => Ok(StringObject::new_from_str(&String::from_utf8_lossy(&bytes.data))),
+        "ascii" => ExceptionObject::raise(ExceptionKind::ValueError, "'ascii' codec can't decode bytes"),
+        "latin-1" | "latin1" => Ok(StringObject::new_from_str(&bytes.data.iter().map(|b| *b as char).collect::<String>())),
+        _ => ExceptionObject::raise(ExceptionKind::ValueError, &format!("unknown encoding: {}", encoding))
+    }
+}
+
+/// the literal form of `data`, refer to CPython(PyBytes_Repr)
+pub fn bytes_repr(data: &[u8]) -> String {
+    let quote = if data.contains(&b'\'') && !data.contains(&b'"') { b'"' } else { b'\'' };
+    let mut res = "b".to_string();
+    res.push(quote as char);
+    for b in data {
+        match *b {
+            b'\\' => res.push_str("\\\\"),
+            b'\n' => res.push_str("\\n"),
+            b'\r' => res.push_str("\\r"),
+            b'\t' => res.push_str("\\t"),
+            b if b == quote => {
+                res.push('\\');
+                res.push(b as char);
+            },
+            0x20..=0x7e => res.push(*b as char),
+            _ => res.push_str(&format!("\\x{:02x}", b))
+        }
+    }
+    res.push(quote as char);
+    res
+}
+
+/// `item in data`, an int is looked up as a single byte, bytes as a contiguous subsequence
+pub fn bytes_contains(data: &[u8], item: &PyObject) -> PyResult<bool> {
+    if let Some(sub) = BytesObject::extract(item) {
+        return Ok(sub.is_empty() || data.windows(sub.len()).any(|window| window == sub.as_slice()));
+    }
+    match Real::extract(item) {
+        Some(Real::Int(value)) => Ok(data.contains(&BytesObject::byte(value)?)),
+        _ => ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("a bytes-like object is required, not '{}'", item.borrow().type_name())
+        )
+    }
+}
+
+/// `data[key]`, an index giving an int and a slice giving the bytes `new` builds
+pub fn bytes_get_item(data: &[u8], key: &PyObject, new: fn(Vec<u8>) -> PyObject) -> PyResult {
+    if let Some(slice) = key.downcast_refcell::<SliceObject>() {
+        let positions = slice.positions(data.len())?;
+        return Ok(new(positions.into_iter().map(|i| data[i]).collect()))
+    }
+    let index = match IntObject::extract(key) {
+        Some(index) => index as i64,
+        None => return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("byte indices must be integers or slices, not {}", key.borrow().type_name())
+        )
+    };
+    match normalize_index(index, data.len()) {
+        Some(index) => Ok(IntObject::new_from_i32(data[index] as i32)),
+        None => ExceptionObject::raise(ExceptionKind::IndexError, "index out of range")
+    }
+}
+
+impl BytesObject {
+    pub fn new(stream: &mut InputStream) -> PyObject {
+        let length = stream.read_u32().unwrap();
+        let mut data = vec![];
+        for _ in 0..length {
+            data.push(stream.read().unwrap());
+        }
+        Self::new_from_vec(data)
+    }
+
+    pub fn new_from_vec(data: Vec<u8>) -> PyObject {
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new_from_char('s'),
+            data
+        })
+    }
+
+    pub fn data(&self) -> &Vec<u8> {
+        &self.data
+    }
+
+    /// the data of a bytes-like object, bytes or bytearray
+    pub fn extract(obj: &PyObject) -> Option<Vec<u8>> {
+        if let Some(bytes) = obj.downcast_refcell::<BytesObject>() {
+            return Some(bytes.data.clone());
+        }
+        obj.downcast_refcell::<ByteArrayObject>().map(|bytes| bytes.data().clone())
+    }
+
+    /// an int as a byte, which must be in range(0, 256)
+    pub fn byte(value: i64) -> PyResult<u8> {
+        u8::try_from(value).or_else(|_| ExceptionObject::raise(ExceptionKind::ValueError, "byte must be in range(0, 256)"))
+    }
+}
+
+impl PartialEq<Self> for BytesObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl Eq for BytesObject {}
+
+impl PyObjectTrait for BytesObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(self.length()? > 0)
+    }
+    fn hash(&self) -> PyResult<i64> {
+        Ok(hash::hash_bytes(&self.data))
+    }
+    fn compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        Ok(other.downcast_refcell::<BytesObject>().map(|other| op.matches(self.data.cmp(&other.data))))
+    }
+    fn repr(&self) -> PyResult<String> {
+        Ok(bytes_repr(&self.data))
+    }
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        match name {
+            "decode" => Some(CallableObject::new_native(name, Box::new(bytes_decode))),
+            _ => None
+        }
+    }
+    fn get_item(&self, key: &PyObject) -> PyResult {
+        bytes_get_item(&self.data, key, Self::new_from_vec)
+    }
+    fn length(&self) -> PyResult<usize> {
+        Ok(self.data.len())
+    }
+    fn contains(&self, item: &PyObject) -> PyResult<bool> {
+        bytes_contains(&self.data, item)
+    }
+}
+
+impl fmt::Display for BytesObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().unwrap())
+    }
+}
+
+impl fmt::Debug for BytesObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "BytesObject({})", self)
+    }
+}
@@ -1,57 +1,110 @@
-use std::cell::{Ref, RefCell};
-use crate::object::{BasePycObject, CodeObject};
+use crate::object::{object_address, BasePycObject, CodeObject, ExceptionKind, ExceptionObject};
 use crate::object::PyObjectTrait;
 use crate::object::ObjectType;
-use std::{fmt, mem};
-use std::ops::Deref;
-use std::rc::Rc;
-use crate::utils::PyObject;
+use std::fmt;
+use crate::utils::{DowncastTrait, PyObject, PyResult};
 
-type NativeFn = Box<dyn Fn(Vec<PyObject>) -> PyObject>;
+type NativeFn = Box<dyn Fn(Vec<PyObject>) -> PyResult>;
+/// keyword arguments in the order of the call
+pub type Kwargs = Vec<(String, PyObject)>;
+type NativeKwFn = Box<dyn Fn(Vec<PyObject>, Kwargs) -> PyResult>;
 
 pub struct CallableObject {
     base: BasePycObject,
-    code: Option<Rc<RefCell<CodeObject>>>,
+    name: String,
+    code: Option<PyObject>,
     defaults: Vec<PyObject>,
+    /// the default values of the keyword-only arguments
+    kw_defaults: Kwargs,
+    /// the dict of the module the function was defined in
+    globals: Option<PyObject>,
+    /// the cells of the variables of enclosing functions used by this one
+    closure: Vec<PyObject>,
     is_native: bool,
-    native_fn: Option<NativeFn>
+    native_fn: Option<NativeFn>,
+    native_kw_fn: Option<NativeKwFn>
 }
 
 impl CallableObject {
-    pub fn new(code: Ref<CodeObject>, defaults: Vec<PyObject>) -> PyObject {
-        let code = code.deref();
-        // FIXME: maybe we should use mem::swap?
-        let code:CodeObject = unsafe {mem::transmute_copy(code)};
+    pub fn new(code: PyObject, defaults: Vec<PyObject>, kw_defaults: Kwargs, closure: Vec<PyObject>, globals: PyObject) -> PyObject {
+        let name = code.downcast_refcell::<CodeObject>().expect("invalid code object").qualified_name();
         BasePycObject::new_py_object(Self {
             base: BasePycObject::new_from_char('C'),
-            code: Some(Rc::new(RefCell::new(code))),
+            name,
+            code: Some(code),
             defaults,
+            kw_defaults,
+            globals: Some(globals),
+            closure,
             is_native: false,
-            native_fn: None
+            native_fn: None,
+            native_kw_fn: None
         })
     }
 
-    pub fn new_native(f: NativeFn) -> PyObject {
+    pub fn new_native(name: &str, f: NativeFn) -> PyObject {
         BasePycObject::new_py_object(Self {
             base: BasePycObject::new_from_char('C'),
+            name: name.to_string(),
             code: None,
             defaults: vec![],
+            kw_defaults: vec![],
+            globals: None,
+            closure: vec![],
             is_native: true,
             native_fn: Some(f),
+            native_kw_fn: None
         })
     }
 
-    pub fn code(&self) -> Rc<RefCell<CodeObject>>{
+    /// a native function accepting keyword arguments, e.g. `list.sort(key=, reverse=)`
+    pub fn new_native_kw(name: &str, f: NativeKwFn) -> PyObject {
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new_from_char('C'),
+            name: name.to_string(),
+            code: None,
+            defaults: vec![],
+            kw_defaults: vec![],
+            globals: None,
+            closure: vec![],
+            is_native: true,
+            native_fn: None,
+            native_kw_fn: Some(f)
+        })
+    }
+
+    pub fn code(&self) -> PyObject {
         assert!(!self.is_native);
-        self.code.clone().unwrap().clone()
+        self.code.clone().unwrap()
     }
     pub fn defaults(&self) -> &Vec<PyObject>{
         assert!(!self.is_native);
         &self.defaults
     }
-    pub fn call_native(&self, args: Vec<PyObject>) -> PyObject {
+    pub fn kw_defaults(&self) -> &Kwargs {
+        assert!(!self.is_native);
+        &self.kw_defaults
+    }
+    pub fn closure(&self) -> &Vec<PyObject> {
+        assert!(!self.is_native);
+        &self.closure
+    }
+    pub fn globals(&self) -> PyObject {
+        assert!(!self.is_native);
+        self.globals.clone().unwrap()
+    }
+    pub fn call_native(&self, args: Vec<PyObject>) -> PyResult {
+        self.call_native_kw(args, vec![])
+    }
+
+    pub fn call_native_kw(&self, args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
         assert!(self.is_native);
-        assert!(self.native_fn.is_some());
+        if let Some(f) = self.native_kw_fn.as_ref() {
+            return f(args, kwargs);
+        }
+        if !kwargs.is_empty() {
+            return ExceptionObject::raise(ExceptionKind::TypeError, &format!("{}() takes no keyword arguments", self.name));
+        }
         let f = self.native_fn.as_ref().unwrap();
         f(args)
     }
@@ -59,11 +112,20 @@ impl CallableObject {
     pub fn is_native(&self) -> bool {
         self.is_native
     }
+
+    /// attach a function to a builtin type, e.g. the class method `dict.fromkeys`
+    pub fn insert_method(&mut self, name: &str, callable: PyObject) {
+        self.base.insert_method(name, callable);
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 impl PartialEq<Self> for CallableObject {
     fn eq(&self, other: &Self) -> bool {
-        self == other
+        std::ptr::eq(self, other)
     }
 }
 
@@ -77,15 +139,29 @@ impl PyObjectTrait for CallableObject {
     fn base_object(&self) -> &BasePycObject {
         &self.base
     }
+    fn type_name(&self) -> String {
+        if self.is_native {
+            "builtin_function_or_method".to_string()
+        } else {
+            "function".to_string()
+        }
+    }
+    fn repr(&self) -> PyResult<String> {
+        if self.is_native {
+            Ok(format!("<built-in function {}>", self.name))
+        } else {
+            Ok(format!("<function {} at {:#x}>", self.name, object_address(self)))
+        }
+    }
 }
 
 impl fmt::Display for CallableObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "")
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
     }
 }
 impl fmt::Debug for CallableObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "CallableObject")
+        write!(f, "CallableObject({})", self.name)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,68 @@
+use crate::object::{object_address, BasePycObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::utils::{PyObject, PyResult};
+
+/// a variable shared between a function and the functions nested in it, refer to CPython(PyCellObject)
+pub struct CellObject {
+    base: BasePycObject,
+    value: Option<PyObject>
+}
+
+impl CellObject {
+    pub fn new(value: Option<PyObject>) -> PyObject {
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::CELL),
+            value
+        })
+    }
+
+    /// the value of the variable, `None` while it is unbound
+    pub fn get(&self) -> Option<PyObject> {
+        self.value.clone()
+    }
+
+    pub fn set(&mut self, value: Option<PyObject>) {
+        self.value = value;
+    }
+}
+
+impl PartialEq<Self> for CellObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for CellObject {}
+
+impl PyObjectTrait for CellObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+    fn repr(&self) -> PyResult<String> {
+        match &self.value {
+            Some(value) => Ok(format!(
+                "<cell at {:#x}: {} object at {:#x}>",
+                object_address(self),
+                value.borrow().type_name(),
+                object_address(&*value.borrow())
+            )),
+            None => Ok(format!("<cell at {:#x}: empty>", object_address(self)))
+        }
+    }
+}
+
+impl fmt::Display for CellObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for CellObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "CellObject({:?})", self.value)
+    }
+}
@@ -0,0 +1,70 @@
+use crate::object::{BasePycObject, ExceptionKind, ExceptionObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::utils::{PyObject, PyResult};
+
+/// a function stored in a class which receives the class instead of the instance, refer to CPython(PyClassMethod_Type)
+pub struct ClassMethodObject {
+    base: BasePycObject,
+    func: PyObject
+}
+
+impl ClassMethodObject {
+    pub fn new_from_args(args: Vec<PyObject>) -> PyResult {
+        if args.len() != 1 {
+            return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("classmethod expected 1 argument, got {}", args.len())
+            );
+        }
+        Ok(BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::CLASS_METHOD),
+            func: args[0].clone()
+        }))
+    }
+
+    pub fn func(&self) -> PyObject {
+        self.func.clone()
+    }
+}
+
+impl PartialEq<Self> for ClassMethodObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for ClassMethodObject {}
+
+impl PyObjectTrait for ClassMethodObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+    fn get_attr(&self, name: String) -> PyResult {
+        match name.as_str() {
+            "__func__" | "__wrapped__" => Ok(self.func.clone()),
+            _ => ExceptionObject::raise(
+                ExceptionKind::AttributeError,
+                &format!("'classmethod' object has no attribute '{}'", name)
+            )
+        }
+    }
+    fn repr(&self) -> PyResult<String> {
+        Ok(format!("<classmethod({})>", self.func.borrow().repr()?))
+    }
+}
+
+impl fmt::Display for ClassMethodObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for ClassMethodObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "ClassMethodObject({:?})", self.func)
+    }
+}
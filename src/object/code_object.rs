@@ -1,4 +1,4 @@
-use crate::object::{BasePycObject, StringObject, TupleObject};
+use crate::object::{object_address, BasePycObject, BytesObject, StringObject, TupleObject};
 use crate::object::PyObjectTrait as PyObjectTrait;
 use crate::object::ObjectType;
 use std::fmt;
@@ -6,8 +6,31 @@ use crate::{InputStream, PycParser};
 use crate::utils::{ByteCode, DowncastTrait};
 use crate::utils::Magic::{self, *};
 
-use crate::utils::PyObject;
+use crate::utils::{PyObject, PyResult};
 type PyObjectOption = Option<PyObject>;
+
+/// the kinds of the local variables since Python 3.11, refer to CPython(Include/internal/pycore_code.h)
+pub const CO_FAST_CELL: u8 = 0x40;
+pub const CO_FAST_FREE: u8 = 0x80;
+
+/// the flags of a code object, refer to CPython(Include/cpython/code.h)
+pub const CO_VARARGS: u32 = 0x04;
+pub const CO_VARKEYWORDS: u32 = 0x08;
+pub const CO_COROUTINE: u32 = 0x80;
+pub const CO_ASYNC_GENERATOR: u32 = 0x200;
+
+/// an entry of the exception table: the exceptions raised by the instructions from `start` to
+/// `end` are handled at `target`, with the stack cut down to `depth` items, and the offset of the
+/// raising instruction pushed when `lasti` is set, refer to CPython(Objects/exception_handling_notes.txt)
+#[derive(Clone, Copy, Debug)]
+pub struct ExceptionHandler {
+    pub start: usize,
+    pub end: usize,
+    pub target: usize,
+    pub depth: usize,
+    pub lasti: bool
+}
+
 #[derive(Default)]
 #[allow(unused)]
 pub struct CodeObject {
@@ -82,7 +105,7 @@ impl CodeObject {
         } else if magic >= MAGIC2_3 {
             flags = Some(stream.read_u32().unwrap());
         }
-        code = Some(PycParser::marshal_object(stream, magic).downcast_refcell::<StringObject>().unwrap().data().clone());
+        code = Some(PycParser::marshal_object(stream, magic).downcast_refcell::<BytesObject>().unwrap().data().clone());
         constants = Some(PycParser::marshal_object(stream, magic));
         names = Some(PycParser::marshal_object(stream, magic));
 
@@ -193,6 +216,19 @@ impl CodeObject {
         let tuple = names.downcast_refcell::<TupleObject>().unwrap();
         tuple.values().clone()
     }
+    /// the names of the arguments and the other local variables
+    pub fn local_names(&self) -> Vec<String> {
+        let names = self.local_names.clone().unwrap();
+        let tuple = names.downcast_refcell::<TupleObject>().unwrap();
+        tuple.values().iter().map(|name| StringObject::extract(name).unwrap()).collect()
+    }
+    /// the kind flags of each local name, which tell the cells and free variables apart
+    pub fn local_kinds(&self) -> Vec<u8> {
+        match &self.local_kinds {
+            Some(kinds) => kinds.downcast_refcell::<BytesObject>().map(|kinds| kinds.data().clone()).unwrap_or_default(),
+            None => vec![]
+        }
+    }
     pub fn code(&self) -> Vec<u8> {
         self.code.clone().unwrap().clone()
     }
@@ -219,6 +255,73 @@ impl CodeObject {
     pub fn num_args(&self) -> u32 {
         self.num_args.unwrap()
     }
+
+    pub fn num_pos_only_args(&self) -> u32 {
+        self.num_pos_only_args.unwrap_or(0)
+    }
+
+    pub fn num_kw_only_args(&self) -> u32 {
+        self.num_kw_only_args.unwrap_or(0)
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags.unwrap_or(0)
+    }
+
+    /// the entries of the exception table with byte offsets, the table being a sequence of
+    /// varints of 6 bits whose bit 6 continues a number, refer to CPython(Lib/dis.py:_parse_exception_table)
+    pub fn exception_table(&self) -> Vec<ExceptionHandler> {
+        let table = match &self.exception_table {
+            Some(table) => table.downcast_refcell::<BytesObject>().map(|table| table.data().clone()).unwrap_or_default(),
+            None => vec![]
+        };
+        let mut bytes = table.into_iter();
+        let mut read_varint = || -> Option<usize> {
+            let mut byte = bytes.next()?;
+            let mut value = (byte & 63) as usize;
+            while byte & 64 != 0 {
+                byte = bytes.next()?;
+                value = value << 6 | (byte & 63) as usize;
+            }
+            Some(value)
+        };
+        let mut handlers = vec![];
+        while let Some(start) = read_varint() {
+            let (Some(length), Some(target), Some(depth_lasti)) = (read_varint(), read_varint(), read_varint()) else {
+                break;
+            };
+            handlers.push(ExceptionHandler {
+                start: start * 2,
+                end: (start + length) * 2,
+                target: target * 2,
+                depth: depth_lasti >> 1,
+                lasti: depth_lasti & 1 != 0
+            });
+        }
+        handlers
+    }
+
+    fn string_field(field: &PyObjectOption) -> String {
+        match field {
+            Some(obj) => obj.downcast_refcell::<StringObject>().map(|s| s.string()).unwrap_or_default(),
+            None => String::new()
+        }
+    }
+
+    pub fn name(&self) -> String {
+        Self::string_field(&self.name)
+    }
+
+    pub fn qualified_name(&self) -> String {
+        match self.qualified_name {
+            Some(_) => Self::string_field(&self.qualified_name),
+            None => self.name()
+        }
+    }
+
+    pub fn file_name(&self) -> String {
+        Self::string_field(&self.file_name)
+    }
 }
 
 impl PartialEq<Self> for CodeObject {
@@ -236,6 +339,15 @@ impl PyObjectTrait for CodeObject {
     fn base_object(&self) -> &BasePycObject {
         &self.base
     }
+    fn repr(&self) -> PyResult<String> {
+        Ok(format!(
+            "<code object {} at {:#x}, file {:?}, line {}>",
+            self.name(),
+            object_address(self),
+            self.file_name(),
+            self.first_line.unwrap_or(0)
+        ))
+    }
 }
 
 impl fmt::Debug for CodeObject {
@@ -257,7 +369,7 @@ impl fmt::Debug for CodeObject {
 }
 impl fmt::Display for CodeObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "")
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
     }
 }
 
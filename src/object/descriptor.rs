@@ -0,0 +1,77 @@
+// the descriptor protocol shared by the attribute lookups of instances, classes and super()
+use crate::object::{CallableObject, ClassMethodObject, ExceptionKind, ExceptionObject, InstanceObject, MemberDescriptorObject, MethodObject, NoneObject, PropertyObject, StaticMethodObject, TypeObject};
+use crate::interpreter::Interpreter;
+use crate::utils::{DowncastTrait, PyObject, PyResult};
+
+/// the method `name` of the class of a user-defined descriptor
+fn descriptor_method(attr: &PyObject, name: &str) -> Option<PyObject> {
+    let class = attr.downcast_refcell::<InstanceObject>().map(|instance| instance.class())?;
+    TypeObject::lookup(&class, name)
+}
+
+/// whether a class attribute takes precedence over the instance dict, refer to CPython(PyDescr_IsData)
+pub fn is_data_descriptor(attr: &PyObject) -> bool {
+    attr.downcast_refcell::<PropertyObject>().is_some()
+        || attr.downcast_refcell::<MemberDescriptorObject>().is_some()
+        || descriptor_method(attr, "__set__").is_some()
+        || descriptor_method(attr, "__delete__").is_some()
+}
+
+/// the value of the class attribute `attr` looked up on `obj`, or on the class `owner` itself when
+/// `obj` is `None`, refer to CPython(tp_descr_get)
+pub fn descriptor_get(attr: PyObject, obj: Option<&PyObject>, owner: &PyObject) -> PyResult {
+    if attr.downcast_refcell::<CallableObject>().is_some() {
+        return Ok(match obj {
+            Some(obj) => MethodObject::new(attr, obj.clone()),
+            None => attr
+        });
+    }
+    if let Some(func) = attr.downcast_refcell::<StaticMethodObject>().map(|method| method.func()) {
+        return Ok(func);
+    }
+    if let Some(func) = attr.downcast_refcell::<ClassMethodObject>().map(|method| method.func()) {
+        return Ok(MethodObject::new(func, owner.clone()));
+    }
+    if let Some(property) = attr.downcast_refcell::<PropertyObject>() {
+        return match obj {
+            Some(obj) => property.get(obj),
+            None => Ok(attr.clone())
+        };
+    }
+    if let Some(member) = attr.downcast_refcell::<MemberDescriptorObject>() {
+        return match obj {
+            Some(obj) => member.get(obj),
+            None => Ok(attr.clone())
+        };
+    }
+    if let Some(get) = descriptor_method(&attr, "__get__") {
+        let obj = obj.cloned().unwrap_or_else(NoneObject::new);
+        return Interpreter::call(&get, vec![attr, obj, owner.clone()]);
+    }
+    Ok(attr)
+}
+
+/// assign `value` through the data descriptor `attr`, or delete when `value` is `None`, which gives
+/// `None` when `attr` is no data descriptor, refer to CPython(tp_descr_set)
+pub fn descriptor_set(attr: &PyObject, obj: &PyObject, value: Option<PyObject>) -> Option<PyResult<()>> {
+    if let Some(property) = attr.downcast_refcell::<PropertyObject>() {
+        return Some(match value {
+            Some(value) => property.set(obj, value),
+            None => property.delete(obj)
+        });
+    }
+    if let Some(member) = attr.downcast_refcell::<MemberDescriptorObject>() {
+        return Some(member.set(obj, value));
+    }
+    if !is_data_descriptor(attr) {
+        return None;
+    }
+    let (name, args) = match value {
+        Some(value) => ("__set__", vec![attr.clone(), obj.clone(), value]),
+        None => ("__delete__", vec![attr.clone(), obj.clone()])
+    };
+    Some(match descriptor_method(attr, name) {
+        Some(method) => Interpreter::call(&method, args).map(|_| ()),
+        None => ExceptionObject::raise(ExceptionKind::AttributeError, name)
+    })
+}
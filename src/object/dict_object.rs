@@ -1,43 +1,403 @@
-use crate::object::{BasePycObject};
+use crate::object::{check_args, object_address, unhashable, repr_guard, BasePycObject, CallableObject, DictViewKind, DictViewObject, ExceptionKind, ExceptionObject, IterObject, NoneObject, TupleObject};
 use crate::object::PyObjectTrait;
 use crate::object::ObjectType;
 use std::fmt;
+use std::rc::Rc;
 use crate::{InputStream, PycParser};
-use crate::utils::{Magic, PyObject};
+use crate::interpreter::Interpreter;
+use crate::utils::{CmpOP, DowncastTrait, Magic, PyObject, PyResult};
+
+// the compact layout of CPython(Objects/dictobject.c): `indices` is the hash table pointing into
+// `entries`, which keeps the insertion order
+const EMPTY: isize = -1;
+const DUMMY: isize = -2;
+const PERTURB_SHIFT: u32 = 5;
+const MIN_SIZE: usize = 8;
 
 pub struct DictObject {
     base: BasePycObject,
-    entries: Vec<DictEntry>
+    indices: Vec<isize>,
+    // deleted entries are left as holes until the next resize
+    entries: Vec<Option<DictEntry>>,
+    used: usize
 }
 
+#[derive(Clone)]
 struct DictEntry {
+    hash: i64,
     key: PyObject,
     value: PyObject,
 }
 
+/// where a key lives, or would live, in the hash table
+struct Lookup {
+    slot: usize,
+    index: Option<usize>
+}
 
 impl DictObject {
     pub fn new(stream: &mut InputStream, magic: Magic) -> PyObject {
-        let mut entries = vec![];
+        let dict: PyObject = BasePycObject::new_py_object(Self::new_raw());
         loop {
             let key = PycParser::marshal_object(stream, magic);
             if key.borrow().object_type() == ObjectType::NULL {
                 break
             }
             let value = PycParser::marshal_object(stream, magic);
-            entries.push(DictEntry{key, value});
+            Self::insert(&dict, key, value).expect("dict keys of marshal data should be hashable");
         }
-        BasePycObject::new_py_object(Self {
+        dict
+    }
+
+    fn new_raw() -> Self {
+        Self {
             base: BasePycObject::new_from_char('{'),
-            entries
+            indices: vec![EMPTY; MIN_SIZE],
+            entries: vec![],
+            used: 0
+        }
+    }
+
+    pub fn new_from_items(items: Vec<(PyObject, PyObject)>) -> PyResult {
+        let dict: PyObject = BasePycObject::new_py_object(Self::new_raw());
+        for (key, value) in items {
+            Self::insert(&dict, key, value)?;
+        }
+        Ok(dict)
+    }
+
+    fn usable(&self) -> usize {
+        self.indices.len() * 2 / 3
+    }
+
+    /// probe the hash table of `dict` for `key`, refer to CPython(_Py_dict_lookup). The dict is not
+    /// borrowed while the `__eq__` of a key runs, which may change it, the probing starts over then
+    fn lookup(dict: &PyObject, key: &PyObject, hash: i64) -> PyResult<Lookup> {
+        'restart: loop {
+            let mask = dict.downcast_refcell::<DictObject>().expect("invalid dict object").indices.len() - 1;
+            let mut perturb = hash as u64;
+            let mut slot = hash as u64 as usize & mask;
+            let mut free_slot = None;
+            loop {
+                let candidate = {
+                    let this = dict.downcast_refcell::<DictObject>().expect("invalid dict object");
+                    match this.indices[slot] {
+                        EMPTY => return Ok(Lookup { slot: free_slot.unwrap_or(slot), index: None }),
+                        DUMMY => {
+                            free_slot = free_slot.or(Some(slot));
+                            None
+                        },
+                        index => {
+                            let entry = this.entries[index as usize].as_ref().expect("index should point to an entry");
+                            if entry.hash == hash && Rc::ptr_eq(&entry.key, key) {
+                                return Ok(Lookup { slot, index: Some(index as usize) });
+                            }
+                            (entry.hash == hash).then(|| (index as usize, entry.key.clone()))
+                        }
+                    }
+                };
+                if let Some((index, entry_key)) = candidate {
+                    let equal = Interpreter::equals(&entry_key, key)?;
+                    let this = dict.downcast_refcell::<DictObject>().expect("invalid dict object");
+                    let unchanged = this.indices.len() == mask + 1
+                        && this.indices[slot] == index as isize
+                        && this.entries[index].as_ref().is_some_and(|entry| Rc::ptr_eq(&entry.key, &entry_key));
+                    if !unchanged {
+                        continue 'restart;
+                    }
+                    if equal {
+                        return Ok(Lookup { slot, index: Some(index) });
+                    }
+                }
+                perturb >>= PERTURB_SHIFT;
+                slot = (slot * 5 + perturb as usize + 1) & mask;
+            }
+        }
+    }
+
+    /// the first empty slot on the probe sequence of `hash`
+    fn empty_slot(&self, hash: i64) -> usize {
+        let mask = self.indices.len() - 1;
+        let mut perturb = hash as u64;
+        let mut slot = hash as u64 as usize & mask;
+        while self.indices[slot] != EMPTY {
+            perturb >>= PERTURB_SHIFT;
+            slot = (slot * 5 + perturb as usize + 1) & mask;
+        }
+        slot
+    }
+
+    /// rebuild the table with room for the live entries, dropping the holes, refer to CPython(dictresize)
+    fn resize(&mut self) {
+        let mut size = MIN_SIZE;
+        while size < self.used * 3 {
+            size <<= 1;
+        }
+        let entries: Vec<Option<DictEntry>> = self.entries.drain(..).filter(|entry| entry.is_some()).collect();
+        self.indices = vec![EMPTY; size];
+        for (index, entry) in entries.iter().enumerate() {
+            let slot = self.empty_slot(entry.as_ref().unwrap().hash);
+            self.indices[slot] = index as isize;
+        }
+        self.entries = entries;
+    }
+
+    pub fn get(dict: &PyObject, key: &PyObject) -> PyResult<Option<PyObject>> {
+        let hash = key.borrow().hash()?;
+        let lookup = Self::lookup(dict, key, hash)?;
+        let dict = dict.downcast_refcell::<DictObject>().expect("invalid dict object");
+        Ok(lookup.index.map(|index| dict.entries[index].as_ref().unwrap().value.clone()))
+    }
+
+    pub fn insert(dict: &PyObject, key: PyObject, value: PyObject) -> PyResult<()> {
+        let hash = key.borrow().hash()?;
+        let lookup = Self::lookup(dict, &key, hash)?;
+        let mut dict = dict.downcast_refcell_mut::<DictObject>().expect("invalid dict object");
+        if let Some(index) = lookup.index {
+            // the original key is kept
+            dict.entries[index].as_mut().unwrap().value = value;
+            return Ok(());
+        }
+        let mut slot = lookup.slot;
+        if dict.entries.len() >= dict.usable() {
+            dict.resize();
+            slot = dict.empty_slot(hash);
+        }
+        dict.indices[slot] = dict.entries.len() as isize;
+        dict.entries.push(Some(DictEntry { hash, key, value }));
+        dict.used += 1;
+        Ok(())
+    }
+
+    /// remove `key` and return its value, if it is present
+    pub fn remove(dict: &PyObject, key: &PyObject) -> PyResult<Option<PyObject>> {
+        let hash = key.borrow().hash()?;
+        let lookup = Self::lookup(dict, key, hash)?;
+        let mut dict = dict.downcast_refcell_mut::<DictObject>().expect("invalid dict object");
+        Ok(lookup.index.map(|index| dict.remove_at(lookup.slot, index)))
+    }
+
+    fn remove_at(&mut self, slot: usize, index: usize) -> PyObject {
+        self.indices[slot] = DUMMY;
+        self.used -= 1;
+        self.entries[index].take().unwrap().value
+    }
+
+    pub fn clear(&mut self) {
+        self.indices = vec![EMPTY; MIN_SIZE];
+        self.entries.clear();
+        self.used = 0;
+    }
+
+    pub fn keys(&self) -> Vec<PyObject> {
+        self.entries.iter().flatten().map(|entry| entry.key.clone()).collect()
+    }
+
+    pub fn values(&self) -> Vec<PyObject> {
+        self.entries.iter().flatten().map(|entry| entry.value.clone()).collect()
+    }
+
+    /// the first entry at or after `position` in insertion order with its position, refer to CPython(dictiter_iternextitem)
+    pub fn next_entry(&self, position: usize) -> Option<(usize, PyObject, PyObject)> {
+        self.entries.iter().enumerate().skip(position)
+            .find_map(|(i, entry)| entry.as_ref().map(|entry| (i, entry.key.clone(), entry.value.clone())))
+    }
+
+    /// repr() of a dict object, whose entries are read one at a time so that it is not borrowed while
+    /// the `__repr__` of a key or value runs and may change it, refer to CPython(dict_repr)
+    pub fn repr_of(dict: &PyObject) -> PyResult<String> {
+        let address = object_address(&*dict.downcast_refcell::<DictObject>().expect("invalid dict object"));
+        repr_guard(address, "{...}", || {
+            let mut items = vec![];
+            let mut position = 0;
+            loop {
+                let entry = dict.downcast_refcell::<DictObject>().expect("invalid dict object").next_entry(position);
+                let Some((index, key, value)) = entry else {
+                    return Ok(format!("{{{}}}", items.join(", ")));
+                };
+                items.push(format!("{}: {}", Interpreter::repr(&key)?, Interpreter::repr(&value)?));
+                position = index + 1;
+            }
         })
     }
+
+    pub fn items(&self) -> Vec<(PyObject, PyObject)> {
+        self.entries.iter().flatten().map(|entry| (entry.key.clone(), entry.value.clone())).collect()
+    }
+
+    /// the key-value pairs of a mapping or of an iterable of pairs, refer to CPython(dict_update_arg)
+    pub fn extract_items(obj: &PyObject) -> PyResult<Vec<(PyObject, PyObject)>> {
+        if let Some(dict) = obj.downcast_refcell::<DictObject>() {
+            return Ok(dict.items());
+        }
+        let mut items = vec![];
+        for (i, pair) in IterObject::collect(obj)?.into_iter().enumerate() {
+            let values = match pair.borrow().length() {
+                Ok(_) => IterObject::collect(&pair)?,
+                Err(_) => return ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    &format!("cannot convert dictionary update sequence element #{} to a sequence", i)
+                )
+            };
+            if values.len() != 2 {
+                return ExceptionObject::raise(
+                    ExceptionKind::ValueError,
+                    &format!("dictionary update sequence element #{} has length {}; 2 is required", i, values.len())
+                );
+            }
+            items.push((values[0].clone(), values[1].clone()));
+        }
+        Ok(items)
+    }
+
+    /// `self |= other`, the items are collected first in case `other` is `self`
+    pub fn update(dict: &PyObject, other: &PyObject) -> PyResult<()> {
+        for (key, value) in Self::extract_items(other)? {
+            Self::insert(dict, key, value)?;
+        }
+        Ok(())
+    }
+
+    /// `lhs | rhs`, a new dict holding the items of both
+    pub fn merge(lhs: &DictObject, rhs: &DictObject) -> PyResult {
+        let mut items = lhs.items();
+        items.extend(rhs.items());
+        Self::new_from_items(items)
+    }
+
+    /// the `dict.fromkeys` class method, `args[0]` is the dict type or an instance
+    pub fn fromkeys(args: Vec<PyObject>) -> PyResult {
+        check_args(&args, "fromkeys", 1, 2)?;
+        let value = args.get(2).cloned().unwrap_or_else(NoneObject::new);
+        let keys = IterObject::collect(&args[1])?;
+        Self::new_from_items(keys.into_iter().map(|key| (key, value.clone())).collect())
+    }
+}
+
+fn this(args: &[PyObject]) -> PyObject {
+    assert!(args[0].downcast_refcell::<DictObject>().is_some(), "invalid dict object");
+    args[0].clone()
+}
+
+fn key_error<T>(key: &PyObject) -> PyResult<T> {
+    Err(ExceptionObject::new(ExceptionKind::KeyError, vec![key.clone()]))
+}
+
+// the subscripts are methods taking the dict itself, so that it is not borrowed while the `__eq__`
+// of a key runs
+
+fn dict_getitem(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__getitem__", 1, 1)?;
+    match DictObject::get(&this(&args), &args[1])? {
+        Some(value) => Ok(value),
+        None => key_error(&args[1])
+    }
+}
+
+fn dict_setitem(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__setitem__", 2, 2)?;
+    DictObject::insert(&this(&args), args[1].clone(), args[2].clone())?;
+    Ok(NoneObject::new())
+}
+
+fn dict_delitem(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__delitem__", 1, 1)?;
+    match DictObject::remove(&this(&args), &args[1])? {
+        Some(_) => Ok(NoneObject::new()),
+        None => key_error(&args[1])
+    }
+}
+
+fn dict_contains(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__contains__", 1, 1)?;
+    Ok(Interpreter::new_bool_object(DictObject::get(&this(&args), &args[1])?.is_some()))
+}
+
+fn dict_get(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "get", 1, 2)?;
+    let value = DictObject::get(&this(&args), &args[1])?;
+    Ok(value.or_else(|| args.get(2).cloned()).unwrap_or_else(NoneObject::new))
+}
+
+fn dict_setdefault(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "setdefault", 1, 2)?;
+    let dict = this(&args);
+    let value = DictObject::get(&dict, &args[1])?;
+    if let Some(value) = value {
+        return Ok(value);
+    }
+    let default = args.get(2).cloned().unwrap_or_else(NoneObject::new);
+    DictObject::insert(&dict, args[1].clone(), default.clone())?;
+    Ok(default)
+}
+
+fn dict_pop(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "pop", 1, 2)?;
+    let value = DictObject::remove(&this(&args), &args[1])?;
+    match (value, args.get(2)) {
+        (Some(value), _) => Ok(value),
+        (None, Some(default)) => Ok(default.clone()),
+        (None, None) => key_error(&args[1])
+    }
+}
+
+fn dict_popitem(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "popitem", 0, 0)?;
+    let dict = this(&args);
+    let mut dict = dict.downcast_refcell_mut::<DictObject>().unwrap();
+    // the last inserted item goes first, refer to CPython(dict_popitem_impl)
+    let last = dict.entries.iter().rposition(|entry| entry.is_some());
+    let index = match last {
+        Some(index) => index,
+        None => return ExceptionObject::raise(ExceptionKind::KeyError, "popitem(): dictionary is empty")
+    };
+    let key = dict.entries[index].as_ref().unwrap().key.clone();
+    let slot = dict.indices.iter().position(|i| *i == index as isize).expect("entry should be indexed");
+    let value = dict.remove_at(slot, index);
+    dict.entries.truncate(index);
+    Ok(TupleObject::new_from_values(vec![key, value]))
+}
+
+fn dict_update(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "update", 0, 1)?;
+    if let Some(other) = args.get(1) {
+        DictObject::update(&this(&args), other)?;
+    }
+    Ok(NoneObject::new())
+}
+
+fn dict_copy(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "copy", 0, 0)?;
+    let items = this(&args).downcast_refcell::<DictObject>().unwrap().items();
+    DictObject::new_from_items(items)
+}
+
+fn dict_clear(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "clear", 0, 0)?;
+    this(&args).downcast_refcell_mut::<DictObject>().unwrap().clear();
+    Ok(NoneObject::new())
+}
+
+fn view_impl(args: Vec<PyObject>, name: &str, kind: DictViewKind) -> PyResult {
+    check_args(&args, name, 0, 0)?;
+    Ok(DictViewObject::new(this(&args), kind))
+}
+
+fn dict_keys(args: Vec<PyObject>) -> PyResult {
+    view_impl(args, "keys", DictViewKind::Keys)
+}
+
+fn dict_values(args: Vec<PyObject>) -> PyResult {
+    view_impl(args, "values", DictViewKind::Values)
+}
+
+fn dict_items(args: Vec<PyObject>) -> PyResult {
+    view_impl(args, "items", DictViewKind::Items)
 }
 
 impl PartialEq<Self> for DictObject {
     fn eq(&self, other: &Self) -> bool {
-        // FIXME: should we compare every entry?
-        self == other
+        std::ptr::eq(self, other)
     }
 }
 
@@ -49,13 +409,81 @@ impl PyObjectTrait for DictObject {
     fn base_object(&self) -> &BasePycObject {
         &self.base
     }
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(self.length()? > 0)
+    }
+
+    fn hash(&self) -> PyResult<i64> {
+        unhashable(&self.type_name())
+    }
+
+    fn compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        if !matches!(op, CmpOP::EQ | CmpOP::NEQ) {
+            return Ok(None);
+        }
+        let (other_used, same_dict) = match other.downcast_refcell::<DictObject>() {
+            Some(other) => (other.used, std::ptr::eq(self, &*other)),
+            None => return Ok(None)
+        };
+        let mut equal = self.used == other_used;
+        if equal && !same_dict {
+            // refer to CPython(dict_equal), `other` is not borrowed while the values are compared
+            for (key, value) in self.items() {
+                let same = match DictObject::get(other, &key)? {
+                    Some(other_value) => Interpreter::equals(&value, &other_value)?,
+                    None => false
+                };
+                if !same {
+                    equal = false;
+                    break;
+                }
+            }
+        }
+        Ok(Some(equal == (*op == CmpOP::EQ)))
+    }
+
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "get" => dict_get,
+            "setdefault" => dict_setdefault,
+            "pop" => dict_pop,
+            "popitem" => dict_popitem,
+            "update" => dict_update,
+            "copy" => dict_copy,
+            "clear" => dict_clear,
+            "keys" => dict_keys,
+            "values" => dict_values,
+            "items" => dict_items,
+            "fromkeys" => DictObject::fromkeys,
+            "__getitem__" => dict_getitem,
+            "__setitem__" => dict_setitem,
+            "__delitem__" => dict_delitem,
+            "__contains__" => dict_contains,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+
+    fn length(&self) -> PyResult<usize> {
+        Ok(self.used)
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        repr_guard(object_address(self), "{...}", || {
+            let mut items = vec![];
+            for entry in self.entries.iter().flatten() {
+                items.push(format!("{}: {}", entry.key.borrow().repr()?, entry.value.borrow().repr()?));
+            }
+            Ok(format!("{{{}}}", items.join(", ")))
+        })
+    }
 }
 
 impl fmt::Debug for DictObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         writeln!(f, "DictObject({{").unwrap();
         // FIXME: considering the nested Dict situation
-        for entry in &self.entries {
+        for entry in self.entries.iter().flatten() {
             writeln!(f, "   {:?}: {:?}", entry.key, entry.value).unwrap();
         }
         writeln!(f, "}})")
@@ -63,13 +491,6 @@ impl fmt::Debug for DictObject {
 }
 impl fmt::Display for DictObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "{{").unwrap();
-        for (i, entry) in self.entries.iter().enumerate() {
-            if i != 0 {
-                write!(f, ", ").unwrap();
-            }
-            write!(f, "{}: {}", entry.key.borrow(), entry.value.borrow()).unwrap();
-        }
-        write!(f, "}}")
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
     }
 }
@@ -0,0 +1,126 @@
+use crate::object::{join_repr, object_address, repr_guard, unhashable, BasePycObject, DictObject, IterObject, TupleObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::interpreter::Interpreter;
+use crate::utils::{hash, DowncastTrait, PyObject, PyResult};
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum DictViewKind {
+    Keys,
+    Values,
+    Items,
+}
+
+impl DictViewKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DictViewKind::Keys => "dict_keys",
+            DictViewKind::Values => "dict_values",
+            DictViewKind::Items => "dict_items",
+        }
+    }
+}
+
+/// the live views returned by `dict.keys()`, `dict.values()` and `dict.items()`, they see later changes of the dict
+pub struct DictViewObject {
+    base: BasePycObject,
+    dict: PyObject,
+    kind: DictViewKind
+}
+
+impl DictViewObject {
+    pub fn new(dict: PyObject, kind: DictViewKind) -> PyObject {
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::DICT_VIEW),
+            dict,
+            kind
+        })
+    }
+
+    /// a snapshot of the items seen through the view
+    fn values(&self) -> Vec<PyObject> {
+        let dict = self.dict.downcast_refcell::<DictObject>().expect("invalid dict object");
+        match self.kind {
+            DictViewKind::Keys => dict.keys(),
+            DictViewKind::Values => dict.values(),
+            DictViewKind::Items => dict.items().into_iter()
+                .map(|(key, value)| TupleObject::new_from_values(vec![key, value]))
+                .collect()
+        }
+    }
+}
+
+impl PartialEq<Self> for DictViewObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for DictViewObject {}
+
+impl PyObjectTrait for DictViewObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(self.length()? > 0)
+    }
+    fn type_name(&self) -> String {
+        self.kind.name().to_string()
+    }
+    fn hash(&self) -> PyResult<i64> {
+        if self.kind == DictViewKind::Values {
+            return Ok(hash::hash_pointer(object_address(self)));
+        }
+        unhashable(&self.type_name())
+    }
+    fn to_iter(&self) -> PyResult {
+        IterObject::new_dict(self.dict.clone(), self.kind)
+    }
+    fn length(&self) -> PyResult<usize> {
+        self.dict.borrow().length()
+    }
+    fn contains(&self, item: &PyObject) -> PyResult<bool> {
+        match self.kind {
+            DictViewKind::Keys => Ok(DictObject::get(&self.dict, item)?.is_some()),
+            DictViewKind::Values => {
+                let values = self.dict.downcast_refcell::<DictObject>().expect("invalid dict object").values();
+                for value in values {
+                    if Interpreter::equals(&value, item)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            },
+            DictViewKind::Items => {
+                // refer to CPython(dictitems_contains)
+                let pair = match item.downcast_refcell::<TupleObject>() {
+                    Some(pair) if pair.values().len() == 2 => pair.values().clone(),
+                    _ => return Ok(false)
+                };
+                match DictObject::get(&self.dict, &pair[0])? {
+                    Some(value) => Interpreter::equals(&value, &pair[1]),
+                    None => Ok(false)
+                }
+            }
+        }
+    }
+    fn repr(&self) -> PyResult<String> {
+        repr_guard(object_address(self), "...", || Ok(format!("{}([{}])", self.kind.name(), join_repr(&self.values())?)))
+    }
+}
+
+impl fmt::Display for DictViewObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for DictViewObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "DictViewObject({:?})", self.kind)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::object::BasePycObject;
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::utils::PyObject;
+
+pub struct EllipsisObject {
+    base: BasePycObject,
+}
+
+thread_local! {
+    static ELLIPSIS: PyObject = BasePycObject::new_py_object(EllipsisObject {
+        base: BasePycObject::new_from_char('.'),
+    });
+}
+
+impl EllipsisObject {
+    /// the Ellipsis singleton, `...`
+    pub fn new() -> PyObject {
+        ELLIPSIS.with(|obj| obj.clone())
+    }
+}
+
+impl PartialEq<Self> for EllipsisObject {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for EllipsisObject {}
+
+impl PyObjectTrait for EllipsisObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+}
+
+impl fmt::Display for EllipsisObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Ellipsis")
+    }
+}
+impl fmt::Debug for EllipsisObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "EllipsisObject")
+    }
+}
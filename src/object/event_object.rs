@@ -0,0 +1,155 @@
+use crate::object::{check_args, future, object_address, BasePycObject, CallableObject, FutureObject, NativeCoroutineObject, NoneObject, Poll};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::cell::{Cell, Ref, RefCell};
+use std::fmt;
+use std::rc::{Rc, Weak};
+use crate::interpreter::Interpreter;
+use crate::utils::DowncastTrait;
+
+use crate::utils::{PyObject, PyResult};
+
+/// a flag tasks wait to be set, refer to asyncio(locks.Event)
+pub struct EventObject {
+    base: BasePycObject,
+    value: Cell<bool>,
+    /// the futures of the tasks waiting, resolved with True once the flag is set
+    waiters: RefCell<Vec<PyObject>>,
+    this: Weak<RefCell<EventObject>>
+}
+
+fn event(obj: &PyObject) -> Ref<'_, EventObject> {
+    obj.downcast_refcell::<EventObject>().expect("invalid event object")
+}
+
+fn event_is_set(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "is_set", 0, 0)?;
+    Ok(Interpreter::new_bool_object(event(&args[0]).is_set()))
+}
+
+fn event_set(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "set", 0, 0)?;
+    event(&args[0]).set()?;
+    Ok(NoneObject::new())
+}
+
+fn event_clear(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "clear", 0, 0)?;
+    event(&args[0]).clear();
+    Ok(NoneObject::new())
+}
+
+fn event_wait(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "wait", 0, 0)?;
+    Ok(event(&args[0]).wait())
+}
+
+impl EventObject {
+    pub fn new() -> PyObject {
+        Rc::new_cyclic(|this| RefCell::new(Self {
+            base: BasePycObject::new(ObjectType::EVENT),
+            value: Cell::new(false),
+            waiters: RefCell::new(vec![]),
+            this: this.clone()
+        }))
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.value.get()
+    }
+
+    /// set the flag and wake up every waiting task
+    pub fn set(&self) -> PyResult<()> {
+        if self.value.get() {
+            return Ok(());
+        }
+        self.value.set(true);
+        let waiters = self.waiters.borrow().clone();
+        for waiter in waiters {
+            let waiter = future(&waiter);
+            if !waiter.done() {
+                waiter.set_result(Interpreter::new_bool_object(true))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn clear(&self) {
+        self.value.set(false);
+    }
+
+    /// the coroutine of `wait()`, which returns True once the flag is set
+    pub fn wait(&self) -> PyObject {
+        let this: PyObject = self.this.upgrade().expect("event should be alive");
+        let mut waiter: Option<PyObject> = None;
+        NativeCoroutineObject::new("wait", Box::new(move |throw| {
+            let event = event(&this);
+            if let Some(fut) = waiter.take() {
+                event.waiters.borrow_mut().retain(|other| !Rc::ptr_eq(other, &fut));
+                if let Some(exc) = throw {
+                    return Err(exc);
+                }
+                return Ok(Poll::Ready(Interpreter::new_bool_object(true)));
+            }
+            if let Some(exc) = throw {
+                return Err(exc);
+            }
+            if event.is_set() {
+                return Ok(Poll::Ready(Interpreter::new_bool_object(true)));
+            }
+            let fut = FutureObject::new_future();
+            event.waiters.borrow_mut().push(fut.clone());
+            waiter = Some(fut.clone());
+            Ok(Poll::Pending(fut))
+        }))
+    }
+}
+
+impl PartialEq<Self> for EventObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for EventObject {}
+
+impl PyObjectTrait for EventObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "is_set" => event_is_set,
+            "set" => event_set,
+            "clear" => event_clear,
+            "wait" => event_wait,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+
+    /// refer to asyncio(locks.Event.__repr__)
+    fn repr(&self) -> PyResult<String> {
+        let mut extra = if self.value.get() { "set" } else { "unset" }.to_string();
+        let waiters = self.waiters.borrow().len();
+        if waiters > 0 {
+            extra = format!("{}, waiters:{}", extra, waiters);
+        }
+        Ok(format!("<asyncio.locks.Event object at {:#x} [{}]>", object_address(self), extra))
+    }
+}
+
+impl fmt::Display for EventObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for EventObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "EventObject({} at {:#x})", self.value.get(), object_address(self))
+    }
+}
@@ -0,0 +1,193 @@
+use crate::object::{join_repr, BasePycObject, NoneObject, StringObject, TupleObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::utils::{DowncastTrait, PyObject, PyResult};
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ExceptionKind {
+    AssertionError,
+    AttributeError,
+    BaseException,
+    CancelledError,
+    Exception,
+    GeneratorExit,
+    ImportError,
+    IndexError,
+    InvalidStateError,
+    KeyError,
+    LookupError,
+    ModuleNotFoundError,
+    NameError,
+    OverflowError,
+    QueueEmpty,
+    QueueFull,
+    RecursionError,
+    RuntimeError,
+    StopAsyncIteration,
+    StopIteration,
+    TimeoutError,
+    TypeError,
+    UnboundLocalError,
+    ValueError,
+    ZeroDivisionError,
+}
+
+impl ExceptionKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExceptionKind::AssertionError => "AssertionError",
+            ExceptionKind::AttributeError => "AttributeError",
+            ExceptionKind::BaseException => "BaseException",
+            ExceptionKind::CancelledError => "CancelledError",
+            ExceptionKind::Exception => "Exception",
+            ExceptionKind::GeneratorExit => "GeneratorExit",
+            ExceptionKind::ImportError => "ImportError",
+            ExceptionKind::IndexError => "IndexError",
+            ExceptionKind::InvalidStateError => "InvalidStateError",
+            ExceptionKind::KeyError => "KeyError",
+            ExceptionKind::LookupError => "LookupError",
+            ExceptionKind::ModuleNotFoundError => "ModuleNotFoundError",
+            ExceptionKind::NameError => "NameError",
+            ExceptionKind::OverflowError => "OverflowError",
+            ExceptionKind::QueueEmpty => "QueueEmpty",
+            ExceptionKind::QueueFull => "QueueFull",
+            ExceptionKind::RecursionError => "RecursionError",
+            ExceptionKind::RuntimeError => "RuntimeError",
+            ExceptionKind::StopAsyncIteration => "StopAsyncIteration",
+            ExceptionKind::StopIteration => "StopIteration",
+            ExceptionKind::TimeoutError => "TimeoutError",
+            ExceptionKind::TypeError => "TypeError",
+            ExceptionKind::UnboundLocalError => "UnboundLocalError",
+            ExceptionKind::ValueError => "ValueError",
+            ExceptionKind::ZeroDivisionError => "ZeroDivisionError",
+        }
+    }
+
+    /// the kind this one derives from, refer to CPython(Lib/test/exception_hierarchy.txt)
+    pub fn base(&self) -> Option<ExceptionKind> {
+        match self {
+            ExceptionKind::BaseException => None,
+            ExceptionKind::Exception | ExceptionKind::GeneratorExit | ExceptionKind::CancelledError => Some(ExceptionKind::BaseException),
+            ExceptionKind::IndexError | ExceptionKind::KeyError => Some(ExceptionKind::LookupError),
+            ExceptionKind::UnboundLocalError => Some(ExceptionKind::NameError),
+            ExceptionKind::ModuleNotFoundError => Some(ExceptionKind::ImportError),
+            ExceptionKind::RecursionError => Some(ExceptionKind::RuntimeError),
+            _ => Some(ExceptionKind::Exception)
+        }
+    }
+
+    /// whether `except base` catches exceptions of this kind
+    pub fn is_subclass(&self, base: ExceptionKind) -> bool {
+        let mut kind = Some(*self);
+        while let Some(current) = kind {
+            if current == base {
+                return true;
+            }
+            kind = current.base();
+        }
+        false
+    }
+}
+
+pub struct ExceptionObject {
+    base: BasePycObject,
+    kind: ExceptionKind,
+    args: Vec<PyObject>
+}
+
+impl ExceptionObject {
+    pub fn new(kind: ExceptionKind, args: Vec<PyObject>) -> PyObject {
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::EXCEPTION),
+            kind,
+            args
+        })
+    }
+
+    /// build an exception carrying a single message and return it as the error of a `PyResult`
+    pub fn raise<T>(kind: ExceptionKind, msg: &str) -> PyResult<T> {
+        Err(Self::new(kind, vec![StringObject::new_from_str(msg)]))
+    }
+
+    pub fn kind(&self) -> ExceptionKind {
+        self.kind
+    }
+
+    /// whether `err` is an exception of exactly `kind`
+    pub fn is_exception(err: &PyObject, kind: ExceptionKind) -> bool {
+        err.downcast_refcell::<ExceptionObject>().is_some_and(|err| err.kind() == kind)
+    }
+
+    #[allow(dead_code)]
+    pub fn args(&self) -> &Vec<PyObject> {
+        &self.args
+    }
+
+    /// the value a StopIteration carries, its first argument, refer to CPython(StopIteration_init)
+    pub fn value(&self) -> PyObject {
+        self.args.first().cloned().unwrap_or_else(NoneObject::new)
+    }
+}
+
+impl PartialEq<Self> for ExceptionObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for ExceptionObject {}
+
+impl PyObjectTrait for ExceptionObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+    fn type_name(&self) -> String {
+        self.kind.name().to_string()
+    }
+    fn repr(&self) -> PyResult<String> {
+        // refer to CPython(BaseException_repr)
+        if self.args.len() == 1 {
+            return Ok(format!("{}({})", self.kind.name(), self.args[0].borrow().repr()?));
+        }
+        Ok(format!("{}({})", self.kind.name(), join_repr(&self.args)?))
+    }
+    fn get_attr(&self, name: String) -> PyResult {
+        match name.as_str() {
+            "args" => Ok(TupleObject::new_from_values(self.args.clone())),
+            "value" if self.kind == ExceptionKind::StopIteration => Ok(self.value()),
+            _ => Self::raise(
+                ExceptionKind::AttributeError,
+                &format!("'{}' object has no attribute '{}'", self.kind.name(), name)
+            )
+        }
+    }
+    fn str(&self) -> PyResult<String> {
+        match self.args.len() {
+            0 => Ok(String::new()),
+            // a missing key is shown with its repr, refer to CPython(KeyError_str)
+            1 if self.kind == ExceptionKind::KeyError => self.args[0].borrow().repr(),
+            1 => self.args[0].borrow().str(),
+            _ => Ok(format!("({})", join_repr(&self.args)?))
+        }
+    }
+}
+
+impl fmt::Display for ExceptionObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.str().map_err(|_| fmt::Error)?)
+    }
+}
+
+impl fmt::Debug for ExceptionObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        // an exception without a message shows its name alone, refer to CPython(print_exception)
+        match self.str().map_err(|_| fmt::Error)?.as_str() {
+            "" => write!(f, "{}", self.kind.name()),
+            msg => write!(f, "{}: {}", self.kind.name(), msg)
+        }
+    }
+}
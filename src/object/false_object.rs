@@ -1,18 +1,23 @@
-use crate::object::BasePycObject;
+use crate::object::{BasePycObject, Real};
 use crate::object::PyObjectTrait;
 use crate::object::ObjectType;
 use std::fmt;
-use crate::utils::PyObject;
+use crate::utils::{CmpOP, PyObject, PyResult};
 
 pub struct FalseObject {
     base: BasePycObject,
 }
 
+thread_local! {
+    static FALSE: PyObject = BasePycObject::new_py_object(FalseObject {
+        base: BasePycObject::new_from_char('F'),
+    });
+}
+
 impl FalseObject {
+    /// the False singleton
     pub fn new() -> PyObject {
-        BasePycObject::new_py_object(Self {
-            base: BasePycObject::new_from_char('F'),
-        })
+        FALSE.with(|obj| obj.clone())
     }
 }
 impl PartialEq<Self> for FalseObject {
@@ -29,6 +34,16 @@ impl PyObjectTrait for FalseObject {
     fn base_object(&self) -> &BasePycObject {
         &self.base
     }
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(false)
+    }
+    fn hash(&self) -> PyResult<i64> {
+        Ok(0)
+    }
+    fn compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        // bool is an int subtype, so `True == 1` and `False < 0.5`
+        Ok(Real::Int(0).compare(other, op))
+    }
 }
 
 impl fmt::Debug for FalseObject {
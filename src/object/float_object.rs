@@ -0,0 +1,222 @@
+use std::cmp::Ordering;
+use crate::object::{object_address, BasePycObject, IntLongObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::InputStream;
+use crate::utils::{hash, CmpOP, DowncastTrait, PyObject, PyResult};
+
+pub struct FloatObject {
+    base: BasePycObject,
+    value: f64
+}
+
+impl FloatObject {
+    /// TYPE_BINARY_FLOAT, the 8 bytes of an IEEE 754 double
+    pub fn new(stream: &mut InputStream) -> PyObject {
+        Self::new_from_f64(f64::from_bits(stream.read_long().unwrap()))
+    }
+
+    /// TYPE_FLOAT, the repr of the value prefixed by its length
+    pub fn new_from_text(stream: &mut InputStream) -> PyObject {
+        let length = stream.read().unwrap();
+        let text: String = (0..length).map(|_| stream.read().unwrap() as char).collect();
+        Self::new_from_f64(text.parse().expect("invalid float literal"))
+    }
+
+    pub fn new_from_f64(value: f64) -> PyObject {
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::BINARY_FLOAT),
+            value
+        })
+    }
+}
+
+/// a real number operand of a comparison or an arithmetic operator, bool counts as an int like in CPython
+#[derive(Clone, Copy)]
+pub enum Real {
+    Int(i64),
+    Float(f64)
+}
+
+impl Real {
+    pub fn extract(obj: &PyObject) -> Option<Real> {
+        if let Some(value) = IntLongObject::extract(obj) {
+            return Some(Real::Int(value));
+        }
+        if let Some(float) = obj.downcast_refcell::<FloatObject>() {
+            return Some(Real::Float(float.value));
+        }
+        None
+    }
+
+    pub fn into_object(self) -> PyObject {
+        match self {
+            Real::Int(value) => IntLongObject::new_from_i64(value),
+            Real::Float(value) => FloatObject::new_from_f64(value)
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Real::Int(value) => *value as f64,
+            Real::Float(value) => *value
+        }
+    }
+
+    /// the exact order of two reals, `None` when a NaN is involved
+    pub fn partial_cmp(&self, other: &Real) -> Option<Ordering> {
+        match (self, other) {
+            (Real::Int(lhs), Real::Int(rhs)) => Some(lhs.cmp(rhs)),
+            (Real::Float(lhs), Real::Float(rhs)) => lhs.partial_cmp(rhs),
+            (Real::Int(lhs), Real::Float(rhs)) => compare_int_float(*lhs, *rhs),
+            (Real::Float(lhs), Real::Int(rhs)) => compare_int_float(*rhs, *lhs).map(Ordering::reverse),
+        }
+    }
+
+    /// the rich comparison of `self` with `other`, `None` if `other` is not a real number
+    pub fn compare(&self, other: &PyObject, op: &CmpOP) -> Option<bool> {
+        let other = Real::extract(other)?;
+        Some(match self.partial_cmp(&other) {
+            Some(ordering) => op.matches(ordering),
+            // every comparison with NaN is false except !=
+            None => *op == CmpOP::NEQ
+        })
+    }
+}
+
+/// compare an int with a float without rounding the int, refer to CPython(float_richcompare)
+fn compare_int_float(int: i64, float: f64) -> Option<Ordering> {
+    if float.is_nan() {
+        return None;
+    }
+    // 2^63 is exact as a double, everything beyond it is larger than any i64
+    const LIMIT: f64 = 9223372036854775808.0;
+    if float >= LIMIT {
+        return Some(Ordering::Less);
+    }
+    if float < -LIMIT {
+        return Some(Ordering::Greater);
+    }
+    let whole = float.trunc();
+    match int.cmp(&(whole as i64)) {
+        Ordering::Equal => 0.0.partial_cmp(&(float - whole)),
+        ordering => Some(ordering)
+    }
+}
+
+/// split `value` into a mantissa in [0.5, 1) and a power of two, like C's frexp
+fn frexp(value: f64) -> (f64, i32) {
+    if value == 0.0 || !value.is_finite() {
+        return (value, 0);
+    }
+    let bits = value.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i32;
+    if exponent == 0 {
+        // subnormal, scale it into the normal range first
+        let (mantissa, exponent) = frexp(value * 2f64.powi(54));
+        return (mantissa, exponent - 54);
+    }
+    let mantissa = f64::from_bits((bits & !(0x7ff << 52)) | (1022 << 52));
+    (mantissa, exponent - 1022)
+}
+
+/// the hash of a float agrees with the hash of an equal int, refer to CPython(_Py_HashDouble)
+fn hash_float(value: f64, address: usize) -> i64 {
+    if value.is_nan() {
+        return hash::hash_pointer(address);
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { 314159 } else { -314159 };
+    }
+    const BITS: i32 = 61;
+    let modulus = hash::MODULUS as u64;
+    let (mut mantissa, mut exponent) = frexp(value);
+    let sign = if mantissa < 0.0 { -1 } else { 1 };
+    mantissa = mantissa.abs();
+    let mut x: u64 = 0;
+    while mantissa != 0.0 {
+        x = ((x << 28) & modulus) | x >> (BITS - 28);
+        mantissa *= 268435456.0; // 2^28
+        exponent -= 28;
+        let y = mantissa as u64;
+        mantissa -= y as f64;
+        x += y;
+        if x >= modulus {
+            x -= modulus;
+        }
+    }
+    let exponent = if exponent >= 0 { exponent % BITS } else { BITS - 1 - ((-1 - exponent) % BITS) };
+    x = ((x << exponent) & modulus) | x >> (BITS - exponent);
+    let hash = x as i64 * sign;
+    if hash == -1 { -2 } else { hash }
+}
+
+/// the shortest repr that round-trips, switching to exponent notation like CPython(float_repr)
+fn float_repr(value: f64) -> String {
+    if value.is_nan() {
+        return "nan".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "inf" } else { "-inf" }.to_string();
+    }
+    // `{:e}` gives the shortest round-tripping digits, e.g. "-1.25e-7"
+    let formatted = format!("{:e}", value);
+    let (mantissa, exponent) = formatted.split_once('e').unwrap();
+    let exponent: i32 = exponent.parse().unwrap();
+    let (sign, mantissa) = match mantissa.strip_prefix('-') {
+        Some(mantissa) => ("-", mantissa),
+        None => ("", mantissa)
+    };
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    if !(-4..16).contains(&exponent) {
+        let mantissa = if digits.len() == 1 { digits } else { format!("{}.{}", &digits[..1], &digits[1..]) };
+        return format!("{}{}e{}{:02}", sign, mantissa, if exponent < 0 { '-' } else { '+' }, exponent.abs());
+    }
+    let point = exponent + 1;
+    let body = if point <= 0 {
+        format!("0.{}{}", "0".repeat(-point as usize), digits)
+    } else if point as usize >= digits.len() {
+        format!("{}{}.0", digits, "0".repeat(point as usize - digits.len()))
+    } else {
+        format!("{}.{}", &digits[..point as usize], &digits[point as usize..])
+    };
+    format!("{}{}", sign, body)
+}
+
+impl PartialEq<Self> for FloatObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for FloatObject {}
+
+impl PyObjectTrait for FloatObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(self.value != 0.0)
+    }
+    fn hash(&self) -> PyResult<i64> {
+        Ok(hash_float(self.value, object_address(self)))
+    }
+    fn compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        Ok(Real::Float(self.value).compare(other, op))
+    }
+}
+
+impl fmt::Display for FloatObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", float_repr(self.value))
+    }
+}
+impl fmt::Debug for FloatObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "FloatObject({})", self.value)
+    }
+}
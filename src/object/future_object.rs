@@ -0,0 +1,564 @@
+use crate::object::{check_args, make_exception, object_address, opt_arg, BasePycObject, CallableObject, ExceptionKind, ExceptionObject, IntLongObject, Kwargs, ListObject, NoneObject, StringObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::cell::{Cell, Ref, RefCell};
+use std::fmt;
+use std::rc::{Rc, Weak};
+use crate::asyncio;
+use crate::interpreter::Interpreter;
+use crate::utils::DowncastTrait;
+
+use crate::utils::{PyObject, PyResult};
+
+/// refer to asyncio(futures._PENDING, _CANCELLED, _FINISHED)
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum FutureState {
+    Pending,
+    Cancelled,
+    Finished
+}
+
+/// what is called once a future is done, with the future as its argument
+pub enum FutureCallback {
+    /// `Task.__wakeup` of the task awaiting the future
+    Wakeup(PyObject),
+    /// resolve the waiter with `None` unless it is done, refer to asyncio(tasks._release_waiter)
+    Release(PyObject),
+    /// count a child of a `gather()` as finished, refer to asyncio(gather._done_callback)
+    Gather(PyObject),
+    /// stop the event loop running until the future is done
+    StopLoop,
+    /// a callable given to `add_done_callback()`
+    Callable(PyObject)
+}
+
+/// the coroutine a task drives, refer to asyncio(tasks.Task)
+pub struct TaskState {
+    coro: PyObject,
+    name: RefCell<String>,
+    /// the future the coroutine is blocked on
+    waiter: RefCell<Option<PyObject>>,
+    /// whether the coroutine is to get CancelledError at its next step
+    must_cancel: Cell<bool>
+}
+
+/// the children of the future `gather()` returns, refer to asyncio(tasks._GatheringFuture)
+pub struct GatherState {
+    children: RefCell<Vec<PyObject>>,
+    /// the number of distinct children
+    count: Cell<usize>,
+    finished: Cell<usize>,
+    return_exceptions: bool,
+    cancel_requested: Cell<bool>
+}
+
+enum FutureKind {
+    Future,
+    Task(TaskState),
+    Gather(GatherState)
+}
+
+/// the eventual result of an asynchronous operation, tasks and the future of `gather()` being
+/// futures as well, refer to asyncio(futures.Future)
+pub struct FutureObject {
+    base: BasePycObject,
+    kind: FutureKind,
+    state: Cell<FutureState>,
+    result: RefCell<Option<PyObject>>,
+    exception: RefCell<Option<PyObject>>,
+    cancel_message: RefCell<Option<PyObject>>,
+    /// the CancelledError a cancelled task ended with, which `result()` raises again
+    cancelled_exc: RefCell<Option<PyObject>>,
+    callbacks: RefCell<Vec<FutureCallback>>,
+    this: Weak<RefCell<FutureObject>>
+}
+
+thread_local! {
+    /// the number in the default names of tasks, refer to asyncio(tasks._task_name_counter)
+    static TASK_COUNTER: Cell<u64> = const { Cell::new(0) };
+}
+
+pub fn future(obj: &PyObject) -> Ref<'_, FutureObject> {
+    obj.downcast_refcell::<FutureObject>().expect("invalid future object")
+}
+
+fn future_done(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "done", 0, 0)?;
+    Ok(Interpreter::new_bool_object(future(&args[0]).done()))
+}
+
+fn future_cancelled(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "cancelled", 0, 0)?;
+    Ok(Interpreter::new_bool_object(future(&args[0]).cancelled()))
+}
+
+fn future_result(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "result", 0, 0)?;
+    future(&args[0]).result()
+}
+
+fn future_exception(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "exception", 0, 0)?;
+    Ok(future(&args[0]).exception()?.unwrap_or_else(NoneObject::new))
+}
+
+fn future_cancel(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    let mut msg = opt_arg(&args, 1);
+    for (name, value) in kwargs {
+        match name.as_str() {
+            "msg" => msg = Some(value).filter(|value| value.borrow().object_type() != ObjectType::NONE),
+            _ => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("cancel() got an unexpected keyword argument '{}'", name)
+            )
+        }
+    }
+    check_args(&args, "cancel", 0, 1)?;
+    Ok(Interpreter::new_bool_object(FutureObject::cancel(&args[0], msg)))
+}
+
+fn future_set_result(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "set_result", 1, 1)?;
+    future(&args[0]).set_result(args[1].clone())?;
+    Ok(NoneObject::new())
+}
+
+fn future_set_exception(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "set_exception", 1, 1)?;
+    let exc = match Interpreter::exception_kind(&args[1]) {
+        Some(kind) => ExceptionObject::new(kind, vec![]),
+        None => args[1].clone()
+    };
+    if exc.downcast_refcell::<ExceptionObject>().is_none() {
+        return ExceptionObject::raise(ExceptionKind::TypeError, "invalid exception object");
+    }
+    future(&args[0]).set_exception(exc)?;
+    Ok(NoneObject::new())
+}
+
+fn future_add_done_callback(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "add_done_callback", 1, 1)?;
+    future(&args[0]).add_callback(FutureCallback::Callable(args[1].clone()));
+    Ok(NoneObject::new())
+}
+
+fn future_remove_done_callback(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "remove_done_callback", 1, 1)?;
+    let fut = future(&args[0]);
+    let mut callbacks = fut.callbacks.borrow_mut();
+    let before = callbacks.len();
+    callbacks.retain(|callback| !matches!(callback, FutureCallback::Callable(fn_) if Rc::ptr_eq(fn_, &args[1])));
+    Ok(IntLongObject::new_from_i64((before - callbacks.len()) as i64))
+}
+
+/// the step of `await future`: the future itself goes up to the task until it is done, then its
+/// result is the value of the `await`, refer to asyncio(futures.Future.__await__)
+fn future_next(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__next__", 0, 0)?;
+    let fut = future(&args[0]);
+    if !fut.done() {
+        return Ok(args[0].clone());
+    }
+    let result = fut.result()?;
+    Err(ExceptionObject::new(ExceptionKind::StopIteration, vec![result]))
+}
+
+fn future_send(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "send", 1, 1)?;
+    future_next(vec![args[0].clone()])
+}
+
+fn future_throw(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "throw", 1, 3)?;
+    Err(make_exception(&args[1], args.get(2))?)
+}
+
+fn future_await(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__await__", 0, 0)?;
+    Ok(args[0].clone())
+}
+
+fn task_get_name(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "get_name", 0, 0)?;
+    let name = future(&args[0]).task().name.borrow().clone();
+    Ok(StringObject::new_from_string(name))
+}
+
+fn task_set_name(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "set_name", 1, 1)?;
+    let name = args[1].borrow().str()?;
+    *future(&args[0]).task().name.borrow_mut() = name;
+    Ok(NoneObject::new())
+}
+
+fn task_get_coro(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "get_coro", 0, 0)?;
+    Ok(future(&args[0]).task().coro.clone())
+}
+
+impl FutureObject {
+    fn new(kind: FutureKind) -> PyObject {
+        Rc::new_cyclic(|this| RefCell::new(Self {
+            base: BasePycObject::new(ObjectType::FUTURE),
+            kind,
+            state: Cell::new(FutureState::Pending),
+            result: RefCell::new(None),
+            exception: RefCell::new(None),
+            cancel_message: RefCell::new(None),
+            cancelled_exc: RefCell::new(None),
+            callbacks: RefCell::new(vec![]),
+            this: this.clone()
+        }))
+    }
+
+    pub fn new_future() -> PyObject {
+        Self::new(FutureKind::Future)
+    }
+
+    /// a task named "Task-<n>" unless `name` is given, its first step is scheduled by the caller
+    pub fn new_task(coro: PyObject, name: Option<String>) -> PyObject {
+        let name = name.unwrap_or_else(|| {
+            let count = TASK_COUNTER.with(|counter| {
+                counter.set(counter.get() + 1);
+                counter.get()
+            });
+            format!("Task-{}", count)
+        });
+        Self::new(FutureKind::Task(TaskState {
+            coro,
+            name: RefCell::new(name),
+            waiter: RefCell::new(None),
+            must_cancel: Cell::new(false)
+        }))
+    }
+
+    pub fn new_gather(return_exceptions: bool) -> PyObject {
+        Self::new(FutureKind::Gather(GatherState {
+            children: RefCell::new(vec![]),
+            count: Cell::new(0),
+            finished: Cell::new(0),
+            return_exceptions,
+            cancel_requested: Cell::new(false)
+        }))
+    }
+
+    fn this(&self) -> PyObject {
+        self.this.upgrade().expect("future should be alive")
+    }
+
+    pub fn is_task(&self) -> bool {
+        matches!(self.kind, FutureKind::Task(_))
+    }
+
+    fn task(&self) -> &TaskState {
+        match &self.kind {
+            FutureKind::Task(task) => task,
+            _ => panic!("future is not a task")
+        }
+    }
+
+    fn gather(&self) -> &GatherState {
+        match &self.kind {
+            FutureKind::Gather(gather) => gather,
+            _ => panic!("future is not a gathering future")
+        }
+    }
+
+    pub fn coro(&self) -> PyObject {
+        self.task().coro.clone()
+    }
+
+    pub fn set_waiter(&self, waiter: Option<PyObject>) {
+        *self.task().waiter.borrow_mut() = waiter;
+    }
+
+    pub fn must_cancel(&self) -> bool {
+        self.task().must_cancel.get()
+    }
+
+    pub fn set_must_cancel(&self, must_cancel: bool) {
+        self.task().must_cancel.set(must_cancel);
+    }
+
+    pub fn cancel_message(&self) -> Option<PyObject> {
+        self.cancel_message.borrow().clone()
+    }
+
+    pub fn done(&self) -> bool {
+        self.state.get() != FutureState::Pending
+    }
+
+    pub fn cancelled(&self) -> bool {
+        self.state.get() == FutureState::Cancelled
+    }
+
+    /// add a child to the future of `gather()`, `distinct` when it was not given before
+    pub fn add_child(&self, child: PyObject, distinct: bool) {
+        let gather = self.gather();
+        gather.children.borrow_mut().push(child);
+        if distinct {
+            gather.count.set(gather.count.get() + 1);
+        }
+    }
+
+    /// refer to asyncio(futures.Future._make_cancelled_error)
+    pub fn make_cancelled_error(&self) -> PyObject {
+        if let Some(exc) = self.cancelled_exc.borrow_mut().take() {
+            return exc;
+        }
+        let args = self.cancel_message.borrow().iter().cloned().collect();
+        ExceptionObject::new(ExceptionKind::CancelledError, args)
+    }
+
+    /// refer to asyncio(futures.Future.result)
+    pub fn result(&self) -> PyResult {
+        match self.state.get() {
+            FutureState::Cancelled => Err(self.make_cancelled_error()),
+            FutureState::Pending => Err(ExceptionObject::new(
+                ExceptionKind::InvalidStateError,
+                vec![StringObject::new_from_str("Result is not set.")]
+            )),
+            FutureState::Finished => match self.exception.borrow().clone() {
+                Some(exc) => Err(exc),
+                None => Ok(self.result.borrow().clone().expect("a finished future has a result"))
+            }
+        }
+    }
+
+    pub fn exception(&self) -> PyResult<Option<PyObject>> {
+        match self.state.get() {
+            FutureState::Cancelled => Err(self.make_cancelled_error()),
+            FutureState::Pending => Err(ExceptionObject::new(
+                ExceptionKind::InvalidStateError,
+                vec![StringObject::new_from_str("Exception is not set.")]
+            )),
+            FutureState::Finished => Ok(self.exception.borrow().clone())
+        }
+    }
+
+    fn check_pending(&self) -> PyResult<()> {
+        if self.done() {
+            let state = if self.cancelled() { "CANCELLED" } else { "FINISHED" };
+            let msg = format!("{}: {}", state, self.repr()?);
+            return Err(ExceptionObject::new(ExceptionKind::InvalidStateError, vec![StringObject::new_from_string(msg)]));
+        }
+        Ok(())
+    }
+
+    pub fn set_result(&self, result: PyObject) -> PyResult<()> {
+        self.check_pending()?;
+        *self.result.borrow_mut() = Some(result);
+        self.state.set(FutureState::Finished);
+        self.schedule_callbacks();
+        Ok(())
+    }
+
+    pub fn set_exception(&self, exc: PyObject) -> PyResult<()> {
+        self.check_pending()?;
+        let is_stop = ExceptionObject::is_exception(&exc, ExceptionKind::StopIteration);
+        if is_stop {
+            return ExceptionObject::raise(ExceptionKind::TypeError, "StopIteration interacts badly with generators and cannot be raised into a Future");
+        }
+        *self.exception.borrow_mut() = Some(exc);
+        self.state.set(FutureState::Finished);
+        self.schedule_callbacks();
+        Ok(())
+    }
+
+    /// the end of a task whose coroutine let CancelledError out
+    pub fn set_cancelled(&self, exc: Option<PyObject>) {
+        match exc {
+            Some(exc) => {
+                *self.cancelled_exc.borrow_mut() = Some(exc);
+                self.cancel_future(None);
+            },
+            None => {
+                self.cancel_future(self.cancel_message());
+            }
+        }
+    }
+
+    /// refer to asyncio(futures.Future.cancel)
+    fn cancel_future(&self, msg: Option<PyObject>) -> bool {
+        if self.done() {
+            return false;
+        }
+        self.state.set(FutureState::Cancelled);
+        *self.cancel_message.borrow_mut() = msg;
+        self.schedule_callbacks();
+        true
+    }
+
+    /// cancel a future, a task gets CancelledError thrown into its coroutine and the future of
+    /// `gather()` cancels its children, refer to asyncio(tasks.Task.cancel, _GatheringFuture.cancel)
+    pub fn cancel(obj: &PyObject, msg: Option<PyObject>) -> bool {
+        let fut = future(obj);
+        match &fut.kind {
+            FutureKind::Future => fut.cancel_future(msg),
+            FutureKind::Task(task) => {
+                if fut.done() {
+                    return false;
+                }
+                let waiter = task.waiter.borrow().clone();
+                if waiter.is_some_and(|waiter| Self::cancel(&waiter, msg.clone())) {
+                    return true;
+                }
+                task.must_cancel.set(true);
+                *fut.cancel_message.borrow_mut() = msg;
+                true
+            },
+            FutureKind::Gather(gather) => {
+                if fut.done() {
+                    return false;
+                }
+                let children = gather.children.borrow().clone();
+                let mut cancelled = false;
+                for child in children {
+                    cancelled |= Self::cancel(&child, msg.clone());
+                }
+                if cancelled {
+                    gather.cancel_requested.set(true);
+                }
+                cancelled
+            }
+        }
+    }
+
+    pub fn add_callback(&self, callback: FutureCallback) {
+        if self.done() {
+            asyncio::call_soon_callback(callback, self.this());
+        } else {
+            self.callbacks.borrow_mut().push(callback);
+        }
+    }
+
+    /// refer to asyncio(futures.Future.__schedule_callbacks)
+    fn schedule_callbacks(&self) {
+        let callbacks = std::mem::take(&mut *self.callbacks.borrow_mut());
+        for callback in callbacks {
+            asyncio::call_soon_callback(callback, self.this());
+        }
+    }
+
+    /// a child of the future of `gather()` is done, refer to asyncio(gather._done_callback)
+    pub fn child_done(&self, child: &PyObject) -> PyResult<()> {
+        let gather = self.gather();
+        gather.finished.set(gather.finished.get() + 1);
+        if self.done() {
+            return Ok(());
+        }
+        if !gather.return_exceptions {
+            let child = future(child);
+            if child.cancelled() {
+                return self.set_exception(child.make_cancelled_error());
+            }
+            if let Some(exc) = child.exception()? {
+                return self.set_exception(exc);
+            }
+        }
+        if gather.finished.get() < gather.count.get() {
+            return Ok(());
+        }
+        let children = gather.children.borrow().clone();
+        let mut results = vec![];
+        for child in &children {
+            let child = future(child);
+            let result = if child.cancelled() {
+                let msg = child.cancel_message().unwrap_or_else(|| StringObject::new_from_str(""));
+                ExceptionObject::new(ExceptionKind::CancelledError, vec![msg])
+            } else {
+                match child.exception()? {
+                    Some(exc) => exc,
+                    None => child.result()?
+                }
+            };
+            results.push(result);
+        }
+        if gather.cancel_requested.get() {
+            let exc = future(children.last().expect("gather has children")).make_cancelled_error();
+            return self.set_exception(exc);
+        }
+        self.set_result(ListObject::new_from_values(results))
+    }
+}
+
+impl PartialEq<Self> for FutureObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for FutureObject {}
+
+impl PyObjectTrait for FutureObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+
+    fn type_name(&self) -> String {
+        match self.kind {
+            FutureKind::Future => "Future",
+            FutureKind::Task(_) => "Task",
+            FutureKind::Gather(_) => "_GatheringFuture"
+        }.to_string()
+    }
+
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        if name == "cancel" {
+            return Some(CallableObject::new_native_kw(name, Box::new(future_cancel)));
+        }
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "done" => future_done,
+            "cancelled" => future_cancelled,
+            "result" => future_result,
+            "exception" => future_exception,
+            "add_done_callback" => future_add_done_callback,
+            "remove_done_callback" => future_remove_done_callback,
+            "__await__" | "__iter__" => future_await,
+            "__next__" => future_next,
+            "send" => future_send,
+            "throw" => future_throw,
+            "set_result" if !self.is_task() => future_set_result,
+            "set_exception" if !self.is_task() => future_set_exception,
+            "get_name" if self.is_task() => task_get_name,
+            "set_name" if self.is_task() => task_set_name,
+            "get_coro" if self.is_task() => task_get_coro,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+
+    /// refer to asyncio(base_futures._future_repr_info, base_tasks._task_repr_info)
+    fn repr(&self) -> PyResult<String> {
+        let mut info = vec![match self.state.get() {
+            FutureState::Pending => "pending",
+            FutureState::Cancelled => "cancelled",
+            FutureState::Finished => "finished"
+        }.to_string()];
+        if let FutureKind::Task(task) = &self.kind {
+            info.push(format!("name='{}'", task.name.borrow()));
+            info.push(format!("coro={}", task.coro.borrow().repr()?));
+        }
+        if self.state.get() == FutureState::Finished {
+            match self.exception.borrow().as_ref() {
+                Some(exc) => info.push(format!("exception={}", exc.borrow().repr()?)),
+                None => info.push(format!("result={}", self.result.borrow().as_ref().expect("a finished future has a result").borrow().repr()?))
+            }
+        }
+        Ok(format!("<{} {}>", self.type_name(), info.join(" ")))
+    }
+}
+
+impl fmt::Display for FutureObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for FutureObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "FutureObject({} at {:#x})", self.type_name(), object_address(self))
+    }
+}
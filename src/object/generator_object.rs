@@ -0,0 +1,520 @@
+use crate::object::{check_args, object_address, AsyncGenStepObject, BasePycObject, CallableObject, ExceptionKind, ExceptionObject, NativeCoroutineObject, NoneObject, StringObject, CO_ASYNC_GENERATOR, CO_COROUTINE};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::cell::{Cell, Ref, RefCell};
+use std::fmt;
+use std::rc::{Rc, Weak};
+use crate::frame::Frame;
+use crate::interpreter::{FrameExit, Interpreter};
+use crate::utils::{ByteCode, DowncastTrait};
+
+use crate::utils::{PyObject, PyResult};
+
+/// refer to CPython(PyFrameState)
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum GeneratorState {
+    Created,
+    Suspended,
+    Running,
+    Closed
+}
+
+/// the kinds of function whose calls give a generator object
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum GeneratorKind {
+    Generator,
+    /// an `async def` function, refer to CPython(PyCoroObject)
+    Coroutine,
+    /// an `async def` function with yields, refer to CPython(PyAsyncGenObject)
+    AsyncGenerator
+}
+
+impl GeneratorKind {
+    fn name(&self) -> &'static str {
+        match self {
+            GeneratorKind::Generator => "generator",
+            GeneratorKind::Coroutine => "coroutine",
+            GeneratorKind::AsyncGenerator => "async generator"
+        }
+    }
+}
+
+/// the result of calling a generator function, which runs the frame of the call from one yield to
+/// the next, refer to CPython(PyGenObject)
+pub struct GeneratorObject {
+    base: BasePycObject,
+    kind: GeneratorKind,
+    /// the suspended frame, taken out while it runs and dropped once it has finished
+    frame: RefCell<Option<Box<Frame>>>,
+    state: Cell<GeneratorState>,
+    /// whether an awaitable of an async generator is running it, refer to CPython(ag_running_async)
+    running_async: Cell<bool>,
+    /// whether an async generator has let StopAsyncIteration or GeneratorExit out, refer to CPython(ag_closed)
+    closed_async: Cell<bool>,
+    name: String,
+    qualname: String,
+    this: Weak<RefCell<GeneratorObject>>
+}
+
+fn generator(obj: &PyObject) -> Ref<'_, GeneratorObject> {
+    obj.downcast_refcell::<GeneratorObject>().expect("invalid generator object")
+}
+
+fn gen_send(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "send", 1, 1)?;
+    let exit = generator(&args[0]).send(args[1].clone())?;
+    step_result(exit)
+}
+
+/// `throw(type[, value])` or `throw(exception)`, the traceback argument is accepted and ignored
+fn gen_throw(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "throw", 1, 3)?;
+    let exc = make_exception(&args[1], args.get(2))?;
+    let exit = generator(&args[0]).throw(exc)?;
+    step_result(exit)
+}
+
+fn gen_close(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "close", 0, 0)?;
+    generator(&args[0]).close()?;
+    Ok(NoneObject::new())
+}
+
+fn gen_next(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__next__", 0, 0)?;
+    let exit = generator(&args[0]).send(NoneObject::new())?;
+    step_result(exit)
+}
+
+fn gen_iter(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__iter__", 0, 0)?;
+    Ok(args[0].clone())
+}
+
+fn agen_aiter(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__aiter__", 0, 0)?;
+    Ok(args[0].clone())
+}
+
+fn agen_anext(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__anext__", 0, 0)?;
+    Ok(AsyncGenStepObject::new_send(args[0].clone(), NoneObject::new()))
+}
+
+fn agen_asend(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "asend", 1, 1)?;
+    Ok(AsyncGenStepObject::new_send(args[0].clone(), args[1].clone()))
+}
+
+fn agen_athrow(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "athrow", 1, 3)?;
+    let exc = make_exception(&args[1], args.get(2))?;
+    Ok(AsyncGenStepObject::new_throw(args[0].clone(), Some(exc)))
+}
+
+fn agen_aclose(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "aclose", 0, 0)?;
+    Ok(AsyncGenStepObject::new_throw(args[0].clone(), None))
+}
+
+
+/// the StopIteration a generator returning `value` raises, refer to CPython(_PyGen_SetStopIterationValue)
+pub fn stop_iteration(value: PyObject) -> PyObject {
+    if value.borrow().object_type() == ObjectType::NONE {
+        return ExceptionObject::new(ExceptionKind::StopIteration, vec![]);
+    }
+    ExceptionObject::new(ExceptionKind::StopIteration, vec![value])
+}
+
+/// the value a step of a generator yields, the value it returns comes with StopIteration
+fn step_result(exit: FrameExit) -> PyResult {
+    match exit {
+        FrameExit::Yield(value) => Ok(value),
+        FrameExit::Return(value) => Err(stop_iteration(value)),
+        FrameExit::Generator => unreachable!("a resumed frame does not return a generator")
+    }
+}
+
+/// call a `__next__`, `send` or `throw` method, the StopIteration it raises carries the value returned
+fn call_step(method: &PyObject, args: Vec<PyObject>) -> PyResult<FrameExit> {
+    match Interpreter::call(method, args) {
+        Ok(value) => Ok(FrameExit::Yield(value)),
+        Err(err) if ExceptionObject::is_exception(&err, ExceptionKind::StopIteration) => {
+            let value = err.downcast_refcell::<ExceptionObject>().expect("invalid exception object").value();
+            Ok(FrameExit::Return(value))
+        },
+        Err(err) => Err(err)
+    }
+}
+
+/// the exception `throw(typ, value)` raises, refer to CPython(_gen_throw)
+pub fn make_exception(typ: &PyObject, value: Option<&PyObject>) -> PyResult {
+    let value = value.filter(|value| value.borrow().object_type() != ObjectType::NONE);
+    if typ.downcast_refcell::<ExceptionObject>().is_some() {
+        if value.is_some() {
+            return ExceptionObject::raise(ExceptionKind::TypeError, "instance exception may not have a separate value");
+        }
+        return Ok(typ.clone());
+    }
+    match Interpreter::exception_kind(typ) {
+        Some(kind) => Ok(ExceptionObject::new(kind, value.into_iter().cloned().collect())),
+        None => ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("exceptions must be classes or instances deriving from BaseException, not {}", typ.borrow().type_name())
+        )
+    }
+}
+
+impl GeneratorObject {
+    pub fn new(frame: Box<Frame>) -> PyObject {
+        let name = frame.name().to_string();
+        let qualname = frame.qualname().to_string();
+        let kind = if frame.flags() & CO_COROUTINE != 0 {
+            GeneratorKind::Coroutine
+        } else if frame.flags() & CO_ASYNC_GENERATOR != 0 {
+            GeneratorKind::AsyncGenerator
+        } else {
+            GeneratorKind::Generator
+        };
+        Rc::new_cyclic(|this| RefCell::new(Self {
+            base: BasePycObject::new(ObjectType::GENERATOR),
+            kind,
+            frame: RefCell::new(Some(frame)),
+            state: Cell::new(GeneratorState::Created),
+            running_async: Cell::new(false),
+            closed_async: Cell::new(false),
+            name,
+            qualname,
+            this: this.clone()
+        }))
+    }
+
+    pub fn kind(&self) -> GeneratorKind {
+        self.kind
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.state.get() == GeneratorState::Closed
+    }
+
+    pub fn running_async(&self) -> bool {
+        self.running_async.get()
+    }
+
+    pub fn set_running_async(&self, running: bool) {
+        self.running_async.set(running);
+    }
+
+    pub fn closed_async(&self) -> bool {
+        self.closed_async.get()
+    }
+
+    pub fn set_closed_async(&self) {
+        self.closed_async.set(true);
+    }
+
+    /// resume the generator, `value` being the result of the yield it stopped at, refer to CPython(gen_send_ex2)
+    pub fn send(&self, value: PyObject) -> PyResult<FrameExit> {
+        match self.state.get() {
+            GeneratorState::Created if value.borrow().object_type() != ObjectType::NONE => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("can't send non-None value to a just-started {}", self.kind.name())
+            ),
+            GeneratorState::Running => return self.already_executing(),
+            GeneratorState::Closed if self.kind == GeneratorKind::Coroutine => return Self::reused(),
+            GeneratorState::Closed => return Ok(FrameExit::Return(NoneObject::new())),
+            _ => {}
+        }
+        let mut frame = self.frame.borrow_mut().take().expect("a suspended generator keeps its frame");
+        // a just-started frame pops the value right after RETURN_GENERATOR
+        frame.push(value);
+        self.run(frame, None)
+    }
+
+    /// run the frame until it yields or finishes, with `throw` raised where it stopped
+    fn run(&self, mut frame: Box<Frame>, throw: Option<PyObject>) -> PyResult<FrameExit> {
+        self.state.set(GeneratorState::Running);
+        match Interpreter::resume_frame(&mut frame, throw) {
+            Ok(FrameExit::Yield(value)) => {
+                *self.frame.borrow_mut() = Some(frame);
+                self.state.set(GeneratorState::Suspended);
+                Ok(FrameExit::Yield(value))
+            },
+            Ok(exit) => {
+                self.state.set(GeneratorState::Closed);
+                Ok(exit)
+            },
+            Err(err) => {
+                self.state.set(GeneratorState::Closed);
+                Err(self.escaped(err))
+            }
+        }
+    }
+
+    fn already_executing<T>(&self) -> PyResult<T> {
+        ExceptionObject::raise(ExceptionKind::ValueError, &format!("{} already executing", self.kind.name()))
+    }
+
+    fn reused<T>() -> PyResult<T> {
+        ExceptionObject::raise(ExceptionKind::RuntimeError, "cannot reuse already awaited coroutine")
+    }
+
+    /// send `value` to the receiver of SEND, generators are resumed directly and other iterators
+    /// get `__next__()` or `send(value)` called
+    pub fn send_to(receiver: &PyObject, value: PyObject) -> PyResult<FrameExit> {
+        if let Some(generator) = receiver.downcast_refcell::<GeneratorObject>() {
+            return generator.send(value);
+        }
+        if let Some(coro) = receiver.downcast_refcell::<NativeCoroutineObject>() {
+            return coro.send(value);
+        }
+        if value.borrow().object_type() == ObjectType::NONE {
+            return call_step(&Interpreter::load_attr(receiver, "__next__")?, vec![]);
+        }
+        call_step(&Interpreter::load_attr(receiver, "send")?, vec![value])
+    }
+
+    /// throw `exc` into a generator or any iterator with a `throw` method, the exception is raised
+    /// as it is when there is no such method
+    pub fn throw_to(receiver: &PyObject, exc: PyObject) -> PyResult<FrameExit> {
+        match Self::throw_into(receiver, exc.clone())? {
+            Some(exit) => Ok(exit),
+            None => Err(exc)
+        }
+    }
+
+    /// raise `exc` where the generator is suspended, the iterator a `yield from` waits on gets it
+    /// first, refer to CPython(_gen_throw)
+    pub fn throw(&self, exc: PyObject) -> PyResult<FrameExit> {
+        if self.state.get() == GeneratorState::Running {
+            return self.already_executing();
+        }
+        if let Some(delegate) = self.delegate() {
+            self.state.set(GeneratorState::Running);
+            let result = if ExceptionObject::is_exception(&exc, ExceptionKind::GeneratorExit) {
+                // the delegate is closed, then the generator itself gets the GeneratorExit
+                Self::close_iter(&delegate).map(|_| None)
+            } else {
+                Self::throw_into(&delegate, exc.clone())
+            };
+            self.state.set(GeneratorState::Suspended);
+            match result {
+                Ok(Some(FrameExit::Yield(value))) => return Ok(FrameExit::Yield(value)),
+                Ok(Some(FrameExit::Return(value))) => {
+                    // the delegate has finished, the generator goes on after its `yield from`
+                    self.leave_yield_from();
+                    return self.send(value);
+                },
+                Ok(_) => {},
+                Err(err) => {
+                    self.frame.borrow_mut().as_mut().expect("a suspended generator keeps its frame").pop();
+                    return self.raise(err);
+                }
+            }
+        }
+        self.raise(exc)
+    }
+
+    /// stop the generator by raising GeneratorExit where it is suspended, refer to CPython(gen_close)
+    pub fn close(&self) -> PyResult<()> {
+        match self.state.get() {
+            GeneratorState::Running => return self.already_executing(),
+            GeneratorState::Suspended => {},
+            GeneratorState::Created | GeneratorState::Closed => {
+                self.frame.borrow_mut().take();
+                self.state.set(GeneratorState::Closed);
+                return Ok(());
+            }
+        }
+        match self.throw(ExceptionObject::new(ExceptionKind::GeneratorExit, vec![])) {
+            Ok(FrameExit::Yield(_)) => ExceptionObject::raise(ExceptionKind::RuntimeError, &format!("{} ignored GeneratorExit", self.kind.name())),
+            Ok(_) => Ok(()),
+            Err(err) if ExceptionObject::is_exception(&err, ExceptionKind::GeneratorExit) || ExceptionObject::is_exception(&err, ExceptionKind::StopIteration) => Ok(()),
+            Err(err) => Err(err)
+        }
+    }
+
+    /// the iterator a `yield from` or `await` of the suspended frame delegates to, which is on the
+    /// top of the stack while the frame waits at a RESUME after a SEND, refer to CPython(_PyGen_yf)
+    pub fn delegate(&self) -> Option<PyObject> {
+        let frame = self.frame.borrow();
+        let frame = frame.as_ref()?;
+        let resume: u8 = ByteCode::RESUME.into();
+        match (frame.peek_code(0), frame.peek_code(1)) {
+            (Some(code), Some(arg)) if code == resume && arg >= 2 => Some(frame.peek(1)),
+            _ => None
+        }
+    }
+
+    /// pop the finished delegate and jump past the SEND loop of its `yield from`, the SEND being
+    /// two instructions before the RESUME the frame waits at
+    fn leave_yield_from(&self) {
+        let mut frame = self.frame.borrow_mut();
+        let frame = frame.as_mut().expect("a suspended generator keeps its frame");
+        frame.pop();
+        let send = frame.peek_code(-3).expect("yield from should be a SEND loop");
+        frame.jump_offset(send as i64 * 2 - 2);
+    }
+
+    /// throw `exc` into the delegate of a `yield from`, `None` when it has no `throw` method
+    fn throw_into(delegate: &PyObject, exc: PyObject) -> PyResult<Option<FrameExit>> {
+        if let Some(generator) = delegate.downcast_refcell::<GeneratorObject>() {
+            return generator.throw(exc).map(Some);
+        }
+        if let Some(coro) = delegate.downcast_refcell::<NativeCoroutineObject>() {
+            return coro.throw(exc).map(Some);
+        }
+        let throw = match Interpreter::load_attr(delegate, "throw") {
+            Ok(throw) => throw,
+            Err(err) if ExceptionObject::is_exception(&err, ExceptionKind::AttributeError) => return Ok(None),
+            Err(err) => return Err(err)
+        };
+        call_step(&throw, vec![exc]).map(Some)
+    }
+
+    /// refer to CPython(gen_close_iter)
+    fn close_iter(iter: &PyObject) -> PyResult<()> {
+        if let Some(generator) = iter.downcast_refcell::<GeneratorObject>() {
+            return generator.close();
+        }
+        if let Some(coro) = iter.downcast_refcell::<NativeCoroutineObject>() {
+            return coro.close();
+        }
+        match Interpreter::load_attr(iter, "close") {
+            Ok(close) => Interpreter::call(&close, vec![]).map(|_| ()),
+            Err(err) if ExceptionObject::is_exception(&err, ExceptionKind::AttributeError) => Ok(()),
+            Err(err) => Err(err)
+        }
+    }
+
+    /// raise `exc` at the point the generator stopped, where the exception table of the frame may
+    /// handle it
+    fn raise(&self, exc: PyObject) -> PyResult<FrameExit> {
+        if self.state.get() == GeneratorState::Closed {
+            if self.kind == GeneratorKind::Coroutine && !ExceptionObject::is_exception(&exc, ExceptionKind::GeneratorExit) {
+                return Self::reused();
+            }
+            return Err(exc);
+        }
+        let frame = self.frame.borrow_mut().take().expect("a suspended generator keeps its frame");
+        self.run(frame, Some(exc))
+    }
+
+    /// a StopIteration leaving the frame would pass for the end of the generator, as would a
+    /// StopAsyncIteration for an async generator, they become a RuntimeError instead, refer to PEP 479
+    fn escaped(&self, err: PyObject) -> PyObject {
+        let stopped = ExceptionObject::is_exception(&err, ExceptionKind::StopIteration)
+            || self.kind == GeneratorKind::AsyncGenerator && ExceptionObject::is_exception(&err, ExceptionKind::StopAsyncIteration);
+        if stopped {
+            let kind = err.downcast_refcell::<ExceptionObject>().expect("invalid exception object").kind();
+            let msg = format!("{} raised {}", self.kind.name(), kind.name());
+            return ExceptionObject::new(ExceptionKind::RuntimeError, vec![StringObject::new_from_string(msg)]);
+        }
+        err
+    }
+}
+
+impl PartialEq<Self> for GeneratorObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for GeneratorObject {}
+
+impl PyObjectTrait for GeneratorObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+
+    fn type_name(&self) -> String {
+        match self.kind {
+            GeneratorKind::Generator => "generator",
+            GeneratorKind::Coroutine => "coroutine",
+            GeneratorKind::AsyncGenerator => "async_generator"
+        }.to_string()
+    }
+
+    /// coroutines and async generators are no iterators, they are driven by `await` and `async for`
+    fn to_iter(&self) -> PyResult {
+        if self.kind != GeneratorKind::Generator {
+            return ExceptionObject::raise(ExceptionKind::TypeError, &format!("'{}' object is not iterable", self.type_name()));
+        }
+        Ok(self.this.upgrade().expect("generator should be alive"))
+    }
+
+    fn next(&self) -> PyResult<Option<PyObject>> {
+        if self.kind != GeneratorKind::Generator {
+            return ExceptionObject::raise(ExceptionKind::TypeError, &format!("'{}' object is not an iterator", self.type_name()));
+        }
+        match self.send(NoneObject::new())? {
+            FrameExit::Yield(value) => Ok(Some(value)),
+            FrameExit::Return(value) if value.borrow().object_type() == ObjectType::NONE => Ok(None),
+            FrameExit::Return(value) => Err(stop_iteration(value)),
+            FrameExit::Generator => unreachable!("a resumed frame does not return a generator")
+        }
+    }
+
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        let method: fn(Vec<PyObject>) -> PyResult = match (self.kind, name) {
+            (GeneratorKind::Generator | GeneratorKind::Coroutine, "send") => gen_send,
+            (GeneratorKind::Generator | GeneratorKind::Coroutine, "throw") => gen_throw,
+            (GeneratorKind::Generator | GeneratorKind::Coroutine, "close") => gen_close,
+            (GeneratorKind::Generator, "__next__") => gen_next,
+            (GeneratorKind::Generator, "__iter__") => gen_iter,
+            (GeneratorKind::AsyncGenerator, "__aiter__") => agen_aiter,
+            (GeneratorKind::AsyncGenerator, "__anext__") => agen_anext,
+            (GeneratorKind::AsyncGenerator, "asend") => agen_asend,
+            (GeneratorKind::AsyncGenerator, "athrow") => agen_athrow,
+            (GeneratorKind::AsyncGenerator, "aclose") => agen_aclose,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+
+    fn get_attr(&self, name: String) -> PyResult {
+        let prefix = match self.kind {
+            GeneratorKind::Generator => "gi",
+            GeneratorKind::Coroutine => "cr",
+            GeneratorKind::AsyncGenerator => "ag"
+        };
+        let delegate = match self.kind {
+            GeneratorKind::Generator => "yieldfrom",
+            _ => "await"
+        };
+        match name.strip_prefix(prefix).and_then(|name| name.strip_prefix('_')) {
+            Some("running") => return Ok(Interpreter::new_bool_object(self.state.get() == GeneratorState::Running)),
+            Some("suspended") if self.kind != GeneratorKind::AsyncGenerator => {
+                return Ok(Interpreter::new_bool_object(self.state.get() == GeneratorState::Suspended));
+            },
+            Some(attr) if attr == delegate => return Ok(self.delegate().unwrap_or_else(NoneObject::new)),
+            _ => {}
+        }
+        match name.as_str() {
+            "__name__" => Ok(StringObject::new_from_str(&self.name)),
+            "__qualname__" => Ok(StringObject::new_from_str(&self.qualname)),
+            _ => ExceptionObject::raise(
+                ExceptionKind::AttributeError,
+                &format!("'{}' object has no attribute '{}'", self.type_name(), name)
+            )
+        }
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        Ok(format!("<{} object {} at {:#x}>", self.type_name(), self.qualname, object_address(self)))
+    }
+}
+
+impl fmt::Display for GeneratorObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for GeneratorObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "GeneratorObject({} at {:#x})", self.qualname, object_address(self))
+    }
+}
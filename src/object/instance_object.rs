@@ -0,0 +1,411 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use crate::frame::{get_name, new_namespace, remove_name, set_name};
+use crate::object::{descriptor_get, descriptor_set, is_data_descriptor, object_address, unhashable, BasePycObject, CallableObject, ExceptionKind, ExceptionObject, IntLongObject, IterObject, ObjectType, StringObject, TypeObject};
+use crate::object::PyObjectTrait;
+use std::fmt;
+use crate::interpreter::Interpreter;
+use crate::utils::{hash, CmpOP, DowncastTrait, PyObject, PyResult};
+
+/// an instance of a user-defined class, its behaviour comes from the methods of the class
+pub struct InstanceObject {
+    base: BasePycObject,
+    class: PyObject,
+    /// the attributes of the instance, a dict so they can be set while the instance is borrowed,
+    /// absent when every class defines `__slots__`
+    dict: Option<PyObject>,
+    /// the values of the `__slots__` of the classes, `None` while unset
+    slots: RefCell<Vec<Option<PyObject>>>,
+    /// the instance itself, passed as `self` to the methods of the class
+    this: Weak<RefCell<InstanceObject>>
+}
+
+impl InstanceObject {
+    pub fn new(class: PyObject) -> PyObject {
+        let (num_slots, has_dict) = {
+            let class = class.downcast_refcell::<TypeObject>().expect("invalid type object");
+            (class.num_slots(), class.has_dict())
+        };
+        Rc::new_cyclic(|this| RefCell::new(Self {
+            base: BasePycObject::new(ObjectType::INSTANCE),
+            class,
+            dict: if has_dict { Some(new_namespace()) } else { None },
+            slots: RefCell::new(vec![None; num_slots]),
+            this: this.clone()
+        }))
+    }
+
+    pub fn class(&self) -> PyObject {
+        self.class.clone()
+    }
+
+    fn this(&self) -> PyObject {
+        self.this.upgrade().expect("instance should be alive")
+    }
+
+    pub fn num_slots(&self) -> usize {
+        self.slots.borrow().len()
+    }
+
+    pub fn slot(&self, index: usize) -> Option<PyObject> {
+        self.slots.borrow()[index].clone()
+    }
+
+    /// replace the value of a slot, giving the previous one
+    pub fn set_slot(&self, index: usize, value: Option<PyObject>) -> Option<PyObject> {
+        std::mem::replace(&mut self.slots.borrow_mut()[index], value)
+    }
+
+    /// call the method `name` of the class bound to the instance, `None` when the class lacks it,
+    /// refer to CPython(vectorcall_method)
+    fn call_method(&self, name: &str, args: Vec<PyObject>) -> Option<PyResult> {
+        let method = TypeObject::lookup(&self.class, name)?;
+        Some(descriptor_get(method, Some(&self.this()), &self.class).and_then(|method| Interpreter::call(&method, args)))
+    }
+
+    /// the attribute lookup of `object.__getattribute__`: data descriptors of the class, then the
+    /// instance dict, then the other class attributes, refer to CPython(_PyObject_GenericGetAttrWithDict)
+    pub fn generic_get_attr(&self, name: &str) -> PyResult {
+        match name {
+            "__dict__" => return self.dict.clone().map_or_else(|| self.no_attribute(name), Ok),
+            "__class__" => return Ok(self.class.clone()),
+            _ => {}
+        }
+        let class_attr = TypeObject::lookup(&self.class, name);
+        if let Some(attr) = class_attr.as_ref().filter(|attr| is_data_descriptor(attr)) {
+            return descriptor_get(attr.clone(), Some(&self.this()), &self.class);
+        }
+        if let Some(value) = self.dict.as_ref().and_then(|dict| get_name(dict, name)) {
+            return Ok(value);
+        }
+        match class_attr {
+            Some(attr) => descriptor_get(attr, Some(&self.this()), &self.class),
+            None => self.no_attribute(name)
+        }
+    }
+
+    /// the assignment of `object.__setattr__`, or its deletion when `value` is `None`
+    pub fn generic_set_attr(&self, name: &str, value: Option<PyObject>) -> PyResult<()> {
+        if let Some(attr) = TypeObject::lookup(&self.class, name) {
+            if let Some(res) = descriptor_set(&attr, &self.this(), value.clone()) {
+                return res;
+            }
+        }
+        let dict = match &self.dict {
+            Some(dict) => dict,
+            None => return self.no_attribute(name)
+        };
+        match value {
+            Some(value) => {
+                set_name(dict, name, value);
+                Ok(())
+            },
+            None => match remove_name(dict, name) {
+                Some(_) => Ok(()),
+                None => self.no_attribute(name)
+            }
+        }
+    }
+
+    /// the function `name` of the class when looking it up on the instance would only bind it,
+    /// not when the instance dict shadows it or attribute access is customized
+    pub fn unbound_method(&self, name: &str) -> Option<PyObject> {
+        if TypeObject::overrides(&self.class, "__getattribute__") {
+            return None;
+        }
+        let attr = TypeObject::lookup(&self.class, name)?;
+        let shadowed = self.dict.as_ref().is_some_and(|dict| get_name(dict, name).is_some());
+        if attr.downcast_refcell::<CallableObject>().is_none() || shadowed {
+            return None;
+        }
+        Some(attr)
+    }
+
+    /// call the hook `name` unless it is the generic one of `object`
+    fn call_hook(&self, name: &str, args: Vec<PyObject>) -> Option<PyResult> {
+        if !TypeObject::overrides(&self.class, name) {
+            return None;
+        }
+        self.call_method(name, args)
+    }
+
+    fn class_name(&self) -> String {
+        self.class.downcast_refcell::<TypeObject>().expect("invalid type object").name().to_string()
+    }
+
+    fn no_attribute<T>(&self, name: &str) -> PyResult<T> {
+        ExceptionObject::raise(
+            ExceptionKind::AttributeError,
+            &format!("'{}' object has no attribute '{}'", self.class_name(), name)
+        )
+    }
+
+    /// the rich comparison method for `op`, `None` when the class does not define it
+    fn compare_method(&self, other: &PyObject, op: &CmpOP) -> Option<PyResult<Option<PyObject>>> {
+        let name = match op {
+            CmpOP::LT => "__lt__",
+            CmpOP::LE => "__le__",
+            CmpOP::EQ => "__eq__",
+            CmpOP::NEQ => "__ne__",
+            CmpOP::GT => "__gt__",
+            CmpOP::GE => "__ge__",
+        };
+        let res = self.call_method(name, vec![other.clone()])?;
+        Some(res.map(|res| {
+            if res.borrow().object_type() == ObjectType::NOT_IMPLEMENTED {
+                return None;
+            }
+            Some(res)
+        }))
+    }
+}
+
+impl PartialEq<Self> for InstanceObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for InstanceObject {}
+
+impl PyObjectTrait for InstanceObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+    fn type_name(&self) -> String {
+        self.class_name()
+    }
+
+    /// refer to CPython(slot_tp_hash)
+    fn hash(&self) -> PyResult<i64> {
+        let method = TypeObject::lookup(&self.class, "__hash__");
+        match method {
+            Some(method) if method.borrow().object_type() == ObjectType::NONE => unhashable(&self.class_name()),
+            Some(_) => {
+                let res = self.call_method("__hash__", vec![]).unwrap()?;
+                match IntLongObject::extract(&res) {
+                    // -1 is the error value of a hash in CPython and is never returned
+                    Some(-1) => Ok(-2),
+                    Some(hash) => Ok(hash),
+                    None => ExceptionObject::raise(ExceptionKind::TypeError, "__hash__ method should return an integer")
+                }
+            },
+            None => Ok(hash::hash_pointer(object_address(self)))
+        }
+    }
+
+    /// `__ne__` falls back to the inverse of `__eq__` like `object.__ne__`, refer to CPython(object_richcompare)
+    fn rich_compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<PyObject>> {
+        if let Some(res) = self.compare_method(other, op) {
+            return res;
+        }
+        if *op == CmpOP::NEQ {
+            if let Some(res) = self.compare_method(other, &CmpOP::EQ) {
+                return match res? {
+                    Some(equal) => Ok(Some(Interpreter::new_bool_object(!Interpreter::is_true(&equal)?))),
+                    None => Ok(None)
+                };
+            }
+        }
+        Ok(None)
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        match self.call_method("__repr__", vec![]) {
+            Some(res) => {
+                let res = res?;
+                match StringObject::extract(&res) {
+                    Ok(repr) => Ok(repr),
+                    Err(_) => ExceptionObject::raise(
+                        ExceptionKind::TypeError,
+                        &format!("__repr__ returned non-string (type {})", res.borrow().type_name())
+                    )
+                }
+            },
+            None => {
+                let class = self.class.downcast_refcell::<TypeObject>().expect("invalid type object");
+                let module = class.module().map(|module| format!("{}.", module)).unwrap_or_default();
+                Ok(format!("<{}{} object at {:#x}>", module, class.qualified_name(), object_address(self)))
+            }
+        }
+    }
+
+    fn str(&self) -> PyResult<String> {
+        match self.call_method("__str__", vec![]) {
+            Some(res) => {
+                let res = res?;
+                match StringObject::extract(&res) {
+                    Ok(s) => Ok(s),
+                    Err(_) => ExceptionObject::raise(
+                        ExceptionKind::TypeError,
+                        &format!("__str__ returned non-string (type {})", res.borrow().type_name())
+                    )
+                }
+            },
+            None => self.repr()
+        }
+    }
+
+    /// functions of the class called with the instance as `self`
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        TypeObject::lookup(&self.class, name)
+    }
+
+    /// `__getattribute__`, falling back to `__getattr__` when the attribute is missing, refer to CPython(slot_tp_getattr_hook)
+    fn get_attr(&self, name: String) -> PyResult {
+        let res = match self.call_hook("__getattribute__", vec![StringObject::new_from_str(&name)]) {
+            Some(res) => res,
+            None => self.generic_get_attr(&name)
+        };
+        let missing = res.as_ref().is_err_and(|err| {
+            ExceptionObject::is_exception(err, ExceptionKind::AttributeError)
+        });
+        if missing {
+            if let Some(res) = self.call_method("__getattr__", vec![StringObject::new_from_str(&name)]) {
+                return res;
+            }
+        }
+        res
+    }
+
+    fn set_attr(&self, name: String, value: PyObject) -> PyResult<()> {
+        match self.call_hook("__setattr__", vec![StringObject::new_from_str(&name), value.clone()]) {
+            Some(res) => res.map(|_| ()),
+            None => self.generic_set_attr(&name, Some(value))
+        }
+    }
+
+    fn del_attr(&self, name: String) -> PyResult<()> {
+        match self.call_hook("__delattr__", vec![StringObject::new_from_str(&name)]) {
+            Some(res) => res.map(|_| ()),
+            None => self.generic_set_attr(&name, None)
+        }
+    }
+
+    fn get_item(&self, key: &PyObject) -> PyResult {
+        match self.call_method("__getitem__", vec![key.clone()]) {
+            Some(res) => res,
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("'{}' object is not subscriptable", self.class_name())
+            )
+        }
+    }
+
+    /// `__contains__`, or else a scan through the items of iter(), refer to CPython(PySequence_Contains)
+    fn contains(&self, item: &PyObject) -> PyResult<bool> {
+        if let Some(res) = self.call_method("__contains__", vec![item.clone()]) {
+            return Interpreter::is_true(&res?);
+        }
+        if TypeObject::lookup(&self.class, "__iter__").is_none() && TypeObject::lookup(&self.class, "__getitem__").is_none() {
+            return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("argument of type '{}' is not iterable", self.class_name())
+            );
+        }
+        let iter = self.to_iter()?;
+        loop {
+            let value = IterObject::advance(&iter)?;
+            match value {
+                Some(value) if Interpreter::equals(&value, item)? => return Ok(true),
+                Some(_) => {},
+                None => return Ok(false)
+            }
+        }
+    }
+
+    /// `__iter__`, or else an iterator indexing `__getitem__`, refer to CPython(PyObject_GetIter)
+    fn to_iter(&self) -> PyResult {
+        if let Some(iter) = self.call_method("__iter__", vec![]) {
+            let iter = iter?;
+            if iter.borrow().get_method("__next__").is_none() {
+                return ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    &format!("iter() returned non-iterator of type '{}'", iter.borrow().type_name())
+                );
+            }
+            return Ok(iter);
+        }
+        if TypeObject::lookup(&self.class, "__getitem__").is_some() {
+            return Ok(IterObject::new_sequence(self.this()));
+        }
+        ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("'{}' object is not iterable", self.class_name())
+        )
+    }
+
+    /// `__next__`, whose StopIteration ends the iteration, refer to CPython(slot_tp_iternext)
+    fn next(&self) -> PyResult<Option<PyObject>> {
+        match self.call_method("__next__", vec![]) {
+            Some(Ok(value)) => Ok(Some(value)),
+            Some(Err(err)) => {
+                let stopped = ExceptionObject::is_exception(&err, ExceptionKind::StopIteration);
+                if stopped { Ok(None) } else { Err(err) }
+            },
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("'{}' object is not an iterator", self.class_name())
+            )
+        }
+    }
+
+    /// refer to CPython(slot_sq_length)
+    fn length(&self) -> PyResult<usize> {
+        let res = match self.call_method("__len__", vec![]) {
+            Some(res) => res?,
+            None => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("object of type '{}' has no len()", self.class_name())
+            )
+        };
+        match IntLongObject::extract(&res) {
+            Some(len) if len < 0 => ExceptionObject::raise(ExceptionKind::ValueError, "__len__() should return >= 0"),
+            Some(len) => Ok(len as usize),
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("'{}' object cannot be interpreted as an integer", res.borrow().type_name())
+            )
+        }
+    }
+
+    /// `__bool__`, or else a nonzero `__len__`, refer to CPython(slot_nb_bool)
+    fn is_true(&self) -> PyResult<bool> {
+        if let Some(res) = self.call_method("__bool__", vec![]) {
+            let res = res?;
+            return match res.borrow().object_type() {
+                ObjectType::TRUE => Ok(true),
+                ObjectType::FALSE => Ok(false),
+                _ => ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    &format!("__bool__ should return bool, returned {}", res.borrow().type_name())
+                )
+            };
+        }
+        if TypeObject::lookup(&self.class, "__len__").is_some() {
+            return Ok(self.length()? > 0);
+        }
+        Ok(true)
+    }
+
+    fn is_proper_subtype_of(&self, other: &PyObject) -> bool {
+        match other.downcast_refcell::<InstanceObject>() {
+            Some(other) => !Rc::ptr_eq(&self.class, &other.class) && TypeObject::is_subclass(&self.class, &other.class),
+            None => false
+        }
+    }
+}
+
+impl fmt::Display for InstanceObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for InstanceObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "InstanceObject({} at {:#x})", self.class_name(), object_address(self))
+    }
+}
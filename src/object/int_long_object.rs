@@ -1,9 +1,9 @@
-use crate::object::BasePycObject;
+use crate::object::{BasePycObject, IntObject, Real};
 use crate::object::PyObjectTrait;
 use crate::object::ObjectType;
 use std::fmt;
 use crate::InputStream;
-use crate::utils::PyObject;
+use crate::utils::{hash, CmpOP, DowncastTrait, PyObject, PyResult};
 
 pub struct IntLongObject {
     base: BasePycObject,
@@ -17,6 +17,25 @@ impl IntLongObject {
             value: stream.read_i64().unwrap()
         })
     }
+
+    /// an int object holding `value`, which is only widened to 64 bits when it does not fit
+    pub fn new_from_i64(value: i64) -> PyObject {
+        if let Ok(value) = i32::try_from(value) {
+            return IntObject::new_from_i32(value);
+        }
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new_from_char('I'),
+            value
+        })
+    }
+
+    /// the integer value of `obj`, if it is an int of either width
+    pub fn extract(obj: &PyObject) -> Option<i64> {
+        if let Some(long) = obj.downcast_refcell::<IntLongObject>() {
+            return Some(long.value);
+        }
+        IntObject::extract(obj).map(|value| value as i64)
+    }
 }
 
 impl PartialEq<Self> for IntLongObject {
@@ -33,11 +52,15 @@ impl PyObjectTrait for IntLongObject {
     fn base_object(&self) -> &BasePycObject {
         &self.base
     }
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(self.value != 0)
+    }
 
-    fn hash_key(&self) -> String {
-        let mut res = "$IntLong_".to_string();
-        res.push_str(&self.value.to_string());
-        res
+    fn hash(&self) -> PyResult<i64> {
+        Ok(hash::hash_int(self.value))
+    }
+    fn compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        Ok(Real::Int(self.value).compare(other, op))
     }
 }
 
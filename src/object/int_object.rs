@@ -1,33 +1,53 @@
 use std::cmp::Ordering;
-use crate::object::BasePycObject;
+use crate::object::{BasePycObject, Real};
 use crate::object::PyObjectTrait;
 use crate::object::ObjectType;
 use std::fmt;
 use crate::InputStream;
-use crate::utils::PyObject;
+use crate::utils::{hash, CmpOP, DowncastTrait, PyObject, PyResult};
 
 pub struct IntObject {
     base: BasePycObject,
     value: i32
 }
 
+/// the range of the preallocated ints, refer to CPython(_PY_NSMALLNEGINTS, _PY_NSMALLPOSINTS)
+const SMALL_INTS: std::ops::RangeInclusive<i32> = -5..=256;
+
+thread_local! {
+    static SMALL_INT_CACHE: Vec<PyObject> = SMALL_INTS.map(IntObject::alloc).collect();
+}
+
 impl IntObject {
     pub fn new(stream: &mut InputStream) -> PyObject {
-        BasePycObject::new_py_object(Self {
-            base: BasePycObject::new_from_char('i'),
-            value: stream.read_i32().unwrap()
-        })
+        Self::new_from_i32(stream.read_i32().unwrap())
     }
 
+    /// an int holding `value`, the small ints are shared so `256 is 256` like in CPython
     pub fn new_from_i32(value: i32) -> PyObject {
+        if SMALL_INTS.contains(&value) {
+            return SMALL_INT_CACHE.with(|cache| cache[(value - SMALL_INTS.start()) as usize].clone());
+        }
+        Self::alloc(value)
+    }
+
+    fn alloc(value: i32) -> PyObject {
         BasePycObject::new_py_object(Self {
             base: BasePycObject::new_from_char('i'),
             value
         })
     }
 
-    pub fn value(&self) -> i32 {
-        self.value
+    /// the integer value of `obj`, if it is an int, bool counts as an int like in CPython
+    pub fn extract(obj: &PyObject) -> Option<i32> {
+        if let Some(int) = obj.downcast_refcell::<IntObject>() {
+            return Some(int.value);
+        }
+        match obj.borrow().object_type() {
+            ObjectType::TRUE => Some(1),
+            ObjectType::FALSE => Some(0),
+            _ => None
+        }
     }
 }
 
@@ -55,11 +75,15 @@ impl PyObjectTrait for IntObject {
     fn base_object(&self) -> &BasePycObject {
         &self.base
     }
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(self.value != 0)
+    }
 
-    fn hash_key(&self) -> String {
-        let mut res = "$Int_".to_string();
-        res.push_str(&self.value.to_string());
-        res
+    fn hash(&self) -> PyResult<i64> {
+        Ok(hash::hash_int(self.value as i64))
+    }
+    fn compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        Ok(Real::Int(self.value as i64).compare(other, op))
     }
 }
 
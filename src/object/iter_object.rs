@@ -1,64 +1,213 @@
-use crate::object::{BasePycObject, CallableObject, NullObject};
+use crate::object::{check_args, object_address, BasePycObject, ByteArrayObject, BytesObject, CallableObject, DictObject, DictViewKind, ExceptionKind, ExceptionObject, IntLongObject, ListObject, SetObject, StringObject, TupleObject};
 use crate::object::PyObjectTrait;
 use crate::object::ObjectType;
+use std::cell::{Cell, RefCell};
 use std::fmt;
-use std::ops::DerefMut;
+use std::rc::{Rc, Weak};
+use crate::interpreter::Interpreter;
 use crate::utils::DowncastTrait;
 
-use crate::utils::PyObject;
+use crate::utils::{PyObject, PyResult};
+
+/// what an iterator walks through, the items are read from it one at a time
+enum IterSource {
+    /// items fetched by increasing indexes until IndexError, so a list growing while it is
+    /// iterated yields the new items as well, refer to CPython(PySeqIter_Type)
+    Sequence(PyObject),
+    /// the code points of a str, the index being a byte offset
+    Str(PyObject),
+    Range { start: i64, step: i64, len: i64 },
+    /// `len` is the size of the dict when the iteration began, refer to CPython(dictiter_iternextkey)
+    Dict { dict: PyObject, kind: DictViewKind, len: usize },
+    Set { set: PyObject, len: usize },
+    /// calls of `callable` until it returns `sentinel`, refer to CPython(PyCallIter_Type)
+    Callable { callable: PyObject, sentinel: PyObject }
+}
 
 pub struct IterObject {
     base: BasePycObject,
-    cur_index: usize,
-    values: Vec<PyObject>
+    source: IterSource,
+    /// the position of the next item in the source
+    index: Cell<usize>,
+    /// an exhausted iterator stays exhausted even when its source grows
+    exhausted: Cell<bool>,
+    /// the iterator itself, which is its own iter()
+    this: Weak<RefCell<IterObject>>
 }
-pub fn iter_next(args: Vec<PyObject>) -> PyObject {
-    let object = args[0].clone();
-    let mut iter = object.downcast_refcell_mut::<IterObject>().expect("invalid iter object");
-    let iter = iter.deref_mut();
 
-    if let Some (obj) = iter.next() {
-        obj
-    } else {
-        NullObject::new()
+/// `__next__`, which raises StopIteration instead of giving `None`
+fn iter_next(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__next__", 0, 0)?;
+    let item = args[0].borrow().next()?;
+    match item {
+        Some(item) => Ok(item),
+        None => Err(ExceptionObject::new(ExceptionKind::StopIteration, vec![]))
     }
 }
 
+fn iter_iter(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__iter__", 0, 0)?;
+    Ok(args[0].clone())
+}
+
+
 impl IterObject {
-    pub fn new(values: Vec<PyObject>) -> PyObject {
-        let mut base = BasePycObject::new(ObjectType::ITER);
-        base.insert_method("next", CallableObject::new_native(Box::new(iter_next)));
-        BasePycObject::new_py_object(Self {
-            base,
-            cur_index: 0,
-            values
-        })
-    }
-
-    pub fn next(&mut self) -> Option<PyObject> {
-        if self.cur_index >= self.values.len() {
-            return None;
-        }
-        let res = self.values[self.cur_index].clone();
-        self.cur_index += 1;
-        Some(res)
+    fn new(source: IterSource) -> PyObject {
+        Rc::new_cyclic(|this| RefCell::new(Self {
+            base: BasePycObject::new(ObjectType::ITER),
+            source,
+            index: Cell::new(0),
+            exhausted: Cell::new(false),
+            this: this.clone()
+        }))
     }
 
-}
+    pub fn new_sequence(seq: PyObject) -> PyObject {
+        Self::new(IterSource::Sequence(seq))
+    }
 
-impl PartialEq<Self> for IterObject {
-    fn eq(&self, other: &Self) -> bool {
-        if self.values.len() != other.values.len() {
-            return false;
+    pub fn new_str(string: PyObject) -> PyObject {
+        Self::new(IterSource::Str(string))
+    }
+
+    pub fn new_range(start: i64, step: i64, len: i64) -> PyObject {
+        Self::new(IterSource::Range { start, step, len })
+    }
+
+    pub fn new_dict(dict: PyObject, kind: DictViewKind) -> PyResult {
+        let len = dict.borrow().length()?;
+        Ok(Self::new(IterSource::Dict { dict, kind, len }))
+    }
+
+    pub fn new_set(set: PyObject) -> PyResult {
+        let len = set.borrow().length()?;
+        Ok(Self::new(IterSource::Set { set, len }))
+    }
+
+    pub fn new_callable(callable: PyObject, sentinel: PyObject) -> PyObject {
+        Self::new(IterSource::Callable { callable, sentinel })
+    }
+
+    /// iter() of any object: the builtin containers are read in place, which needs the object
+    /// itself rather than the borrow `to_iter` gets, refer to CPython(PyObject_GetIter)
+    pub fn get_iter(obj: &PyObject) -> PyResult {
+        let is_sequence = obj.downcast_refcell::<ListObject>().is_some()
+            || obj.downcast_refcell::<TupleObject>().is_some()
+            || obj.downcast_refcell::<BytesObject>().is_some()
+            || obj.downcast_refcell::<ByteArrayObject>().is_some();
+        if is_sequence {
+            return Ok(Self::new_sequence(obj.clone()));
+        }
+        if obj.downcast_refcell::<StringObject>().is_some() {
+            return Ok(Self::new_str(obj.clone()));
         }
+        if obj.downcast_refcell::<DictObject>().is_some() {
+            return Self::new_dict(obj.clone(), DictViewKind::Keys);
+        }
+        if obj.downcast_refcell::<SetObject>().is_some() {
+            return Self::new_set(obj.clone());
+        }
+        obj.borrow().to_iter()
+    }
 
-        for i in 0..self.values.len() {
-            if self.values.get(i).unwrap() != other.values.get(i).unwrap() {
-                return false;
+    /// the next item of an iterator, `None` once it is exhausted, which a generator returning a
+    /// value signals with a StopIteration carrying it, refer to CPython(gen_iternext)
+    pub fn advance(iter: &PyObject) -> PyResult<Option<PyObject>> {
+        let item = iter.borrow().next();
+        match item {
+            Err(err) if ExceptionObject::is_exception(&err, ExceptionKind::StopIteration) => Ok(None),
+            item => item
+        }
+    }
+
+    /// drain any iterable into a vector of its items
+    pub fn collect(iterable: &PyObject) -> PyResult<Vec<PyObject>> {
+        let iter = Self::get_iter(iterable)?;
+        let mut values = vec![];
+        loop {
+            let value = Self::advance(&iter)?;
+            match value {
+                Some(value) => values.push(value),
+                None => return Ok(values)
             }
         }
+    }
+
+    fn next_item(&self) -> PyResult<Option<PyObject>> {
+        let index = self.index.get();
+        let item = match &self.source {
+            IterSource::Sequence(seq) => {
+                let item = seq.borrow().get_item(&IntLongObject::new_from_i64(index as i64));
+                match item {
+                    Ok(item) => item,
+                    Err(err) if ExceptionObject::is_exception(&err, ExceptionKind::IndexError) || ExceptionObject::is_exception(&err, ExceptionKind::StopIteration) => return Ok(None),
+                    Err(err) => return Err(err)
+                }
+            },
+            IterSource::Str(string) => {
+                let string = string.downcast_refcell::<StringObject>().expect("invalid str object");
+                match string.as_str()[index..].chars().next() {
+                    Some(c) => {
+                        self.index.set(index + c.len_utf8());
+                        return Ok(Some(StringObject::new_from_string(c.to_string())));
+                    },
+                    None => return Ok(None)
+                }
+            },
+            IterSource::Range { start, step, len } => {
+                if index as i64 >= *len {
+                    return Ok(None);
+                }
+                IntLongObject::new_from_i64(start + index as i64 * step)
+            },
+            IterSource::Dict { dict, kind, len } => {
+                let dict = dict.downcast_refcell::<DictObject>().expect("invalid dict object");
+                if dict.length()? != *len {
+                    return ExceptionObject::raise(ExceptionKind::RuntimeError, "dictionary changed size during iteration");
+                }
+                let (position, key, value) = match dict.next_entry(index) {
+                    Some(entry) => entry,
+                    None => return Ok(None)
+                };
+                self.index.set(position + 1);
+                return Ok(Some(match kind {
+                    DictViewKind::Keys => key,
+                    DictViewKind::Values => value,
+                    DictViewKind::Items => TupleObject::new_from_values(vec![key, value])
+                }));
+            },
+            IterSource::Set { set, len } => {
+                let set = set.downcast_refcell::<SetObject>().expect("invalid set object");
+                if set.length()? != *len {
+                    return ExceptionObject::raise(ExceptionKind::RuntimeError, "Set changed size during iteration");
+                }
+                let (position, key) = match set.next_entry(index) {
+                    Some(entry) => entry,
+                    None => return Ok(None)
+                };
+                self.index.set(position + 1);
+                return Ok(Some(key));
+            },
+            IterSource::Callable { callable, sentinel } => {
+                let value = match Interpreter::call(callable, vec![]) {
+                    Ok(value) => value,
+                    Err(err) if ExceptionObject::is_exception(&err, ExceptionKind::StopIteration) => return Ok(None),
+                    Err(err) => return Err(err)
+                };
+                if Interpreter::equals(&value, sentinel)? {
+                    return Ok(None);
+                }
+                value
+            }
+        };
+        self.index.set(index + 1);
+        Ok(Some(item))
+    }
+}
 
-        return true;
+impl PartialEq<Self> for IterObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
     }
 }
 
@@ -72,26 +221,63 @@ impl PyObjectTrait for IterObject {
         &self.base
     }
 
-    fn hash_key(&self) -> String {
-        // TODO: tuple should be hashable
-        unimplemented!()
+    fn type_name(&self) -> String {
+        let name = match &self.source {
+            IterSource::Sequence(seq) if seq.downcast_refcell::<ListObject>().is_some() => "list_iterator",
+            IterSource::Sequence(seq) if seq.downcast_refcell::<TupleObject>().is_some() => "tuple_iterator",
+            IterSource::Sequence(seq) if seq.downcast_refcell::<BytesObject>().is_some() => "bytes_iterator",
+            IterSource::Sequence(seq) if seq.downcast_refcell::<ByteArrayObject>().is_some() => "bytearray_iterator",
+            IterSource::Sequence(_) => "iterator",
+            IterSource::Str(string) => {
+                let string = string.downcast_refcell::<StringObject>().expect("invalid str object");
+                if string.as_str().is_ascii() { "str_ascii_iterator" } else { "str_iterator" }
+            },
+            IterSource::Range { .. } => "range_iterator",
+            IterSource::Dict { kind: DictViewKind::Keys, .. } => "dict_keyiterator",
+            IterSource::Dict { kind: DictViewKind::Values, .. } => "dict_valueiterator",
+            IterSource::Dict { kind: DictViewKind::Items, .. } => "dict_itemiterator",
+            IterSource::Set { .. } => "set_iterator",
+            IterSource::Callable { .. } => "callable_iterator"
+        };
+        name.to_string()
+    }
+
+    fn to_iter(&self) -> PyResult {
+        Ok(self.this.upgrade().expect("iterator should be alive"))
+    }
+
+    fn next(&self) -> PyResult<Option<PyObject>> {
+        if self.exhausted.get() {
+            return Ok(None);
+        }
+        let item = self.next_item()?;
+        if item.is_none() {
+            self.exhausted.set(true);
+        }
+        Ok(item)
+    }
+
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "__next__" => iter_next,
+            "__iter__" => iter_iter,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        Ok(format!("<{} object at {:#x}>", self.type_name(), object_address(self)))
     }
 }
 
 impl fmt::Display for IterObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "(").unwrap();
-        for (i, entry) in self.values.iter().enumerate() {
-            if i != 0 {
-                write!(f, ", ").unwrap();
-            }
-            write!(f, "{}", entry.borrow()).unwrap();
-        }
-        write!(f, ")")
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
     }
 }
 impl fmt::Debug for IterObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        writeln!(f, "IterObject({:?})", self.values)
+        write!(f, "IterObject({} at {:#x})", self.type_name(), object_address(self))
     }
-}
\ No newline at end of file
+}
@@ -1,12 +1,12 @@
-use crate::object::{BasePycObject, IterObject, TupleObject};
+use crate::object::{check_args, join_repr, normalize_index, unhashable, object_address, range_args, repr_guard, BasePycObject, CallableObject, ExceptionKind, ExceptionObject, IntObject, IterObject, Kwargs, NoneObject, SliceObject};
 use crate::object::PyObjectTrait;
 use crate::object::ObjectType;
 use std::{fmt, mem};
-use std::ops::DerefMut;
 use crate::{InputStream, PycParser};
-use crate::utils::{DowncastTrait, Magic};
+use crate::interpreter::Interpreter;
+use crate::utils::{sort, CmpOP, DowncastTrait, Magic};
 
-use crate::utils::PyObject;
+use crate::utils::{PyObject, PyResult};
 
 #[derive(Default)]
 pub struct ListObject {
@@ -34,29 +34,355 @@ impl ListObject {
         })
     }
 
-    pub fn extend(base: PyObject, other: PyObject) -> PyObject {
-        let mut base = base.downcast_refcell_mut::<ListObject>().expect("base object should be ListObject!");
-        let base = base.deref_mut();
-        if let Some(item) = other.downcast_refcell::<TupleObject>() {
-            Self::extend_impl(base, item.values())
-        } else if let Some(item) = other.downcast_refcell::<ListObject>() {
-            Self::extend_impl(base, &item.values)
-        } else {
-            panic!("the extend object must be list or tuple");
+    pub fn values(&self) -> &Vec<PyObject> {
+        &self.values
+    }
+
+    pub fn append(&mut self, value: PyObject) {
+        self.values.push(value);
+    }
+
+    /// `list.extend(iterable)` in place, the items are collected first so a list may extend itself
+    pub fn extend(list: &PyObject, iterable: &PyObject) -> PyResult<()> {
+        let values = IterObject::collect(iterable)?;
+        list.downcast_refcell_mut::<ListObject>().expect("invalid list object").values.extend(values);
+        Ok(())
+    }
+
+    /// `lhs + rhs`
+    pub fn concat(&self, other: &PyObject) -> PyResult {
+        match other.downcast_refcell::<ListObject>() {
+            Some(other) => {
+                let mut values = self.values.clone();
+                values.extend(other.values.iter().cloned());
+                Ok(Self::new_from_values(values))
+            },
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("can only concatenate list (not \"{}\") to list", other.borrow().type_name())
+            )
+        }
+    }
+
+    /// the items of `list * times`, a non-positive count gives no items
+    pub fn repeat(&self, times: i32) -> Vec<PyObject> {
+        let mut values = vec![];
+        for _ in 0..times.max(0) {
+            values.extend(self.values.iter().cloned());
+        }
+        values
+    }
+
+    /// `list *= times` keeps the identity of the list
+    pub fn repeat_in_place(&mut self, times: i32) {
+        self.values = self.repeat(times);
+    }
+
+    /// the item at `index` of a list object and its length, read under a borrow of its own
+    fn item_at(list: &PyObject, index: usize) -> (Option<PyObject>, usize) {
+        let list = list.downcast_refcell::<ListObject>().expect("invalid list object");
+        (list.values.get(index).cloned(), list.values.len())
+    }
+
+    /// the comparison of two list objects, `None` unless both are lists; the items are read one at a
+    /// time so that neither list is borrowed while the `__eq__` of an item runs and may change
+    /// them, refer to CPython(list_richcompare)
+    pub fn compare_values(lhs: &PyObject, rhs: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        let lhs_len = match lhs.downcast_refcell::<ListObject>() {
+            Some(lhs) => lhs.values.len(),
+            None => return Ok(None)
+        };
+        let rhs_len = match rhs.downcast_refcell::<ListObject>() {
+            Some(rhs) => rhs.values.len(),
+            None => return Ok(None)
+        };
+        if lhs_len != rhs_len && matches!(op, CmpOP::EQ | CmpOP::NEQ) {
+            // lists of different lengths are never equal
+            return Ok(Some(*op == CmpOP::NEQ));
+        }
+        let mut index = 0;
+        loop {
+            let (lhs_item, lhs_len) = Self::item_at(lhs, index);
+            let (rhs_item, rhs_len) = Self::item_at(rhs, index);
+            let (Some(lhs_item), Some(rhs_item)) = (lhs_item, rhs_item) else {
+                // no more items to compare, the longer one is greater
+                return Ok(Some(op.matches(lhs_len.cmp(&rhs_len))));
+            };
+            if !Interpreter::equals(&lhs_item, &rhs_item)? {
+                return match op {
+                    CmpOP::EQ => Ok(Some(false)),
+                    CmpOP::NEQ => Ok(Some(true)),
+                    op => Ok(Some(Interpreter::compare(&lhs_item, &rhs_item, op)?))
+                };
+            }
+            index += 1;
+        }
+    }
+
+    /// repr() of a list object, whose items are read one at a time so that it is not borrowed while
+    /// the `__repr__` of an item runs and may change it, refer to CPython(list_repr)
+    pub fn repr_of(list: &PyObject) -> PyResult<String> {
+        let address = object_address(&*list.downcast_refcell::<ListObject>().expect("invalid list object"));
+        repr_guard(address, "[...]", || {
+            let mut items = vec![];
+            while let (Some(item), _) = Self::item_at(list, items.len()) {
+                items.push(Interpreter::repr(&item)?);
+            }
+            Ok(format!("[{}]", items.join(", ")))
+        })
+    }
+
+    fn index_arg(key: &PyObject) -> PyResult<i64> {
+        match IntObject::extract(key) {
+            Some(index) => Ok(index as i64),
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("list indices must be integers or slices, not {}", key.borrow().type_name())
+            )
+        }
+    }
+
+    /// the items assigned to a slice, `value` may be the list itself as in `l[:] = l`
+    fn assigned_values(&self, value: &PyObject) -> PyResult<Vec<PyObject>> {
+        if object_address(self) == value.as_ptr() as *const () as usize {
+            return Ok(self.values.clone());
+        }
+        IterObject::collect(value)
+    }
+
+    /// `list[slice] = value`, refer to CPython(list_ass_subscript)
+    fn set_slice(&mut self, slice: &SliceObject, value: &PyObject) -> PyResult<()> {
+        let values = self.assigned_values(value)?;
+        let (start, stop, step, slice_length) = slice.indices(self.values.len())?;
+        if step == 1 {
+            let (start, stop) = (start as usize, stop.max(start) as usize);
+            self.values.splice(start..stop, values);
+            return Ok(());
+        }
+        if values.len() != slice_length {
+            return ExceptionObject::raise(
+                ExceptionKind::ValueError,
+                &format!("attempt to assign sequence of size {} to extended slice of size {}", values.len(), slice_length)
+            );
+        }
+        for (position, value) in slice.positions(self.values.len())?.into_iter().zip(values) {
+            self.values[position] = value;
+        }
+        Ok(())
+    }
+
+    /// `del list[slice]`
+    fn del_slice(&mut self, slice: &SliceObject) -> PyResult<()> {
+        let (start, stop, step, _) = slice.indices(self.values.len())?;
+        if step == 1 {
+            self.values.drain(start as usize..stop.max(start) as usize);
+            return Ok(());
+        }
+        let mut positions = slice.positions(self.values.len())?;
+        positions.sort_unstable();
+        for position in positions.into_iter().rev() {
+            self.values.remove(position);
+        }
+        Ok(())
+    }
+}
+
+fn this(args: &[PyObject]) -> Vec<PyObject> {
+    args[0].downcast_refcell::<ListObject>().expect("invalid list object").values.clone()
+}
+
+fn this_mut(args: &[PyObject]) -> std::cell::RefMut<'_, ListObject> {
+    args[0].downcast_refcell_mut::<ListObject>().expect("invalid list object")
+}
+
+fn list_append(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "append", 1, 1)?;
+    this_mut(&args).append(args[1].clone());
+    Ok(NoneObject::new())
+}
+
+fn list_extend(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "extend", 1, 1)?;
+    ListObject::extend(&args[0], &args[1])?;
+    Ok(NoneObject::new())
+}
+
+fn list_insert(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "insert", 2, 2)?;
+    let index = match IntObject::extract(&args[1]) {
+        Some(index) => index as i64,
+        None => return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("'{}' object cannot be interpreted as an integer", args[1].borrow().type_name())
+        )
+    };
+    let mut list = this_mut(&args);
+    let length = list.values.len() as i64;
+    // the index is clamped rather than checked, refer to CPython(ins1)
+    let index = if index < 0 { (index + length).max(0) } else { index.min(length) };
+    list.values.insert(index as usize, args[2].clone());
+    Ok(NoneObject::new())
+}
+
+fn list_pop(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "pop", 0, 1)?;
+    let mut list = this_mut(&args);
+    if list.values.is_empty() {
+        return ExceptionObject::raise(ExceptionKind::IndexError, "pop from empty list");
+    }
+    let length = list.values.len() as i64;
+    let index = match args.get(1) {
+        None => length - 1,
+        Some(arg) => match IntObject::extract(arg) {
+            Some(index) if index < 0 => index as i64 + length,
+            Some(index) => index as i64,
+            None => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("'{}' object cannot be interpreted as an integer", arg.borrow().type_name())
+            )
         }
+    };
+    if index < 0 || index >= length {
+        return ExceptionObject::raise(ExceptionKind::IndexError, "pop index out of range");
     }
+    Ok(list.values.remove(index as usize))
+}
+
+/// the position of the first item equal to `value` in `values[start..end]`
+fn find(values: &[PyObject], value: &PyObject, start: i64, end: i64) -> PyResult<Option<usize>> {
+    for i in start..end.min(values.len() as i64) {
+        if Interpreter::equals(&values[i as usize], value)? {
+            return Ok(Some(i as usize));
+        }
+    }
+    Ok(None)
+}
+
+fn list_remove(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "remove", 1, 1)?;
+    // the comparisons may run Python code, so the list is not borrowed while searching
+    let values = this(&args);
+    match find(&values, &args[1], 0, values.len() as i64)? {
+        Some(index) => {
+            let mut list = this_mut(&args);
+            if index < list.values.len() {
+                list.values.remove(index);
+            }
+            Ok(NoneObject::new())
+        },
+        None => ExceptionObject::raise(ExceptionKind::ValueError, "list.remove(x): x not in list")
+    }
+}
 
-    fn extend_impl(base: &mut ListObject, values: &Vec<PyObject>) -> PyObject {
-        for item in values {
-            base.values.push(item.clone());
+/// the list is read again for each item, so an `__eq__` changing it is not running while it is
+/// borrowed, refer to CPython(list_contains)
+fn list_contains(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__contains__", 1, 1)?;
+    let mut index = 0;
+    loop {
+        let value = match args[0].downcast_refcell::<ListObject>().expect("invalid list object").values.get(index) {
+            Some(value) => value.clone(),
+            None => return Ok(Interpreter::new_bool_object(false))
+        };
+        if Interpreter::equals(&value, &args[1])? {
+            return Ok(Interpreter::new_bool_object(true));
         }
-        let mut res = ListObject::default();
-        mem::swap(&mut res, base);
-        BasePycObject::new_py_object(res)
+        index += 1;
+    }
+}
 
+fn list_clear(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "clear", 0, 0)?;
+    // the items are dropped after the borrow ends
+    let values = mem::take(&mut this_mut(&args).values);
+    drop(values);
+    Ok(NoneObject::new())
+}
+
+fn list_index(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "index", 1, 3)?;
+    let values = this(&args);
+    let (start, end) = range_args(&args, 2, values.len())?;
+    match find(&values, &args[1], start, end)? {
+        Some(index) => Ok(IntObject::new_from_i32(index as i32)),
+        None => ExceptionObject::raise(ExceptionKind::ValueError, &format!("{} is not in list", args[1].borrow().repr()?))
     }
 }
 
+fn list_count(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "count", 1, 1)?;
+    let mut count = 0;
+    for value in this(&args) {
+        if Interpreter::equals(&value, &args[1])? {
+            count += 1;
+        }
+    }
+    Ok(IntObject::new_from_i32(count))
+}
+
+fn list_reverse(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "reverse", 0, 0)?;
+    this_mut(&args).values.reverse();
+    Ok(NoneObject::new())
+}
+
+fn list_copy(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "copy", 0, 0)?;
+    Ok(ListObject::new_from_values(this(&args)))
+}
+
+/// sort `values` by the keys, `reverse` keeps the order of equal items like CPython does
+fn sort_values(values: &mut Vec<PyObject>, key: Option<PyObject>, reverse: bool) -> PyResult<()> {
+    let mut keys = match key {
+        Some(key) => values.iter().map(|value| Interpreter::call(&key, vec![value.clone()])).collect::<PyResult<Vec<_>>>()?,
+        None => values.clone()
+    };
+    if reverse {
+        values.reverse();
+        keys.reverse();
+    }
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    let result = sort::timsort(&mut order, |a, b| Interpreter::compare(&keys[*a], &keys[*b], &CmpOP::LT));
+    *values = order.into_iter().map(|i| values[i].clone()).collect();
+    if reverse {
+        values.reverse();
+    }
+    result
+}
+
+fn list_sort(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    if args.len() > 1 {
+        return ExceptionObject::raise(ExceptionKind::TypeError, "sort() takes no positional arguments");
+    }
+    let mut key = None;
+    let mut reverse = false;
+    for (name, value) in kwargs {
+        match name.as_str() {
+            "key" if value.borrow().object_type() != ObjectType::NONE => key = Some(value),
+            "key" => key = None,
+            "reverse" => reverse = Interpreter::is_true(&value)?,
+            _ => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("'{}' is an invalid keyword argument for sort()", name)
+            )
+        }
+    }
+    // the list looks empty while it is sorted, so the key and the comparisons cannot change it unnoticed
+    let mut values = mem::take(&mut this_mut(&args).values);
+    let result = sort_values(&mut values, key, reverse);
+    let modified = {
+        let mut list = this_mut(&args);
+        let modified = !list.values.is_empty();
+        mem::swap(&mut list.values, &mut values);
+        modified
+    };
+    result?;
+    if modified {
+        return ExceptionObject::raise(ExceptionKind::ValueError, "list modified during sort");
+    }
+    Ok(NoneObject::new())
+}
+
 impl PartialEq<Self> for ListObject {
     fn eq(&self, other: &Self) -> bool {
         if self.values.len() != other.values.len() {
@@ -81,26 +407,87 @@ impl PyObjectTrait for ListObject {
     fn base_object(&self) -> &BasePycObject {
         &self.base
     }
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(self.length()? > 0)
+    }
+    fn hash(&self) -> PyResult<i64> {
+        unhashable(&self.type_name())
+    }
+
+    fn length(&self) -> PyResult<usize> {
+        Ok(self.values.len())
+    }
+
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        if name == "sort" {
+            return Some(CallableObject::new_native_kw(name, Box::new(list_sort)));
+        }
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "append" => list_append,
+            "extend" => list_extend,
+            "insert" => list_insert,
+            "pop" => list_pop,
+            "remove" => list_remove,
+            "clear" => list_clear,
+            "index" => list_index,
+            "count" => list_count,
+            "reverse" => list_reverse,
+            "copy" => list_copy,
+            "__contains__" => list_contains,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+
+    fn get_item(&self, key: &PyObject) -> PyResult {
+        if let Some(slice) = key.downcast_refcell::<SliceObject>() {
+            let values = slice.positions(self.values.len())?.into_iter().map(|i| self.values[i].clone()).collect();
+            return Ok(Self::new_from_values(values));
+        }
+        match normalize_index(Self::index_arg(key)?, self.values.len()) {
+            Some(index) => Ok(self.values[index].clone()),
+            None => ExceptionObject::raise(ExceptionKind::IndexError, "list index out of range")
+        }
+    }
 
-    fn to_iter(&self) -> PyObject {
-        IterObject::new(self.values.clone())
+    fn set_item(&mut self, key: PyObject, value: PyObject) -> PyResult<()> {
+        if let Some(slice) = key.downcast_refcell::<SliceObject>() {
+            return self.set_slice(&slice, &value);
+        }
+        match normalize_index(Self::index_arg(&key)?, self.values.len()) {
+            Some(index) => {
+                self.values[index] = value;
+                Ok(())
+            },
+            None => ExceptionObject::raise(ExceptionKind::IndexError, "list assignment index out of range")
+        }
+    }
+
+    fn del_item(&mut self, key: &PyObject) -> PyResult<()> {
+        if let Some(slice) = key.downcast_refcell::<SliceObject>() {
+            return self.del_slice(&slice);
+        }
+        match normalize_index(Self::index_arg(key)?, self.values.len()) {
+            Some(index) => {
+                self.values.remove(index);
+                Ok(())
+            },
+            None => ExceptionObject::raise(ExceptionKind::IndexError, "list assignment index out of range")
+        }
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        repr_guard(object_address(self), "[...]", || Ok(format!("[{}]", join_repr(&self.values)?)))
     }
 }
 
 impl fmt::Display for ListObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "[").unwrap();
-        for (i, entry) in self.values.iter().enumerate() {
-            if i != 0 {
-                write!(f, ", ").unwrap();
-            }
-            write!(f, "{}", entry.borrow()).unwrap();
-        }
-        write!(f, "]")
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
     }
 }
 impl fmt::Debug for ListObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         write!(f, "ListObject({:?})", self.values)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,104 @@
+use crate::object::{BasePycObject, ExceptionKind, ExceptionObject, InstanceObject, StringObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::utils::{DowncastTrait, PyObject, PyResult};
+
+/// the data descriptor of a name in `__slots__`, which stores the attribute at a fixed index of the
+/// instance instead of its dict, refer to CPython(PyMemberDescrObject)
+pub struct MemberDescriptorObject {
+    base: BasePycObject,
+    name: String,
+    /// the name of the class defining the slot
+    class_name: String,
+    index: usize
+}
+
+impl MemberDescriptorObject {
+    pub fn new(name: &str, class_name: &str, index: usize) -> PyObject {
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::MEMBER_DESCRIPTOR),
+            name: name.to_string(),
+            class_name: class_name.to_string(),
+            index
+        })
+    }
+
+    /// refer to CPython(member_get)
+    pub fn get(&self, obj: &PyObject) -> PyResult {
+        let value = self.instance(obj)?.slot(self.index);
+        match value {
+            Some(value) => Ok(value),
+            None => ExceptionObject::raise(
+                ExceptionKind::AttributeError,
+                &format!("'{}' object has no attribute '{}'", obj.borrow().type_name(), self.name)
+            )
+        }
+    }
+
+    /// assign the slot, or empty it when `value` is `None`, refer to CPython(member_set)
+    pub fn set(&self, obj: &PyObject, value: Option<PyObject>) -> PyResult<()> {
+        let deleting = value.is_none();
+        let old = self.instance(obj)?.set_slot(self.index, value);
+        if deleting && old.is_none() {
+            return ExceptionObject::raise(ExceptionKind::AttributeError, &self.name);
+        }
+        Ok(())
+    }
+
+    /// `obj` when its layout has the slot, refer to CPython(descr_check)
+    fn instance<'a>(&self, obj: &'a PyObject) -> PyResult<std::cell::Ref<'a, InstanceObject>> {
+        match obj.downcast_refcell::<InstanceObject>() {
+            Some(instance) if instance.num_slots() > self.index => Ok(instance),
+            _ => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!(
+                    "descriptor '{}' for '{}' objects doesn't apply to a '{}' object",
+                    self.name, self.class_name, obj.borrow().type_name()
+                )
+            )
+        }
+    }
+}
+
+impl PartialEq<Self> for MemberDescriptorObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for MemberDescriptorObject {}
+
+impl PyObjectTrait for MemberDescriptorObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+
+    fn get_attr(&self, name: String) -> PyResult {
+        match name.as_str() {
+            "__name__" => Ok(StringObject::new_from_str(&self.name)),
+            _ => ExceptionObject::raise(
+                ExceptionKind::AttributeError,
+                &format!("'member_descriptor' object has no attribute '{}'", name)
+            )
+        }
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        Ok(format!("<member '{}' of '{}' objects>", self.name, self.class_name))
+    }
+}
+
+impl fmt::Display for MemberDescriptorObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for MemberDescriptorObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "MemberDescriptorObject({} of {})", self.name, self.class_name)
+    }
+}
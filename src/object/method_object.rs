@@ -0,0 +1,74 @@
+use crate::object::{object_address, BasePycObject, CallableObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::utils::{DowncastTrait, PyObject, PyResult};
+
+/// a function bound to the object it was looked up on
+pub struct MethodObject {
+    base: BasePycObject,
+    func: PyObject,
+    self_obj: PyObject
+}
+
+impl MethodObject {
+    pub fn new(func: PyObject, self_obj: PyObject) -> PyObject {
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::METHOD),
+            func,
+            self_obj
+        })
+    }
+
+    pub fn func(&self) -> PyObject {
+        self.func.clone()
+    }
+
+    pub fn self_obj(&self) -> PyObject {
+        self.self_obj.clone()
+    }
+}
+
+impl PartialEq<Self> for MethodObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(self.func.as_ptr(), other.func.as_ptr())
+            && std::ptr::addr_eq(self.self_obj.as_ptr(), other.self_obj.as_ptr())
+    }
+}
+
+impl Eq for MethodObject {}
+
+impl PyObjectTrait for MethodObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+    fn type_name(&self) -> String {
+        self.func.borrow().type_name().replace("function", "method")
+    }
+    fn repr(&self) -> PyResult<String> {
+        let func = self.func.downcast_refcell::<CallableObject>().expect("invalid method function");
+        if func.is_native() {
+            return Ok(format!(
+                "<built-in method {} of {} object at {:#x}>",
+                func.name(),
+                self.self_obj.borrow().type_name(),
+                object_address(&*self.self_obj.borrow())
+            ));
+        }
+        Ok(format!("<bound method {} of {}>", func.name(), self.self_obj.borrow().repr()?))
+    }
+}
+
+impl fmt::Display for MethodObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for MethodObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "MethodObject({:?})", self.func)
+    }
+}
@@ -1,31 +1,85 @@
 mod base_object;
+mod args;
 mod int_object;
 mod int_long_object;
+mod float_object;
 mod none_object;
 mod null_object;
+mod ellipsis_object;
+mod not_implemented_object;
 mod true_object;
 mod false_object;
 mod str_object;
 mod dict_object;
+mod dict_view_object;
 mod tuple_object;
 mod list_object;
 mod set_object;
 mod code_object;
 mod callable_object;
 mod iter_object;
+mod bytes_object;
+mod byte_array_object;
+mod slice_object;
+mod range_object;
+mod method_object;
+mod type_object;
+mod instance_object;
+mod cell_object;
+mod super_object;
+mod property_object;
+mod static_method_object;
+mod class_method_object;
+mod member_descriptor_object;
+mod generator_object;
+mod async_gen_step_object;
+mod native_coroutine_object;
+mod future_object;
+mod queue_object;
+mod event_object;
+mod module_object;
+mod descriptor;
+mod exception_object;
 
 pub(crate) use base_object::*;
+pub(crate) use args::*;
+pub(crate) use descriptor::*;
 pub use int_object::IntObject;
 pub use none_object::NoneObject;
 pub use null_object::NullObject;
+pub use ellipsis_object::EllipsisObject;
+pub use not_implemented_object::NotImplementedObject;
 pub use int_long_object::IntLongObject;
+pub use float_object::{FloatObject, Real};
 pub use true_object::TrueObject;
 pub use false_object::FalseObject;
 pub use str_object::*;
 pub use dict_object::DictObject;
+pub use dict_view_object::{DictViewKind, DictViewObject};
 pub use tuple_object::TupleObject;
 pub use list_object::ListObject;
 pub use set_object::SetObject;
-pub use code_object::CodeObject;
-pub use callable_object::CallableObject;
+pub use code_object::{CodeObject, ExceptionHandler, CO_ASYNC_GENERATOR, CO_COROUTINE, CO_FAST_CELL, CO_FAST_FREE, CO_VARARGS, CO_VARKEYWORDS};
+pub use callable_object::{CallableObject, Kwargs};
 pub use iter_object::IterObject;
+pub use bytes_object::{bytes_contains, bytes_get_item, bytes_repr, BytesObject};
+pub use byte_array_object::ByteArrayObject;
+pub use slice_object::SliceObject;
+pub use range_object::RangeObject;
+pub use method_object::MethodObject;
+pub use type_object::TypeObject;
+pub use instance_object::InstanceObject;
+pub use cell_object::CellObject;
+pub use super_object::SuperObject;
+pub use property_object::PropertyObject;
+pub use static_method_object::StaticMethodObject;
+pub use class_method_object::ClassMethodObject;
+pub use member_descriptor_object::MemberDescriptorObject;
+pub use generator_object::{make_exception, stop_iteration, GeneratorKind, GeneratorObject};
+pub use async_gen_step_object::{AsyncGenStepObject, AsyncGenValueObject};
+pub use native_coroutine_object::{NativeCoroutineObject, Poll};
+pub use future_object::{future, FutureCallback, FutureObject};
+pub use queue_object::QueueObject;
+pub use event_object::EventObject;
+pub use module_object::ModuleObject;
+pub use exception_object::{ExceptionKind, ExceptionObject};
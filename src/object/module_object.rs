@@ -0,0 +1,86 @@
+use crate::object::{object_address, BasePycObject, ExceptionKind, ExceptionObject, StringObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use crate::utils::{PyObject, PyResult};
+
+/// a module implemented in Rust, whose attributes are the names it defines,
+/// refer to CPython(PyModuleObject)
+pub struct ModuleObject {
+    base: BasePycObject,
+    name: String,
+    dict: RefCell<HashMap<String, PyObject>>
+}
+
+impl ModuleObject {
+    pub fn new(name: &str, dict: HashMap<String, PyObject>) -> PyObject {
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::MODULE),
+            name: name.to_string(),
+            dict: RefCell::new(dict)
+        })
+    }
+}
+
+impl PartialEq<Self> for ModuleObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for ModuleObject {}
+
+impl PyObjectTrait for ModuleObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+
+    /// refer to CPython(module_getattro)
+    fn get_attr(&self, name: String) -> PyResult {
+        if name == "__name__" {
+            return Ok(StringObject::new_from_str(&self.name));
+        }
+        match self.dict.borrow().get(&name) {
+            Some(value) => Ok(value.clone()),
+            None => ExceptionObject::raise(
+                ExceptionKind::AttributeError,
+                &format!("module '{}' has no attribute '{}'", self.name, name)
+            )
+        }
+    }
+
+    fn set_attr(&self, name: String, value: PyObject) -> PyResult<()> {
+        self.dict.borrow_mut().insert(name, value);
+        Ok(())
+    }
+
+    fn del_attr(&self, name: String) -> PyResult<()> {
+        match self.dict.borrow_mut().remove(&name) {
+            Some(_) => Ok(()),
+            None => ExceptionObject::raise(
+                ExceptionKind::AttributeError,
+                &format!("module '{}' has no attribute '{}'", self.name, name)
+            )
+        }
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        Ok(format!("<module '{}' (built-in)>", self.name))
+    }
+}
+
+impl fmt::Display for ModuleObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for ModuleObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "ModuleObject({} at {:#x})", self.name, object_address(self))
+    }
+}
@@ -0,0 +1,174 @@
+use crate::object::{check_args, make_exception, object_address, stop_iteration, BasePycObject, CallableObject, ExceptionKind, ExceptionObject, NoneObject, StringObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::cell::{Cell, Ref, RefCell};
+use std::fmt;
+use std::rc::Rc;
+use crate::interpreter::FrameExit;
+use crate::utils::DowncastTrait;
+
+use crate::utils::{PyObject, PyResult};
+
+/// how far a step of a native coroutine got
+pub enum Poll {
+    /// the coroutine has finished with the value
+    Ready(PyObject),
+    /// the coroutine waits for the future it yields, or yields `None` to give way to other tasks
+    Pending(PyObject)
+}
+
+/// a step of a native coroutine, given the exception thrown into it if any
+pub type CoroutineStep = Box<dyn FnMut(Option<PyObject>) -> PyResult<Poll>>;
+
+/// a coroutine implemented in Rust, a state machine stepped by its closure as a task sends to it
+/// or throws into it, it stands for the `async def` functions of the built-in modules
+pub struct NativeCoroutineObject {
+    base: BasePycObject,
+    name: String,
+    /// the closure, dropped once the coroutine has finished
+    step: RefCell<Option<CoroutineStep>>,
+    started: Cell<bool>,
+    running: Cell<bool>
+}
+
+fn coroutine(obj: &PyObject) -> Ref<'_, NativeCoroutineObject> {
+    obj.downcast_refcell::<NativeCoroutineObject>().expect("invalid coroutine object")
+}
+
+fn step_result(exit: FrameExit) -> PyResult {
+    match exit {
+        FrameExit::Yield(value) => Ok(value),
+        FrameExit::Return(value) => Err(stop_iteration(value)),
+        FrameExit::Generator => unreachable!("a native coroutine does not return a generator")
+    }
+}
+
+fn coro_send(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "send", 1, 1)?;
+    let exit = coroutine(&args[0]).send(args[1].clone())?;
+    step_result(exit)
+}
+
+fn coro_throw(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "throw", 1, 3)?;
+    let exc = make_exception(&args[1], args.get(2))?;
+    let exit = coroutine(&args[0]).throw(exc)?;
+    step_result(exit)
+}
+
+fn coro_close(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "close", 0, 0)?;
+    coroutine(&args[0]).close()?;
+    Ok(NoneObject::new())
+}
+
+impl NativeCoroutineObject {
+    pub fn new(name: &str, step: CoroutineStep) -> PyObject {
+        Rc::new(RefCell::new(Self {
+            base: BasePycObject::new(ObjectType::NATIVE_COROUTINE),
+            name: name.to_string(),
+            step: RefCell::new(Some(step)),
+            started: Cell::new(false),
+            running: Cell::new(false)
+        }))
+    }
+
+    /// run a step of the closure, which is dropped once the coroutine has finished
+    fn run(&self, throw: Option<PyObject>) -> PyResult<FrameExit> {
+        if self.running.get() {
+            return ExceptionObject::raise(ExceptionKind::ValueError, "coroutine already executing");
+        }
+        let mut step = match self.step.borrow_mut().take() {
+            Some(step) => step,
+            None => return ExceptionObject::raise(ExceptionKind::RuntimeError, "cannot reuse already awaited coroutine")
+        };
+        self.started.set(true);
+        self.running.set(true);
+        let poll = step(throw);
+        self.running.set(false);
+        match poll? {
+            Poll::Ready(value) => Ok(FrameExit::Return(value)),
+            Poll::Pending(value) => {
+                *self.step.borrow_mut() = Some(step);
+                Ok(FrameExit::Yield(value))
+            }
+        }
+    }
+
+    pub fn send(&self, value: PyObject) -> PyResult<FrameExit> {
+        if !self.started.get() && value.borrow().object_type() != ObjectType::NONE {
+            return ExceptionObject::raise(ExceptionKind::TypeError, "can't send non-None value to a just-started coroutine");
+        }
+        self.run(None)
+    }
+
+    pub fn throw(&self, exc: PyObject) -> PyResult<FrameExit> {
+        self.run(Some(exc))
+    }
+
+    /// refer to CPython(gen_close)
+    pub fn close(&self) -> PyResult<()> {
+        if !self.started.get() || self.step.borrow().is_none() {
+            self.step.borrow_mut().take();
+            return Ok(());
+        }
+        match self.run(Some(ExceptionObject::new(ExceptionKind::GeneratorExit, vec![]))) {
+            Ok(FrameExit::Yield(_)) => ExceptionObject::raise(ExceptionKind::RuntimeError, "coroutine ignored GeneratorExit"),
+            Ok(_) => Ok(()),
+            Err(err) if ExceptionObject::is_exception(&err, ExceptionKind::GeneratorExit) => Ok(()),
+            Err(err) => Err(err)
+        }
+    }
+}
+
+impl PartialEq<Self> for NativeCoroutineObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for NativeCoroutineObject {}
+
+impl PyObjectTrait for NativeCoroutineObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "send" => coro_send,
+            "throw" => coro_throw,
+            "close" => coro_close,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+
+    fn get_attr(&self, name: String) -> PyResult {
+        match name.as_str() {
+            "__name__" | "__qualname__" => Ok(StringObject::new_from_str(&self.name)),
+            _ => ExceptionObject::raise(
+                ExceptionKind::AttributeError,
+                &format!("'coroutine' object has no attribute '{}'", name)
+            )
+        }
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        Ok(format!("<coroutine object {} at {:#x}>", self.name, object_address(self)))
+    }
+}
+
+impl fmt::Display for NativeCoroutineObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for NativeCoroutineObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "NativeCoroutineObject({} at {:#x})", self.name, object_address(self))
+    }
+}
@@ -2,17 +2,20 @@ use crate::object::BasePycObject;
 use crate::object::PyObjectTrait;
 use crate::object::ObjectType;
 use std::fmt;
-use crate::utils::PyObject;
+use crate::utils::{CmpOP, PyObject, PyResult};
 
 pub struct NoneObject {
     base: BasePycObject,
 }
 
+thread_local! {
+    static NONE: PyObject = BasePycObject::new_py_object(NoneObject::new_raw());
+}
+
 impl NoneObject {
+    /// the None singleton, so `is None` is an identity test
     pub fn new() -> PyObject {
-        BasePycObject::new_py_object(Self {
-            base: BasePycObject::new_from_char('N'),
-        })
+        NONE.with(|obj| obj.clone())
     }
     #[allow(dead_code)]
     pub fn new_raw() -> Self {
@@ -36,6 +39,21 @@ impl PyObjectTrait for NoneObject {
     fn base_object(&self) -> &BasePycObject {
         &self.base
     }
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(false)
+    }
+    fn hash(&self) -> PyResult<i64> {
+        // a constant like CPython 3.12 does, since every None compares equal
+        Ok(0xFCA86420)
+    }
+    fn compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        let same = other.borrow().object_type() == ObjectType::NONE;
+        Ok(match op {
+            CmpOP::EQ => Some(same),
+            CmpOP::NEQ => Some(!same),
+            _ => None
+        })
+    }
 }
 
 impl fmt::Debug for NoneObject {
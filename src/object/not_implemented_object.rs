@@ -0,0 +1,50 @@
+use crate::object::BasePycObject;
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::utils::PyObject;
+
+pub struct NotImplementedObject {
+    base: BasePycObject,
+}
+
+thread_local! {
+    static NOT_IMPLEMENTED: PyObject = BasePycObject::new_py_object(NotImplementedObject {
+        base: BasePycObject::new(ObjectType::NOT_IMPLEMENTED),
+    });
+}
+
+impl NotImplementedObject {
+    /// the NotImplemented singleton returned by binary special methods which do not support the operands
+    pub fn new() -> PyObject {
+        NOT_IMPLEMENTED.with(|obj| obj.clone())
+    }
+}
+
+impl PartialEq<Self> for NotImplementedObject {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for NotImplementedObject {}
+
+impl PyObjectTrait for NotImplementedObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+}
+
+impl fmt::Display for NotImplementedObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "NotImplemented")
+    }
+}
+impl fmt::Debug for NotImplementedObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "NotImplementedObject")
+    }
+}
@@ -0,0 +1,187 @@
+use crate::object::{check_args, object_address, opt_arg, BasePycObject, CallableObject, ExceptionKind, ExceptionObject, Kwargs, NoneObject, StringObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::interpreter::Interpreter;
+use crate::utils::{DowncastTrait, PyObject, PyResult};
+
+/// a data descriptor computing an attribute with functions, refer to CPython(Objects/descrobject.c)
+pub struct PropertyObject {
+    base: BasePycObject,
+    fget: Option<PyObject>,
+    fset: Option<PyObject>,
+    fdel: Option<PyObject>,
+    doc: Option<PyObject>,
+    /// the attribute name given by `__set_name__`, used by the error messages
+    name: Option<String>
+}
+
+/// a copy of the property with one of its functions replaced, refer to CPython(property_copy)
+fn property_copy(args: Vec<PyObject>, name: &str) -> PyResult {
+    check_args(&args, name, 1, 1)?;
+    let mut property = args[0].downcast_refcell::<PropertyObject>().expect("invalid property object").copy();
+    let func = opt_arg(&args, 1);
+    match name {
+        "getter" => property.fget = func,
+        "setter" => property.fset = func,
+        _ => property.fdel = func
+    }
+    Ok(BasePycObject::new_py_object(property))
+}
+
+fn property_getter(args: Vec<PyObject>) -> PyResult {
+    property_copy(args, "getter")
+}
+
+fn property_setter(args: Vec<PyObject>) -> PyResult {
+    property_copy(args, "setter")
+}
+
+fn property_deleter(args: Vec<PyObject>) -> PyResult {
+    property_copy(args, "deleter")
+}
+
+fn property_set_name(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__set_name__", 2, 2)?;
+    let name = StringObject::extract(&args[2])?;
+    args[0].downcast_refcell_mut::<PropertyObject>().expect("invalid property object").name = Some(name);
+    Ok(NoneObject::new())
+}
+
+impl PropertyObject {
+    /// property(fget=None, fset=None, fdel=None, doc=None)
+    pub fn new_from_args(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+        const PARAMS: [&str; 4] = ["fget", "fset", "fdel", "doc"];
+        if args.len() > PARAMS.len() {
+            return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("property() takes at most 4 arguments ({} given)", args.len())
+            );
+        }
+        let mut values: Vec<Option<PyObject>> = vec![None; PARAMS.len()];
+        for (i, value) in values.iter_mut().enumerate().take(args.len()) {
+            *value = opt_arg(&args, i);
+        }
+        for (key, value) in kwargs {
+            let index = match PARAMS.iter().position(|param| *param == key) {
+                Some(index) => index,
+                None => return ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    &format!("'{}' is an invalid keyword argument for property()", key)
+                )
+            };
+            if index < args.len() {
+                return ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    &format!("argument for property() given by name ('{}') and position ({})", key, index + 1)
+                );
+            }
+            values[index] = Some(value).filter(|value| value.borrow().object_type() != ObjectType::NONE);
+        }
+        let mut values = values.into_iter();
+        Ok(BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::PROPERTY),
+            fget: values.next().unwrap(),
+            fset: values.next().unwrap(),
+            fdel: values.next().unwrap(),
+            doc: values.next().unwrap(),
+            name: None
+        }))
+    }
+
+    fn copy(&self) -> Self {
+        Self {
+            base: BasePycObject::new(ObjectType::PROPERTY),
+            fget: self.fget.clone(),
+            fset: self.fset.clone(),
+            fdel: self.fdel.clone(),
+            doc: self.doc.clone(),
+            name: self.name.clone()
+        }
+    }
+
+    /// refer to CPython(property_descr_get)
+    pub fn get(&self, obj: &PyObject) -> PyResult {
+        match &self.fget {
+            Some(fget) => Interpreter::call(fget, vec![obj.clone()]),
+            None => self.missing("getter", obj)
+        }
+    }
+
+    /// refer to CPython(property_descr_set)
+    pub fn set(&self, obj: &PyObject, value: PyObject) -> PyResult<()> {
+        match &self.fset {
+            Some(fset) => Interpreter::call(fset, vec![obj.clone(), value]).map(|_| ()),
+            None => self.missing("setter", obj)
+        }
+    }
+
+    pub fn delete(&self, obj: &PyObject) -> PyResult<()> {
+        match &self.fdel {
+            Some(fdel) => Interpreter::call(fdel, vec![obj.clone()]).map(|_| ()),
+            None => self.missing("deleter", obj)
+        }
+    }
+
+    fn missing<T>(&self, function: &str, obj: &PyObject) -> PyResult<T> {
+        let type_name = obj.borrow().type_name();
+        let msg = match &self.name {
+            Some(name) => format!("property '{}' of '{}' object has no {}", name, type_name, function),
+            None => format!("property of '{}' object has no {}", type_name, function)
+        };
+        ExceptionObject::raise(ExceptionKind::AttributeError, &msg)
+    }
+}
+
+impl PartialEq<Self> for PropertyObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for PropertyObject {}
+
+impl PyObjectTrait for PropertyObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "getter" => property_getter,
+            "setter" => property_setter,
+            "deleter" => property_deleter,
+            "__set_name__" => property_set_name,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+
+    fn get_attr(&self, name: String) -> PyResult {
+        let value = match name.as_str() {
+            "fget" => &self.fget,
+            "fset" => &self.fset,
+            "fdel" => &self.fdel,
+            "__doc__" => &self.doc,
+            _ => return ExceptionObject::raise(
+                ExceptionKind::AttributeError,
+                &format!("'property' object has no attribute '{}'", name)
+            )
+        };
+        Ok(value.clone().unwrap_or_else(NoneObject::new))
+    }
+}
+
+impl fmt::Display for PropertyObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "<property object at {:#x}>", object_address(self))
+    }
+}
+impl fmt::Debug for PropertyObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "PropertyObject({:?})", self.name)
+    }
+}
@@ -0,0 +1,327 @@
+use crate::object::{check_args, future, join_repr, object_address, BasePycObject, CallableObject, EventObject, ExceptionKind, ExceptionObject, FutureObject, GeneratorObject, IntLongObject, NativeCoroutineObject, NoneObject, Poll};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+use std::rc::{Rc, Weak};
+use crate::interpreter::{FrameExit, Interpreter};
+use crate::utils::DowncastTrait;
+
+use crate::utils::{PyObject, PyResult};
+
+/// a FIFO queue of producer and consumer tasks, refer to asyncio(queues.Queue)
+pub struct QueueObject {
+    base: BasePycObject,
+    /// the number of items above which `put()` waits, unbounded when it is not positive
+    maxsize: i64,
+    items: RefCell<VecDeque<PyObject>>,
+    /// the futures of the tasks waiting in `get()`
+    getters: RefCell<VecDeque<PyObject>>,
+    /// the futures of the tasks waiting in `put()`
+    putters: RefCell<VecDeque<PyObject>>,
+    unfinished_tasks: Cell<usize>,
+    /// set while every item put has been marked done, for `join()`
+    finished: PyObject,
+    this: Weak<RefCell<QueueObject>>
+}
+
+fn queue(obj: &PyObject) -> Ref<'_, QueueObject> {
+    obj.downcast_refcell::<QueueObject>().expect("invalid queue object")
+}
+
+fn queue_qsize(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "qsize", 0, 0)?;
+    Ok(IntLongObject::new_from_i64(queue(&args[0]).qsize() as i64))
+}
+
+fn queue_empty(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "empty", 0, 0)?;
+    Ok(Interpreter::new_bool_object(queue(&args[0]).qsize() == 0))
+}
+
+fn queue_full(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "full", 0, 0)?;
+    Ok(Interpreter::new_bool_object(queue(&args[0]).full()))
+}
+
+fn queue_put_nowait(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "put_nowait", 1, 1)?;
+    queue(&args[0]).put_nowait(args[1].clone())?;
+    Ok(NoneObject::new())
+}
+
+fn queue_get_nowait(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "get_nowait", 0, 0)?;
+    queue(&args[0]).get_nowait()
+}
+
+fn queue_put(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "put", 1, 1)?;
+    Ok(queue(&args[0]).put(args[1].clone()))
+}
+
+fn queue_get(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "get", 0, 0)?;
+    Ok(queue(&args[0]).get())
+}
+
+fn queue_task_done(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "task_done", 0, 0)?;
+    queue(&args[0]).task_done()?;
+    Ok(NoneObject::new())
+}
+
+fn queue_join(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "join", 0, 0)?;
+    Ok(queue(&args[0]).join())
+}
+
+/// resolve the first future still waiting, refer to asyncio(queues.Queue._wakeup_next)
+fn wakeup_next(waiters: &RefCell<VecDeque<PyObject>>) -> PyResult<()> {
+    loop {
+        let waiter = match waiters.borrow_mut().pop_front() {
+            Some(waiter) => waiter,
+            None => return Ok(())
+        };
+        let waiter = future(&waiter);
+        if !waiter.done() {
+            return waiter.set_result(NoneObject::new());
+        }
+    }
+}
+
+fn remove_waiter(waiters: &RefCell<VecDeque<PyObject>>, waiter: &PyObject) {
+    waiters.borrow_mut().retain(|other| !Rc::ptr_eq(other, waiter));
+}
+
+impl QueueObject {
+    pub fn new(maxsize: i64) -> PyObject {
+        let finished = EventObject::new();
+        finished.downcast_refcell::<EventObject>().expect("invalid event object").set().expect("a new event is unset");
+        Rc::new_cyclic(|this| RefCell::new(Self {
+            base: BasePycObject::new(ObjectType::QUEUE),
+            maxsize,
+            items: RefCell::new(VecDeque::new()),
+            getters: RefCell::new(VecDeque::new()),
+            putters: RefCell::new(VecDeque::new()),
+            unfinished_tasks: Cell::new(0),
+            finished,
+            this: this.clone()
+        }))
+    }
+
+    fn this(&self) -> PyObject {
+        self.this.upgrade().expect("queue should be alive")
+    }
+
+    fn finished(&self) -> Ref<'_, EventObject> {
+        self.finished.downcast_refcell::<EventObject>().expect("invalid event object")
+    }
+
+    pub fn qsize(&self) -> usize {
+        self.items.borrow().len()
+    }
+
+    pub fn full(&self) -> bool {
+        self.maxsize > 0 && self.qsize() as i64 >= self.maxsize
+    }
+
+    pub fn put_nowait(&self, item: PyObject) -> PyResult<()> {
+        if self.full() {
+            return Err(ExceptionObject::new(ExceptionKind::QueueFull, vec![]));
+        }
+        self.items.borrow_mut().push_back(item);
+        self.unfinished_tasks.set(self.unfinished_tasks.get() + 1);
+        self.finished().clear();
+        wakeup_next(&self.getters)
+    }
+
+    pub fn get_nowait(&self) -> PyResult {
+        let item = match self.items.borrow_mut().pop_front() {
+            Some(item) => item,
+            None => return Err(ExceptionObject::new(ExceptionKind::QueueEmpty, vec![]))
+        };
+        wakeup_next(&self.putters)?;
+        Ok(item)
+    }
+
+    /// refer to asyncio(queues.Queue.task_done)
+    pub fn task_done(&self) -> PyResult<()> {
+        if self.unfinished_tasks.get() == 0 {
+            return ExceptionObject::raise(ExceptionKind::ValueError, "task_done() called too many times");
+        }
+        self.unfinished_tasks.set(self.unfinished_tasks.get() - 1);
+        if self.unfinished_tasks.get() == 0 {
+            self.finished().set()?;
+        }
+        Ok(())
+    }
+
+    /// the coroutine of `put(item)`, which waits for a free slot while the queue is full
+    pub fn put(&self, item: PyObject) -> PyObject {
+        let this = self.this();
+        let mut putter: Option<PyObject> = None;
+        NativeCoroutineObject::new("put", Box::new(move |throw| {
+            let queue = queue(&this);
+            if let Some(exc) = throw {
+                if let Some(putter) = putter.take() {
+                    FutureObject::cancel(&putter, None);
+                    remove_waiter(&queue.putters, &putter);
+                    if !queue.full() && !future(&putter).cancelled() {
+                        wakeup_next(&queue.putters)?;
+                    }
+                }
+                return Err(exc);
+            }
+            putter = None;
+            if queue.full() {
+                let fut = FutureObject::new_future();
+                queue.putters.borrow_mut().push_back(fut.clone());
+                putter = Some(fut.clone());
+                return Ok(Poll::Pending(fut));
+            }
+            queue.put_nowait(item.clone())?;
+            Ok(Poll::Ready(NoneObject::new()))
+        }))
+    }
+
+    /// the coroutine of `get()`, which waits for an item while the queue is empty
+    pub fn get(&self) -> PyObject {
+        let this = self.this();
+        let mut getter: Option<PyObject> = None;
+        NativeCoroutineObject::new("get", Box::new(move |throw| {
+            let queue = queue(&this);
+            if let Some(exc) = throw {
+                if let Some(getter) = getter.take() {
+                    FutureObject::cancel(&getter, None);
+                    remove_waiter(&queue.getters, &getter);
+                    if queue.qsize() > 0 && !future(&getter).cancelled() {
+                        wakeup_next(&queue.getters)?;
+                    }
+                }
+                return Err(exc);
+            }
+            getter = None;
+            if queue.qsize() == 0 {
+                let fut = FutureObject::new_future();
+                queue.getters.borrow_mut().push_back(fut.clone());
+                getter = Some(fut.clone());
+                return Ok(Poll::Pending(fut));
+            }
+            Ok(Poll::Ready(queue.get_nowait()?))
+        }))
+    }
+
+    /// the coroutine of `join()`, which waits until every item put has been marked done
+    pub fn join(&self) -> PyObject {
+        let this = self.this();
+        let mut wait: Option<PyObject> = None;
+        NativeCoroutineObject::new("join", Box::new(move |throw| {
+            let wait = match &wait {
+                Some(wait) => wait.clone(),
+                None => {
+                    if let Some(exc) = throw {
+                        return Err(exc);
+                    }
+                    let queue = queue(&this);
+                    if queue.unfinished_tasks.get() == 0 {
+                        return Ok(Poll::Ready(NoneObject::new()));
+                    }
+                    let event = queue.finished().wait();
+                    wait.insert(event).clone()
+                }
+            };
+            let exit = match throw {
+                Some(exc) => GeneratorObject::throw_to(&wait, exc)?,
+                None => GeneratorObject::send_to(&wait, NoneObject::new())?
+            };
+            match exit {
+                FrameExit::Yield(value) => Ok(Poll::Pending(value)),
+                _ => Ok(Poll::Ready(NoneObject::new()))
+            }
+        }))
+    }
+
+    /// refer to asyncio(queues.Queue._format)
+    fn format(&self) -> PyResult<String> {
+        let mut result = format!("maxsize={}", self.maxsize);
+        let items = self.items.borrow();
+        if !items.is_empty() {
+            let items: Vec<PyObject> = items.iter().cloned().collect();
+            result += &format!(" _queue=[{}]", join_repr(&items)?);
+        }
+        if !self.getters.borrow().is_empty() {
+            result += &format!(" _getters[{}]", self.getters.borrow().len());
+        }
+        if !self.putters.borrow().is_empty() {
+            result += &format!(" _putters[{}]", self.putters.borrow().len());
+        }
+        if self.unfinished_tasks.get() > 0 {
+            result += &format!(" tasks={}", self.unfinished_tasks.get());
+        }
+        Ok(result)
+    }
+}
+
+impl PartialEq<Self> for QueueObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for QueueObject {}
+
+impl PyObjectTrait for QueueObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "qsize" => queue_qsize,
+            "empty" => queue_empty,
+            "full" => queue_full,
+            "put_nowait" => queue_put_nowait,
+            "get_nowait" => queue_get_nowait,
+            "put" => queue_put,
+            "get" => queue_get,
+            "task_done" => queue_task_done,
+            "join" => queue_join,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+
+    fn get_attr(&self, name: String) -> PyResult {
+        match name.as_str() {
+            "maxsize" => Ok(IntLongObject::new_from_i64(self.maxsize)),
+            _ => ExceptionObject::raise(
+                ExceptionKind::AttributeError,
+                &format!("'Queue' object has no attribute '{}'", name)
+            )
+        }
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        Ok(format!("<Queue at {:#x} {}>", object_address(self), self.format()?))
+    }
+
+    fn str(&self) -> PyResult<String> {
+        Ok(format!("<Queue {}>", self.format()?))
+    }
+}
+
+impl fmt::Display for QueueObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for QueueObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "QueueObject({} at {:#x})", self.maxsize, object_address(self))
+    }
+}
@@ -0,0 +1,235 @@
+use crate::object::{check_args, BasePycObject, CallableObject, ExceptionKind, ExceptionObject, IntLongObject, IterObject, NoneObject, Real, SliceObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::interpreter::Interpreter;
+use crate::utils::{hash, CmpOP, DowncastTrait, PyObject, PyResult};
+
+pub struct RangeObject {
+    base: BasePycObject,
+    start: i64,
+    stop: i64,
+    step: i64
+}
+
+/// an integer argument of range(), bool counts as an int
+fn index_value(obj: &PyObject) -> PyResult<i64> {
+    match Real::extract(obj) {
+        Some(Real::Int(value)) => Ok(value),
+        _ => ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("'{}' object cannot be interpreted as an integer", obj.borrow().type_name())
+        )
+    }
+}
+
+fn range_count(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "count", 1, 1)?;
+    let range = args[0].downcast_refcell::<RangeObject>().expect("invalid range object");
+    if let Some(Real::Int(value)) = Real::extract(&args[1]) {
+        return Ok(IntLongObject::new_from_i64(range.position(value).is_some() as i64));
+    }
+    let mut count = 0;
+    for i in 0..range.len() {
+        if Interpreter::equals(&IntLongObject::new_from_i64(range.item(i)), &args[1])? {
+            count += 1;
+        }
+    }
+    Ok(IntLongObject::new_from_i64(count))
+}
+
+fn range_index(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "index", 1, 1)?;
+    let range = args[0].downcast_refcell::<RangeObject>().expect("invalid range object");
+    if let Some(Real::Int(value)) = Real::extract(&args[1]) {
+        if let Some(i) = range.position(value) {
+            return Ok(IntLongObject::new_from_i64(i));
+        }
+    } else {
+        for i in 0..range.len() {
+            if Interpreter::equals(&IntLongObject::new_from_i64(range.item(i)), &args[1])? {
+                return Ok(IntLongObject::new_from_i64(i));
+            }
+        }
+    }
+    ExceptionObject::raise(ExceptionKind::ValueError, &format!("{} is not in range", args[1].borrow().repr()?))
+}
+
+impl RangeObject {
+    pub fn new(start: i64, stop: i64, step: i64) -> PyObject {
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::RANGE),
+            start,
+            stop,
+            step
+        })
+    }
+
+    /// range(stop) or range(start, stop[, step])
+    pub fn new_from_args(args: Vec<PyObject>) -> PyResult {
+        let (start, stop, step) = match args.len() {
+            0 => return ExceptionObject::raise(ExceptionKind::TypeError, "range expected at least 1 argument, got 0"),
+            1 => (0, index_value(&args[0])?, 1),
+            2 => (index_value(&args[0])?, index_value(&args[1])?, 1),
+            3 => (index_value(&args[0])?, index_value(&args[1])?, index_value(&args[2])?),
+            n => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("range expected at most 3 arguments, got {}", n)
+            )
+        };
+        if step == 0 {
+            return ExceptionObject::raise(ExceptionKind::ValueError, "range() arg 3 must not be zero");
+        }
+        Ok(Self::new(start, stop, step))
+    }
+
+    /// the number of items, refer to CPython(compute_range_length)
+    fn len(&self) -> i64 {
+        if self.step > 0 && self.start < self.stop {
+            (self.stop - self.start - 1) / self.step + 1
+        } else if self.step < 0 && self.start > self.stop {
+            (self.start - self.stop - 1) / (-self.step) + 1
+        } else {
+            0
+        }
+    }
+
+    fn item(&self, i: i64) -> i64 {
+        self.start + i * self.step
+    }
+
+    /// the index of `value` in the range, computed without walking it
+    fn position(&self, value: i64) -> Option<i64> {
+        let inside = if self.step > 0 {
+            self.start <= value && value < self.stop
+        } else {
+            self.stop < value && value <= self.start
+        };
+        if !inside || (value - self.start) % self.step != 0 {
+            return None;
+        }
+        Some((value - self.start) / self.step)
+    }
+}
+
+impl PartialEq<Self> for RangeObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start && self.stop == other.stop && self.step == other.step
+    }
+}
+
+impl Eq for RangeObject {}
+
+impl PyObjectTrait for RangeObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(self.len() > 0)
+    }
+
+    /// equal ranges hash alike, refer to CPython(range_hash)
+    fn hash(&self) -> PyResult<i64> {
+        let length = self.len();
+        let none = NoneObject::new().borrow().hash()?;
+        let lanes = match length {
+            0 => vec![hash::hash_int(0), none, none],
+            1 => vec![hash::hash_int(1), hash::hash_int(self.start), none],
+            _ => vec![hash::hash_int(length), hash::hash_int(self.start), hash::hash_int(self.step)]
+        };
+        Ok(hash::hash_tuple(&lanes))
+    }
+
+    /// ranges are equal when they produce the same items, refer to CPython(range_equals)
+    fn compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        let other = match other.downcast_refcell::<RangeObject>() {
+            Some(other) if matches!(op, CmpOP::EQ | CmpOP::NEQ) => other,
+            _ => return Ok(None)
+        };
+        let length = self.len();
+        let equal = length == other.len()
+            && (length == 0 || (self.start == other.start && (length == 1 || self.step == other.step)));
+        Ok(Some(equal == (*op == CmpOP::EQ)))
+    }
+
+    fn length(&self) -> PyResult<usize> {
+        Ok(self.len() as usize)
+    }
+
+    fn to_iter(&self) -> PyResult {
+        Ok(IterObject::new_range(self.start, self.step, self.len()))
+    }
+
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "count" => range_count,
+            "index" => range_index,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+
+    fn get_attr(&self, name: String) -> PyResult {
+        match name.as_str() {
+            "start" => Ok(IntLongObject::new_from_i64(self.start)),
+            "stop" => Ok(IntLongObject::new_from_i64(self.stop)),
+            "step" => Ok(IntLongObject::new_from_i64(self.step)),
+            _ => ExceptionObject::raise(
+                ExceptionKind::AttributeError,
+                &format!("'range' object has no attribute '{}'", name)
+            )
+        }
+    }
+
+    fn get_item(&self, key: &PyObject) -> PyResult {
+        let length = self.len();
+        if let Some(slice) = key.downcast_refcell::<SliceObject>() {
+            // map the clamped slice bounds through the range, refer to CPython(compute_slice)
+            let (start, stop, step, _) = slice.indices(length as usize)?;
+            return Ok(Self::new(self.item(start), self.item(stop), self.step * step));
+        }
+        let index = match IntLongObject::extract(key) {
+            Some(index) => index,
+            None => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("range indices must be integers or slices, not {}", key.borrow().type_name())
+            )
+        };
+        let index = if index < 0 { index + length } else { index };
+        if index < 0 || index >= length {
+            return ExceptionObject::raise(ExceptionKind::IndexError, "range object index out of range");
+        }
+        Ok(IntLongObject::new_from_i64(self.item(index)))
+    }
+
+    /// ints are located arithmetically, anything else is compared against each item
+    fn contains(&self, item: &PyObject) -> PyResult<bool> {
+        if let Some(Real::Int(value)) = Real::extract(item) {
+            return Ok(self.position(value).is_some());
+        }
+        for i in 0..self.len() {
+            if Interpreter::equals(&IntLongObject::new_from_i64(self.item(i)), item)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl fmt::Display for RangeObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        if self.step == 1 {
+            write!(f, "range({}, {})", self.start, self.stop)
+        } else {
+            write!(f, "range({}, {}, {})", self.start, self.stop, self.step)
+        }
+    }
+}
+impl fmt::Debug for RangeObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "RangeObject({}, {}, {})", self.start, self.stop, self.step)
+    }
+}
@@ -1,42 +1,788 @@
-use crate::object::BasePycObject;
+use crate::object::{check_args, join_repr, object_address, repr_guard, unhashable, BasePycObject, CallableObject, DictObject, ExceptionKind, ExceptionObject, IterObject, NoneObject};
 use crate::object::PyObjectTrait;
 use crate::object::ObjectType;
 use std::fmt;
+use std::rc::Rc;
 use crate::{InputStream, PycParser};
-use crate::utils::{Magic, PyObject};
+use crate::interpreter::Interpreter;
+use crate::utils::{CmpOP, DowncastTrait, Magic, PyObject, PyResult};
 
+// the open addressing table of CPython(Objects/setobject.c), a probe checks a few neighbouring
+// slots before jumping, so the iteration order agrees with CPython's
+const MIN_SIZE: usize = 8;
+const LINEAR_PROBES: usize = 9;
+const PERTURB_SHIFT: u32 = 5;
+
+#[derive(Clone)]
+enum Slot {
+    Empty,
+    Dummy,
+    Active(i64, PyObject)
+}
+
+/// where a lookup is in the probe sequence of a hash
+struct Probe {
+    start: usize,
+    offset: usize,
+    perturb: u64
+}
+
+impl Probe {
+    fn new(hash: i64, mask: usize) -> Self {
+        Self { start: hash as u64 as usize & mask, offset: 0, perturb: hash as u64 }
+    }
+}
+
+/// what a probe found next
+enum Candidate {
+    Absent,
+    Found(usize),
+    /// a key with the same hash, which needs comparing
+    Compare(usize, PyObject)
+}
+
+/// `set` and `frozenset`, told apart by the object type
 pub struct SetObject {
     base: BasePycObject,
-    values: Vec<PyObject>
+    table: Vec<Slot>,
+    // number of active and dummy slots
+    fill: usize,
+    used: usize,
+    // where `pop` starts searching
+    finger: usize
 }
 
 impl SetObject {
     pub fn new(stream: &mut InputStream, magic: Magic) -> PyObject {
+        Self::marshal(stream, magic, ObjectType::SET)
+    }
+
+    pub fn new_frozen(stream: &mut InputStream, magic: Magic) -> PyObject {
+        Self::marshal(stream, magic, ObjectType::FROZENSET)
+    }
+
+    fn marshal(stream: &mut InputStream, magic: Magic, object_type: ObjectType) -> PyObject {
         let length = stream.read_u32().unwrap();
-        let mut values = vec![];
+        let mut set = Self::new_raw(object_type);
         for _ in 0..length {
-            values.push(PycParser::marshal_object(stream, magic));
+            let key = PycParser::marshal_object(stream, magic);
+            set.add(key).expect("set items of marshal data should be hashable");
+        }
+        BasePycObject::new_py_object(set)
+    }
+
+    fn new_raw(object_type: ObjectType) -> Self {
+        Self {
+            base: BasePycObject::new(object_type),
+            table: vec![Slot::Empty; MIN_SIZE],
+            fill: 0,
+            used: 0,
+            finger: 0
+        }
+    }
+
+    /// a set or frozenset of the items of any iterable, refer to CPython(make_new_set)
+    pub fn new_from_iterable(iterable: Option<&PyObject>, frozen: bool) -> PyResult {
+        let object_type = if frozen { ObjectType::FROZENSET } else { ObjectType::SET };
+        let mut set = Self::new_raw(object_type);
+        if let Some(iterable) = iterable {
+            set.update_internal(iterable)?;
         }
-        BasePycObject::new_py_object(Self {
-            base: BasePycObject::new_from_char('<'),
-            values
+        Ok(BasePycObject::new_py_object(set))
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.object_type() == ObjectType::FROZENSET
+    }
+
+    fn mask(&self) -> usize {
+        self.table.len() - 1
+    }
+
+    /// the active entries in table order
+    fn entries(&self) -> Vec<(i64, PyObject)> {
+        self.table.iter().filter_map(|slot| match slot {
+            Slot::Active(hash, key) => Some((*hash, key.clone())),
+            _ => None
+        }).collect()
+    }
+
+    /// the first key in the table at or after `position` with its position, refer to CPython(setiter_iternext)
+    pub fn next_entry(&self, position: usize) -> Option<(usize, PyObject)> {
+        self.table.iter().enumerate().skip(position).find_map(|(i, slot)| match slot {
+            Slot::Active(_, key) => Some((i, key.clone())),
+            _ => None
         })
     }
-}
 
-impl PartialEq<Self> for SetObject {
-    fn eq(&self, other: &Self) -> bool {
-        if self.values.len() != other.values.len() {
-            return false;
+    pub fn values(&self) -> Vec<PyObject> {
+        self.entries().into_iter().map(|(_, key)| key).collect()
+    }
+
+    /// the next slot of the probe sequence worth a look, refer to CPython(set_lookkey)
+    fn next_candidate(&self, probe: &mut Probe, key: &PyObject, hash: i64) -> Candidate {
+        let mask = self.mask();
+        loop {
+            let probes = if probe.start + LINEAR_PROBES <= mask { LINEAR_PROBES } else { 0 };
+            while probe.offset <= probes {
+                let slot = probe.start + probe.offset;
+                probe.offset += 1;
+                match &self.table[slot] {
+                    Slot::Empty => return Candidate::Absent,
+                    Slot::Active(entry_hash, entry_key) if *entry_hash == hash => {
+                        if Rc::ptr_eq(entry_key, key) {
+                            return Candidate::Found(slot);
+                        }
+                        return Candidate::Compare(slot, entry_key.clone());
+                    },
+                    _ => {}
+                }
+            }
+            probe.perturb >>= PERTURB_SHIFT;
+            probe.start = (probe.start * 5 + 1 + probe.perturb as usize) & mask;
+            probe.offset = 0;
+        }
+    }
+
+    /// the slot holding `key` in a set nothing else can reach, like a result being built
+    fn lookup(&self, key: &PyObject, hash: i64) -> PyResult<Option<usize>> {
+        let mut probe = Probe::new(hash, self.mask());
+        loop {
+            match self.next_candidate(&mut probe, key, hash) {
+                Candidate::Absent => return Ok(None),
+                Candidate::Found(slot) => return Ok(Some(slot)),
+                Candidate::Compare(slot, entry_key) => {
+                    if Interpreter::equals(&entry_key, key)? {
+                        return Ok(Some(slot));
+                    }
+                }
+            }
+        }
+    }
+
+    /// the slot holding `key` in the set object `set`, which is not borrowed while the `__eq__` of a
+    /// key runs, a comparison changing the set starts the probing over
+    fn find(set: &PyObject, key: &PyObject, hash: i64) -> PyResult<Option<usize>> {
+        'restart: loop {
+            let mask = set.downcast_refcell::<SetObject>().expect("invalid set object").mask();
+            let mut probe = Probe::new(hash, mask);
+            loop {
+                let candidate = set.downcast_refcell::<SetObject>().expect("invalid set object").next_candidate(&mut probe, key, hash);
+                match candidate {
+                    Candidate::Absent => return Ok(None),
+                    Candidate::Found(slot) => return Ok(Some(slot)),
+                    Candidate::Compare(slot, entry_key) => {
+                        let equal = Interpreter::equals(&entry_key, key)?;
+                        let this = set.downcast_refcell::<SetObject>().expect("invalid set object");
+                        let unchanged = this.mask() == mask
+                            && matches!(&this.table[slot], Slot::Active(_, current) if Rc::ptr_eq(current, &entry_key));
+                        if !unchanged {
+                            continue 'restart;
+                        }
+                        if equal {
+                            return Ok(Some(slot));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// refer to CPython(set_add_entry)
+    fn add_entry(&mut self, key: PyObject, hash: i64) -> PyResult<()> {
+        if self.lookup(&key, hash)?.is_none() {
+            self.insert_new(key, hash);
+        }
+        Ok(())
+    }
+
+    /// put a key known to be absent into the first free slot of its probe sequence, where a dummy
+    /// passed before the empty slot ending the probe is reused, refer to CPython(set_add_entry)
+    fn insert_new(&mut self, key: PyObject, hash: i64) {
+        let slot = Self::free_slot(&self.table, hash, true);
+        let reused = matches!(self.table[slot], Slot::Dummy);
+        self.table[slot] = Slot::Active(hash, key);
+        self.used += 1;
+        if reused {
+            return;
+        }
+        self.fill += 1;
+        if self.fill * 5 >= self.mask() * 3 {
+            self.resize(if self.used > 50000 { self.used * 2 } else { self.used * 4 });
+        }
+    }
+
+    /// put a key known to be absent into a table without dummies, refer to CPython(set_insert_clean)
+    fn insert_clean(table: &mut [Slot], key: PyObject, hash: i64) {
+        let slot = Self::free_slot(table, hash, false);
+        table[slot] = Slot::Active(hash, key);
+    }
+
+    /// the first slot in the probe sequence of `hash` that is empty, or a dummy too if `dummies`
+    fn free_slot(table: &[Slot], hash: i64, dummies: bool) -> usize {
+        let mask = table.len() - 1;
+        let mut perturb = hash as u64;
+        let mut i = hash as u64 as usize & mask;
+        loop {
+            let probes = if i + LINEAR_PROBES <= mask { LINEAR_PROBES } else { 0 };
+            let free = table[i..=i + probes].iter()
+                .position(|slot| matches!(slot, Slot::Empty) || dummies && matches!(slot, Slot::Dummy));
+            if let Some(offset) = free {
+                return i + offset;
+            }
+            perturb >>= PERTURB_SHIFT;
+            i = (i * 5 + 1 + perturb as usize) & mask;
+        }
+    }
+
+    /// refer to CPython(set_table_resize)
+    fn resize(&mut self, min_used: usize) {
+        let mut size = MIN_SIZE;
+        while size <= min_used {
+            size <<= 1;
+        }
+        let entries = self.entries();
+        self.table = vec![Slot::Empty; size];
+        for (hash, key) in entries {
+            Self::insert_clean(&mut self.table, key, hash);
+        }
+        self.fill = self.used;
+    }
+
+    /// drop the dummies once they make up a quarter of the table
+    fn compact(&mut self) {
+        if self.fill - self.used > self.mask() / 4 {
+            self.resize(if self.used > 50000 { self.used * 2 } else { self.used * 4 });
+        }
+    }
+
+    /// remove the key at `slot`, leaving a dummy to keep the probe chains intact
+    fn remove_at(&mut self, slot: usize) -> PyObject {
+        let key = match std::mem::replace(&mut self.table[slot], Slot::Dummy) {
+            Slot::Active(_, key) => key,
+            _ => unreachable!("only active slots are removed")
+        };
+        self.used -= 1;
+        key
+    }
+
+    fn discard_entry(&mut self, key: &PyObject, hash: i64) -> PyResult<bool> {
+        match self.lookup(key, hash)? {
+            Some(slot) => {
+                self.remove_at(slot);
+                Ok(true)
+            },
+            None => Ok(false)
+        }
+    }
+
+    pub fn add(&mut self, key: PyObject) -> PyResult<()> {
+        let hash = key.borrow().hash()?;
+        self.add_entry(key, hash)
+    }
+
+    /// `set.add` on a set object
+    fn add_key(set: &PyObject, key: PyObject, hash: i64) -> PyResult<()> {
+        if Self::find(set, &key, hash)?.is_none() {
+            set.downcast_refcell_mut::<SetObject>().expect("invalid set object").insert_new(key, hash);
+        }
+        Ok(())
+    }
+
+    /// `set.discard` on a set object, whether the key was there
+    fn discard_key(set: &PyObject, key: &PyObject, hash: i64) -> PyResult<bool> {
+        match Self::find(set, key, hash)? {
+            Some(slot) => {
+                set.downcast_refcell_mut::<SetObject>().expect("invalid set object").remove_at(slot);
+                Ok(true)
+            },
+            None => Ok(false)
+        }
+    }
+
+    fn clear(&mut self) {
+        self.table = vec![Slot::Empty; MIN_SIZE];
+        self.fill = 0;
+        self.used = 0;
+    }
+
+    /// copy all entries of `other` into this empty set, no keys need comparing, refer to CPython(set_merge)
+    fn copy_from(&mut self, other: &SetObject) {
+        if other.used == 0 {
+            return;
+        }
+        if (self.fill + other.used) * 5 >= self.mask() * 3 {
+            self.resize((self.used + other.used) * 2);
+        }
+        if self.mask() == other.mask() && other.fill == other.used {
+            // no dummies to drop, the table is copied as is
+            self.table = other.table.clone();
+        } else {
+            for (hash, key) in other.entries() {
+                Self::insert_clean(&mut self.table, key, hash);
+            }
+        }
+        self.fill = other.used;
+        self.used = other.used;
+    }
+
+    /// the keys of a set with their hashes, or of a dict or any iterable with the hashes left to
+    /// `hash_key`, taken before any key is compared
+    fn keys_of(other: &PyObject) -> PyResult<Vec<(Option<i64>, PyObject)>> {
+        if let Some(other) = other.downcast_refcell::<SetObject>() {
+            return Ok(other.entries().into_iter().map(|(hash, key)| (Some(hash), key)).collect());
+        }
+        let keys = match other.downcast_refcell::<DictObject>() {
+            Some(dict) => dict.keys(),
+            None => IterObject::collect(other)?
+        };
+        Ok(keys.into_iter().map(|key| (None, key)).collect())
+    }
+
+    /// the hash of a key from `keys_of`, a key being removed may be an unhashable set
+    fn hash_key(hash: Option<i64>, key: &PyObject, discarding: bool) -> PyResult<i64> {
+        match hash {
+            Some(hash) => Ok(hash),
+            None if discarding => Self::key_hash(key),
+            None => key.borrow().hash()
+        }
+    }
+
+    /// refer to CPython(set_update_internal)
+    fn update_internal(&mut self, other: &PyObject) -> PyResult<()> {
+        if let Some(other) = other.downcast_refcell::<SetObject>() {
+            if self.fill == 0 {
+                self.copy_from(&other);
+                return Ok(());
+            }
+        }
+        let keys = Self::keys_of(other)?;
+        self.presize(other, keys.len());
+        for (hash, key) in keys {
+            let hash = Self::hash_key(hash, &key, false)?;
+            self.add_entry(key, hash)?;
+        }
+        Ok(())
+    }
+
+    /// make room for the `count` keys of a set or dict before they are added, the keys of other
+    /// iterables are added as they come
+    fn presize(&mut self, other: &PyObject, count: usize) {
+        let sized = other.downcast_refcell::<SetObject>().is_some() || other.downcast_refcell::<DictObject>().is_some();
+        if sized && (self.fill + count) * 5 >= self.mask() * 3 {
+            self.resize((self.used + count) * 2);
+        }
+    }
+
+    /// `set.update(other)` on a set object, `other` may be any iterable
+    pub fn update(set: &PyObject, other: &PyObject) -> PyResult<()> {
+        set_update(vec![set.clone(), other.clone()]).map(|_| ())
+    }
+
+    fn contains_entry(set: &PyObject, key: &PyObject, hash: i64) -> PyResult<bool> {
+        Ok(Self::find(set, key, hash)?.is_some())
+    }
+
+    /// membership test where an unhashable set is looked up as a frozenset, refer to CPython(set_contains_key)
+    fn contains_key(set: &PyObject, key: &PyObject) -> PyResult<bool> {
+        let hash = Self::key_hash(key)?;
+        Self::contains_entry(set, key, hash)
+    }
+
+    fn key_hash(key: &PyObject) -> PyResult<i64> {
+        let res = key.borrow().hash();
+        match res {
+            Err(err) => match key.downcast_refcell::<SetObject>() {
+                Some(set) => Ok(set.frozen_hash()),
+                None => Err(err)
+            },
+            ok => ok
+        }
+    }
+
+    /// the order independent hash of the entries, refer to CPython(frozenset_hash)
+    fn frozen_hash(&self) -> i64 {
+        fn shuffle_bits(h: u64) -> u64 {
+            ((h ^ 89869747) ^ (h << 16)).wrapping_mul(3644798167)
+        }
+        let mut hash: u64 = 0;
+        for (entry_hash, _) in self.entries() {
+            hash ^= shuffle_bits(entry_hash as u64);
+        }
+        hash ^= (self.used as u64 + 1).wrapping_mul(1927868237);
+        hash ^= (hash >> 11) ^ (hash >> 25);
+        hash = hash.wrapping_mul(69069).wrapping_add(907133923);
+        if hash as i64 == -1 {
+            hash = 590923713;
+        }
+        hash as i64
+    }
+
+    fn new_like(&self) -> Self {
+        Self::new_raw(self.object_type())
+    }
+
+    fn copy(&self) -> Self {
+        let mut res = self.new_like();
+        res.copy_from(self);
+        res
+    }
+
+    /// whether all of `entries`, the keys of a set, are in the set object `other`
+    fn all_in(entries: Vec<(i64, PyObject)>, other: &PyObject) -> PyResult<bool> {
+        for (hash, key) in entries {
+            if !Self::contains_entry(other, &key, hash)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// every key of the set object `set` is in the set object `other`
+    fn is_subset(set: &PyObject, other: &PyObject) -> PyResult<bool> {
+        let (used, entries) = {
+            let set = set.downcast_refcell::<SetObject>().expect("invalid set object");
+            (set.used, set.entries())
+        };
+        if used > other.downcast_refcell::<SetObject>().expect("invalid set object").used {
+            return Ok(false);
+        }
+        Self::all_in(entries, other)
+    }
+
+    /// refer to CPython(set_intersection)
+    fn intersection(set: &PyObject, other: &PyObject) -> PyResult<Self> {
+        let mut res = set.downcast_refcell::<SetObject>().expect("invalid set object").new_like();
+        let sizes = other.downcast_refcell::<SetObject>().map(|other| other.used);
+        if let Some(other_used) = sizes {
+            let set_used = set.downcast_refcell::<SetObject>().expect("invalid set object").used;
+            let (large, small) = if other_used > set_used { (other, set) } else { (set, other) };
+            let entries = small.downcast_refcell::<SetObject>().expect("invalid set object").entries();
+            for (hash, key) in entries {
+                if Self::contains_entry(large, &key, hash)? {
+                    res.add_entry(key, hash)?;
+                }
+            }
+            return Ok(res);
+        }
+        for key in IterObject::collect(other)? {
+            let hash = key.borrow().hash()?;
+            if Self::contains_entry(set, &key, hash)? {
+                res.add_entry(key, hash)?;
+            }
+        }
+        Ok(res)
+    }
+
+    /// refer to CPython(set_difference_update_internal)
+    fn difference_update(&mut self, other: &PyObject) -> PyResult<()> {
+        for (hash, key) in Self::keys_of(other)? {
+            let hash = Self::hash_key(hash, &key, true)?;
+            self.discard_entry(&key, hash)?;
+        }
+        self.compact();
+        Ok(())
+    }
+
+    /// refer to CPython(set_difference)
+    fn difference(set: &PyObject, other: &PyObject) -> PyResult<Self> {
+        let other_size = if let Some(other) = other.downcast_refcell::<SetObject>() {
+            Some(other.used)
+        } else if let Some(dict) = other.downcast_refcell::<DictObject>() {
+            Some(dict.length()?)
+        } else {
+            None
+        };
+        let copy = {
+            let set = set.downcast_refcell::<SetObject>().expect("invalid set object");
+            match other_size {
+                Some(size) if (set.used >> 2) <= size => None,
+                // removing a few keys from a copy is cheaper when `other` is much smaller
+                _ => Some(set.copy())
+            }
+        };
+        if let Some(mut res) = copy {
+            res.difference_update(other)?;
+            return Ok(res);
+        }
+        let (mut res, entries) = {
+            let set = set.downcast_refcell::<SetObject>().expect("invalid set object");
+            (set.new_like(), set.entries())
+        };
+        for (hash, key) in entries {
+            if !Interpreter::contains(other, &key)? {
+                res.add_entry(key, hash)?;
+            }
         }
+        Ok(res)
+    }
 
-        for i in 0..self.values.len() {
-            if self.values.get(i).unwrap() != other.values.get(i).unwrap() {
-                return false;
+    /// refer to CPython(set_symmetric_difference_update)
+    fn symmetric_difference_update(&mut self, other: &PyObject) -> PyResult<()> {
+        let entries = match other.downcast_refcell::<SetObject>() {
+            Some(other) => other.entries(),
+            None => {
+                let mut other_set = self.new_like();
+                other_set.update_internal(other)?;
+                other_set.entries()
+            }
+        };
+        for (hash, key) in entries {
+            if !self.discard_entry(&key, hash)? {
+                self.add_entry(key, hash)?;
             }
         }
+        Ok(())
+    }
 
-        return true;
+    fn check_set(obj: &PyObject) -> bool {
+        obj.downcast_refcell::<SetObject>().is_some()
+    }
+
+    /// the binary operators `| & - ^` and their in-place forms, `None` when an operand is not a set
+    pub fn binary_op(lhs: &PyObject, rhs: &PyObject, op: char, inplace: bool) -> Option<PyResult> {
+        if !Self::check_set(lhs) || !Self::check_set(rhs) {
+            return None;
+        }
+        // frozenset has no in-place operators, a new object is bound instead
+        let inplace = inplace && !lhs.downcast_refcell::<SetObject>().unwrap().is_frozen();
+        if inplace {
+            let res = match op {
+                '|' => set_update(vec![lhs.clone(), rhs.clone()]),
+                '&' => set_intersection_update(vec![lhs.clone(), rhs.clone()]),
+                '-' => set_difference_update(vec![lhs.clone(), rhs.clone()]),
+                '^' => set_symmetric_difference_update(vec![lhs.clone(), rhs.clone()]),
+                _ => unreachable!()
+            };
+            return Some(res.map(|_| lhs.clone()));
+        }
+        Some(match op {
+            '|' => set_union(vec![lhs.clone(), rhs.clone()]),
+            '&' => set_intersection(vec![lhs.clone(), rhs.clone()]),
+            '-' => set_difference(vec![lhs.clone(), rhs.clone()]),
+            '^' => set_symmetric_difference(vec![lhs.clone(), rhs.clone()]),
+            _ => unreachable!()
+        })
+    }
+}
+
+fn this(args: &[PyObject]) -> PyObject {
+    assert!(SetObject::check_set(&args[0]), "invalid set object");
+    args[0].clone()
+}
+
+fn wrap(set: SetObject) -> PyResult {
+    Ok(BasePycObject::new_py_object(set))
+}
+
+/// a set of the items of `obj`, `obj` itself if it is already a set or frozenset
+fn as_set(obj: &PyObject) -> PyResult {
+    if SetObject::check_set(obj) {
+        return Ok(obj.clone());
+    }
+    SetObject::new_from_iterable(Some(obj), false)
+}
+
+// the methods changing a set look its keys up with `find`, so that it is not borrowed while the
+// `__eq__` of a key runs
+
+fn set_contains(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__contains__", 1, 1)?;
+    Ok(Interpreter::new_bool_object(SetObject::contains_key(&this(&args), &args[1])?))
+}
+
+fn set_add(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "add", 1, 1)?;
+    let hash = args[1].borrow().hash()?;
+    SetObject::add_key(&this(&args), args[1].clone(), hash)?;
+    Ok(NoneObject::new())
+}
+
+fn set_discard(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "discard", 1, 1)?;
+    let hash = SetObject::key_hash(&args[1])?;
+    SetObject::discard_key(&this(&args), &args[1], hash)?;
+    Ok(NoneObject::new())
+}
+
+fn set_remove(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "remove", 1, 1)?;
+    let hash = SetObject::key_hash(&args[1])?;
+    if !SetObject::discard_key(&this(&args), &args[1], hash)? {
+        return Err(ExceptionObject::new(ExceptionKind::KeyError, vec![args[1].clone()]));
+    }
+    Ok(NoneObject::new())
+}
+
+fn set_pop(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "pop", 0, 0)?;
+    let set = this(&args);
+    let mut set = set.downcast_refcell_mut::<SetObject>().unwrap();
+    if set.used == 0 {
+        return ExceptionObject::raise(ExceptionKind::KeyError, "pop from an empty set");
+    }
+    // resume the search from the previous pop, refer to CPython(set_pop)
+    let mask = set.mask();
+    let mut slot = set.finger & mask;
+    while !matches!(set.table[slot], Slot::Active(..)) {
+        slot = (slot + 1) & mask;
+    }
+    set.finger = slot + 1;
+    Ok(set.remove_at(slot))
+}
+
+fn set_clear(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "clear", 0, 0)?;
+    this(&args).downcast_refcell_mut::<SetObject>().unwrap().clear();
+    Ok(NoneObject::new())
+}
+
+fn set_copy(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "copy", 0, 0)?;
+    let set = this(&args);
+    if set.downcast_refcell::<SetObject>().unwrap().is_frozen() {
+        return Ok(set);
+    }
+    let res = set.downcast_refcell::<SetObject>().unwrap().copy();
+    wrap(res)
+}
+
+fn set_update(args: Vec<PyObject>) -> PyResult {
+    let set = this(&args);
+    for other in &args[1..] {
+        if Rc::ptr_eq(&set, other) {
+            continue;
+        }
+        // the steps of `update_internal`, with the keys added to the set object
+        if let Some(other) = other.downcast_refcell::<SetObject>() {
+            let mut set = set.downcast_refcell_mut::<SetObject>().unwrap();
+            if set.fill == 0 {
+                set.copy_from(&other);
+                continue;
+            }
+        }
+        let keys = SetObject::keys_of(other)?;
+        set.downcast_refcell_mut::<SetObject>().unwrap().presize(other, keys.len());
+        for (hash, key) in keys {
+            let hash = SetObject::hash_key(hash, &key, false)?;
+            SetObject::add_key(&set, key, hash)?;
+        }
+    }
+    Ok(NoneObject::new())
+}
+
+fn set_union(args: Vec<PyObject>) -> PyResult {
+    let mut res = this(&args).downcast_refcell::<SetObject>().unwrap().copy();
+    for other in &args[1..] {
+        res.update_internal(other)?;
+    }
+    wrap(res)
+}
+
+/// refer to CPython(set_intersection_multi)
+fn intersection_multi(args: &[PyObject]) -> PyResult {
+    let mut res = this(args);
+    if args.len() == 1 {
+        let copy = res.downcast_refcell::<SetObject>().unwrap().copy();
+        return wrap(copy);
+    }
+    for other in &args[1..] {
+        res = wrap(SetObject::intersection(&res, other)?)?;
+    }
+    Ok(res)
+}
+
+fn set_intersection(args: Vec<PyObject>) -> PyResult {
+    intersection_multi(&args)
+}
+
+fn set_intersection_update(args: Vec<PyObject>) -> PyResult {
+    let res = intersection_multi(&args)?;
+    let res = res.downcast_refcell::<SetObject>().unwrap();
+    let set = this(&args);
+    let mut set = set.downcast_refcell_mut::<SetObject>().unwrap();
+    set.table = res.table.clone();
+    set.fill = res.fill;
+    set.used = res.used;
+    Ok(NoneObject::new())
+}
+
+fn set_difference(args: Vec<PyObject>) -> PyResult {
+    let set = this(&args);
+    let mut res = match args.get(1) {
+        Some(other) => SetObject::difference(&set, other)?,
+        None => set.downcast_refcell::<SetObject>().unwrap().copy()
+    };
+    for other in args.iter().skip(2) {
+        res.difference_update(other)?;
+    }
+    wrap(res)
+}
+
+fn set_difference_update(args: Vec<PyObject>) -> PyResult {
+    let set = this(&args);
+    for other in &args[1..] {
+        if Rc::ptr_eq(&args[0], other) {
+            set.downcast_refcell_mut::<SetObject>().unwrap().clear();
+            continue;
+        }
+        for (hash, key) in SetObject::keys_of(other)? {
+            let hash = SetObject::hash_key(hash, &key, true)?;
+            SetObject::discard_key(&set, &key, hash)?;
+        }
+        set.downcast_refcell_mut::<SetObject>().unwrap().compact();
+    }
+    Ok(NoneObject::new())
+}
+
+fn set_symmetric_difference(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "symmetric_difference", 1, 1)?;
+    // refer to CPython(set_symmetric_difference)
+    let object_type = this(&args).borrow().object_type();
+    let mut res = SetObject::new_raw(object_type);
+    res.update_internal(&args[1])?;
+    res.symmetric_difference_update(&args[0])?;
+    wrap(res)
+}
+
+fn set_symmetric_difference_update(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "symmetric_difference_update", 1, 1)?;
+    let set = this(&args);
+    if Rc::ptr_eq(&args[0], &args[1]) {
+        set.downcast_refcell_mut::<SetObject>().unwrap().clear();
+        return Ok(NoneObject::new());
+    }
+    // refer to CPython(set_symmetric_difference_update)
+    let entries = as_set(&args[1])?.downcast_refcell::<SetObject>().unwrap().entries();
+    for (hash, key) in entries {
+        if !SetObject::discard_key(&set, &key, hash)? {
+            SetObject::add_key(&set, key, hash)?;
+        }
+    }
+    Ok(NoneObject::new())
+}
+
+fn set_issubset(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "issubset", 1, 1)?;
+    let other = as_set(&args[1])?;
+    Ok(Interpreter::new_bool_object(SetObject::is_subset(&this(&args), &other)?))
+}
+
+fn set_issuperset(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "issuperset", 1, 1)?;
+    let other = as_set(&args[1])?;
+    Ok(Interpreter::new_bool_object(SetObject::is_subset(&other, &this(&args))?))
+}
+
+fn set_isdisjoint(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "isdisjoint", 1, 1)?;
+    let common = SetObject::intersection(&this(&args), &args[1])?;
+    Ok(Interpreter::new_bool_object(common.used == 0))
+}
+
+impl PartialEq<Self> for SetObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
     }
 }
 
@@ -48,22 +794,101 @@ impl PyObjectTrait for SetObject {
     fn base_object(&self) -> &BasePycObject {
         &self.base
     }
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(self.length()? > 0)
+    }
+    fn hash(&self) -> PyResult<i64> {
+        if self.is_frozen() {
+            return Ok(self.frozen_hash());
+        }
+        unhashable(&self.type_name())
+    }
+
+    /// subset and superset comparisons, refer to CPython(set_richcompare)
+    fn compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        let (other_used, other_entries) = match other.downcast_refcell::<SetObject>() {
+            Some(other) => (other.used, other.entries()),
+            None => return Ok(None)
+        };
+        // `other` is not borrowed while the keys of this set are looked up in it, and the keys of
+        // `other` are looked up in a copy of this set, which stays borrowed
+        let is_subset = || Ok::<bool, PyObject>(self.used <= other_used && Self::all_in(self.entries(), other)?);
+        let is_superset = || {
+            if other_used > self.used {
+                return Ok::<bool, PyObject>(false);
+            }
+            let copy = self.copy();
+            for (hash, key) in &other_entries {
+                if copy.lookup(key, *hash)?.is_none() {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        };
+        let res = match op {
+            CmpOP::EQ => self.used == other_used && is_subset()?,
+            CmpOP::NEQ => !(self.used == other_used && is_subset()?),
+            CmpOP::LE => is_subset()?,
+            CmpOP::GE => is_superset()?,
+            CmpOP::LT => self.used < other_used && is_subset()?,
+            CmpOP::GT => self.used > other_used && is_superset()?,
+        };
+        Ok(Some(res))
+    }
+
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "copy" => set_copy,
+            "union" => set_union,
+            "intersection" => set_intersection,
+            "difference" => set_difference,
+            "symmetric_difference" => set_symmetric_difference,
+            "issubset" => set_issubset,
+            "issuperset" => set_issuperset,
+            "isdisjoint" => set_isdisjoint,
+            "__contains__" => set_contains,
+            _ if self.is_frozen() => return None,
+            "add" => set_add,
+            "discard" => set_discard,
+            "remove" => set_remove,
+            "pop" => set_pop,
+            "clear" => set_clear,
+            "update" => set_update,
+            "intersection_update" => set_intersection_update,
+            "difference_update" => set_difference_update,
+            "symmetric_difference_update" => set_symmetric_difference_update,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+
+    fn length(&self) -> PyResult<usize> {
+        Ok(self.used)
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        let name = self.type_name();
+        if self.used == 0 {
+            return Ok(format!("{}()", name));
+        }
+        repr_guard(object_address(self), &format!("{}(...)", name), || {
+            let items = join_repr(&self.values())?;
+            if self.is_frozen() {
+                Ok(format!("{}({{{}}})", name, items))
+            } else {
+                Ok(format!("{{{}}}", items))
+            }
+        })
+    }
 }
 
 impl fmt::Display for SetObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "{{").unwrap();
-        for (i, entry) in self.values.iter().enumerate() {
-            if i != 0 {
-                write!(f, ", ").unwrap();
-            }
-            write!(f, "{}", entry.borrow()).unwrap();
-        }
-        write!(f, "}}")
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
     }
 }
 impl fmt::Debug for SetObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        writeln!(f, "SetObject({:?})", self.values)
+        writeln!(f, "SetObject({:?})", self.values())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,102 @@
+use crate::object::{join_repr, BasePycObject, ExceptionKind, ExceptionObject, IntObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::utils::{PyObject, PyResult};
+
+pub struct SliceObject {
+    base: BasePycObject,
+    start: PyObject,
+    stop: PyObject,
+    step: PyObject
+}
+
+impl SliceObject {
+    pub fn new(start: PyObject, stop: PyObject, step: PyObject) -> PyObject {
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::SLICE),
+            start,
+            stop,
+            step
+        })
+    }
+
+    fn component(obj: &PyObject) -> PyResult<Option<i64>> {
+        if obj.borrow().object_type() == ObjectType::NONE {
+            return Ok(None)
+        }
+        match IntObject::extract(obj) {
+            Some(value) => Ok(Some(value as i64)),
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                "slice indices must be integers or None or have an __index__ method"
+            )
+        }
+    }
+
+    /// resolve the slice against a sequence of `length` items, refer to CPython(PySlice_GetIndicesEx)
+    /// returns (start, stop, step, slice_length)
+    pub fn indices(&self, length: usize) -> PyResult<(i64, i64, i64, usize)> {
+        let length = length as i64;
+        let step = Self::component(&self.step)?.unwrap_or(1);
+        if step == 0 {
+            return ExceptionObject::raise(ExceptionKind::ValueError, "slice step cannot be zero");
+        }
+        let (lower, upper) = if step < 0 { (-1, length - 1) } else { (0, length) };
+        let adjust = |value: Option<i64>, default: i64| -> i64 {
+            match value {
+                None => default,
+                Some(v) if v < 0 => (v + length).max(lower),
+                Some(v) => v.min(upper),
+            }
+        };
+        let start = adjust(Self::component(&self.start)?, if step < 0 { upper } else { lower });
+        let stop = adjust(Self::component(&self.stop)?, if step < 0 { lower } else { upper });
+
+        let slice_length = if step < 0 {
+            if stop < start { (start - stop - 1) / (-step) + 1 } else { 0 }
+        } else if start < stop {
+            (stop - start - 1) / step + 1
+        } else {
+            0
+        };
+        Ok((start, stop, step, slice_length as usize))
+    }
+
+    /// the positions selected by the slice in a sequence of `length` items
+    pub fn positions(&self, length: usize) -> PyResult<Vec<usize>> {
+        let (start, _, step, slice_length) = self.indices(length)?;
+        Ok((0..slice_length as i64).map(|i| (start + i * step) as usize).collect())
+    }
+}
+
+impl PartialEq<Self> for SliceObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.start.eq(&other.start) && self.stop.eq(&other.stop) && self.step.eq(&other.step)
+    }
+}
+
+impl Eq for SliceObject {}
+
+impl PyObjectTrait for SliceObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+    fn repr(&self) -> PyResult<String> {
+        Ok(format!("slice({})", join_repr(&[self.start.clone(), self.stop.clone(), self.step.clone()])?))
+    }
+}
+
+impl fmt::Display for SliceObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for SliceObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "SliceObject({:?}, {:?}, {:?})", self.start, self.stop, self.step)
+    }
+}
@@ -0,0 +1,70 @@
+use crate::object::{BasePycObject, ExceptionKind, ExceptionObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::utils::{PyObject, PyResult};
+
+/// a function stored in a class which is called without the instance or the class, refer to CPython(PyStaticMethod_Type)
+pub struct StaticMethodObject {
+    base: BasePycObject,
+    func: PyObject
+}
+
+impl StaticMethodObject {
+    pub fn new_from_args(args: Vec<PyObject>) -> PyResult {
+        if args.len() != 1 {
+            return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("staticmethod expected 1 argument, got {}", args.len())
+            );
+        }
+        Ok(BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::STATIC_METHOD),
+            func: args[0].clone()
+        }))
+    }
+
+    pub fn func(&self) -> PyObject {
+        self.func.clone()
+    }
+}
+
+impl PartialEq<Self> for StaticMethodObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for StaticMethodObject {}
+
+impl PyObjectTrait for StaticMethodObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+    fn get_attr(&self, name: String) -> PyResult {
+        match name.as_str() {
+            "__func__" | "__wrapped__" => Ok(self.func.clone()),
+            _ => ExceptionObject::raise(
+                ExceptionKind::AttributeError,
+                &format!("'staticmethod' object has no attribute '{}'", name)
+            )
+        }
+    }
+    fn repr(&self) -> PyResult<String> {
+        Ok(format!("<staticmethod({})>", self.func.borrow().repr()?))
+    }
+}
+
+impl fmt::Display for StaticMethodObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for StaticMethodObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "StaticMethodObject({:?})", self.func)
+    }
+}
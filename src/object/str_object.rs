@@ -1,14 +1,17 @@
-use crate::object::{BasePycObject, IterObject};
+use crate::object::{check_args, int_arg, normalize_index, opt_arg, range_args, BasePycObject, BytesObject, CallableObject, ExceptionKind, ExceptionObject, IntObject, IterObject, ListObject, SliceObject, TupleObject};
 use crate::object::PyObjectTrait;
 use crate::object::ObjectType;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use crate::InputStream;
-use crate::utils::PyObject;
+use crate::interpreter::Interpreter;
+use crate::utils::{hash, unicode, CmpOP, DowncastTrait, PyObject, PyResult};
 
 pub struct StringObject {
     base: BasePycObject,
-    data: Vec<u8>
+    value: String,
+    // number of code points, strings are indexed by code point rather than by byte
+    length: usize
 }
 
 impl StringObject {
@@ -27,10 +30,19 @@ impl StringObject {
     }
 
     pub fn new_from_str(s: &str) -> PyObject {
-        BasePycObject::new_py_object(Self {
-            base: BasePycObject::new_from_char('s'),
-            data: s.to_string().as_bytes().to_vec(),
-        })
+        BasePycObject::new_py_object(Self::new_raw(s.to_string()))
+    }
+
+    pub fn new_from_string(s: String) -> PyObject {
+        BasePycObject::new_py_object(Self::new_raw(s))
+    }
+
+    fn new_raw(value: String) -> Self {
+        Self {
+            base: BasePycObject::new_from_char('u'),
+            length: value.chars().count(),
+            value,
+        }
     }
 
     fn _new(stream: &mut InputStream, length: u32) -> Self {
@@ -38,33 +50,628 @@ impl StringObject {
         for _ in 0..length {
             data.push(stream.read().unwrap());
         }
-        Self {
-            base: BasePycObject::new_from_char('s'),
-            data,
-        }
+        Self::new_raw(String::from_utf8_lossy(&data).into_owned())
     }
 
     pub fn string(&self) -> String {
-        // String::from_utf8(self.data.clone()).expect("invalid utf8 string")
-        String::from_utf8(self.data.clone()).unwrap_or("".to_string())
+        self.value.clone()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// the value of `obj`, or a TypeError if it is not a str
+    pub fn extract(obj: &PyObject) -> PyResult<String> {
+        match obj.downcast_refcell::<StringObject>() {
+            Some(s) => Ok(s.string()),
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("expected str instance, {} found", obj.borrow().type_name())
+            )
+        }
+    }
+
+    fn is_ascii(&self) -> bool {
+        self.length == self.value.len()
+    }
+
+    /// the byte offset of the code point at `index`, `index` may be equal to the length
+    fn byte_offset(&self, index: usize) -> usize {
+        if self.is_ascii() {
+            return index;
+        }
+        self.value.char_indices().nth(index).map(|(i, _)| i).unwrap_or(self.value.len())
+    }
+
+    /// the substring between two code point positions
+    fn substr(&self, start: usize, end: usize) -> &str {
+        if start >= end {
+            return "";
+        }
+        &self.value[self.byte_offset(start)..self.byte_offset(end)]
+    }
+
+    /// `lhs + rhs`, refer to CPython(PyUnicode_Concat)
+    pub fn concat(&self, other: &PyObject) -> PyResult {
+        match other.downcast_refcell::<StringObject>() {
+            Some(other) => Ok(Self::new_from_string(self.value.clone() + &other.value)),
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("can only concatenate str (not \"{}\") to str", other.borrow().type_name())
+            )
+        }
     }
 
-    pub fn data(&self) -> &Vec<u8> {
-        &self.data
+    pub fn repeat(&self, times: i32) -> PyObject {
+        Self::new_from_string(self.value.repeat(times.max(0) as usize))
     }
 
+    /// the quoted and escaped form of `s`, refer to CPython(unicode_repr)
+    pub fn repr_str(s: &str) -> String {
+        let quote = if s.contains('\'') && !s.contains('"') { '"' } else { '\'' };
+        let mut res = String::with_capacity(s.len() + 2);
+        res.push(quote);
+        for c in s.chars() {
+            match c {
+                '\\' => res.push_str("\\\\"),
+                '\t' => res.push_str("\\t"),
+                '\n' => res.push_str("\\n"),
+                '\r' => res.push_str("\\r"),
+                c if c == quote => {
+                    res.push('\\');
+                    res.push(c);
+                },
+                c if (c as u32) < 0x20 || c as u32 == 0x7f => res.push_str(&format!("\\x{:02x}", c as u32)),
+                c if unicode::is_printable(c) => res.push(c),
+                c if (c as u32) <= 0xff => res.push_str(&format!("\\x{:02x}", c as u32)),
+                c if (c as u32) <= 0xffff => res.push_str(&format!("\\u{:04x}", c as u32)),
+                c => res.push_str(&format!("\\U{:08x}", c as u32)),
+            }
+        }
+        res.push(quote);
+        res
+    }
+}
+
+fn this(args: &[PyObject]) -> String {
+    args[0].downcast_refcell::<StringObject>().expect("invalid str object").string()
+}
+
+fn str_arg(args: &[PyObject], index: usize) -> PyResult<Option<String>> {
+    match opt_arg(args, index) {
+        None => Ok(None),
+        Some(arg) => match arg.downcast_refcell::<StringObject>() {
+            Some(s) => Ok(Some(s.string())),
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("must be str or None, not {}", arg.borrow().type_name())
+            )
+        }
+    }
+}
+
+fn str_list(values: Vec<&str>) -> PyObject {
+    ListObject::new_from_values(values.into_iter().map(StringObject::new_from_str).collect())
+}
+
+fn bool_result(value: bool) -> PyResult {
+    Ok(Interpreter::new_bool_object(value))
+}
+
+fn is_line_break(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\x0b' | '\x0c' | '\x1c' | '\x1d' | '\x1e' | '\u{85}' | '\u{2028}' | '\u{2029}')
+}
+
+fn split_whitespace(s: &str, maxsplit: i64) -> Vec<&str> {
+    let mut res = vec![];
+    let mut rest = s.trim_start();
+    while !rest.is_empty() {
+        if maxsplit >= 0 && res.len() as i64 == maxsplit {
+            res.push(rest);
+            break;
+        }
+        match rest.find(char::is_whitespace) {
+            Some(pos) => {
+                res.push(&rest[..pos]);
+                rest = rest[pos..].trim_start();
+            },
+            None => {
+                res.push(rest);
+                break;
+            }
+        }
+    }
+    res
+}
+
+fn rsplit_whitespace(s: &str, maxsplit: i64) -> Vec<&str> {
+    let mut res = vec![];
+    let mut rest = s.trim_end();
+    while !rest.is_empty() {
+        if maxsplit >= 0 && res.len() as i64 == maxsplit {
+            res.push(rest);
+            break;
+        }
+        match rest.rfind(char::is_whitespace) {
+            Some(pos) => {
+                let c = rest[pos..].chars().next().unwrap();
+                res.push(&rest[pos + c.len_utf8()..]);
+                rest = rest[..pos].trim_end();
+            },
+            None => {
+                res.push(rest);
+                break;
+            }
+        }
+    }
+    res.reverse();
+    res
+}
+
+fn str_split(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "split", 0, 2)?;
+    let s = this(&args);
+    let maxsplit = int_arg(&args, 2, -1)?;
+    match str_arg(&args, 1)? {
+        None => Ok(str_list(split_whitespace(&s, maxsplit))),
+        Some(sep) if sep.is_empty() => ExceptionObject::raise(ExceptionKind::ValueError, "empty separator"),
+        Some(sep) if maxsplit < 0 => Ok(str_list(s.split(sep.as_str()).collect())),
+        Some(sep) => Ok(str_list(s.splitn(maxsplit as usize + 1, sep.as_str()).collect()))
+    }
+}
+
+fn str_rsplit(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "rsplit", 0, 2)?;
+    let s = this(&args);
+    let maxsplit = int_arg(&args, 2, -1)?;
+    let mut values: Vec<&str> = match str_arg(&args, 1)? {
+        None => return Ok(str_list(rsplit_whitespace(&s, maxsplit))),
+        Some(sep) if sep.is_empty() => return ExceptionObject::raise(ExceptionKind::ValueError, "empty separator"),
+        Some(sep) if maxsplit < 0 => s.rsplit(sep.as_str()).collect(),
+        Some(sep) => s.rsplitn(maxsplit as usize + 1, sep.as_str()).collect()
+    };
+    values.reverse();
+    Ok(str_list(values))
+}
+
+fn str_join(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "join", 1, 1)?;
+    let s = this(&args);
+    let items = IterObject::collect(&args[1])?;
+    let mut parts = vec![];
+    for (i, item) in items.iter().enumerate() {
+        match item.downcast_refcell::<StringObject>() {
+            Some(item) => parts.push(item.string()),
+            None => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("sequence item {}: expected str instance, {} found", i, item.borrow().type_name())
+            )
+        }
+    }
+    Ok(StringObject::new_from_string(parts.join(&s)))
+}
+
+fn strip_impl(args: Vec<PyObject>, name: &str, left: bool, right: bool) -> PyResult {
+    check_args(&args, name, 0, 1)?;
+    let s = this(&args);
+    let chars = str_arg(&args, 1)?;
+    let pred = |c: char| match &chars {
+        Some(chars) => chars.contains(c),
+        None => c.is_whitespace()
+    };
+    let mut res = s.as_str();
+    if left {
+        res = res.trim_start_matches(pred);
+    }
+    if right {
+        res = res.trim_end_matches(pred);
+    }
+    Ok(StringObject::new_from_str(res))
+}
+
+fn str_strip(args: Vec<PyObject>) -> PyResult {
+    strip_impl(args, "strip", true, true)
+}
+
+fn str_lstrip(args: Vec<PyObject>) -> PyResult {
+    strip_impl(args, "lstrip", true, false)
+}
+
+fn str_rstrip(args: Vec<PyObject>) -> PyResult {
+    strip_impl(args, "rstrip", false, true)
+}
+
+fn str_replace(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "replace", 2, 3)?;
+    let s = this(&args);
+    let old = StringObject::extract(&args[1])?;
+    let new = StringObject::extract(&args[2])?;
+    let count = int_arg(&args, 3, -1)?;
+    if count < 0 {
+        Ok(StringObject::new_from_string(s.replace(&old, &new)))
+    } else {
+        Ok(StringObject::new_from_string(s.replacen(&old, &new, count as usize)))
+    }
+}
+
+/// the code point position of `sub` inside `args[0][start:end]`, searching from the right if `reverse`
+fn find_impl(args: &[PyObject], name: &str, reverse: bool) -> PyResult<i64> {
+    check_args(args, name, 1, 3)?;
+    let sub = StringObject::extract(&args[1])?;
+    let this = args[0].downcast_refcell::<StringObject>().expect("invalid str object");
+    let (start, end) = range_args(args, 2, this.length)?;
+    if start > this.length as i64 || start > end {
+        return Ok(-1);
+    }
+    let haystack = this.substr(start as usize, end as usize);
+    let pos = if reverse { haystack.rfind(&sub) } else { haystack.find(&sub) };
+    Ok(match pos {
+        Some(pos) => start + haystack[..pos].chars().count() as i64,
+        None => -1
+    })
+}
+
+fn str_find(args: Vec<PyObject>) -> PyResult {
+    Ok(IntObject::new_from_i32(find_impl(&args, "find", false)? as i32))
+}
+
+fn str_rfind(args: Vec<PyObject>) -> PyResult {
+    Ok(IntObject::new_from_i32(find_impl(&args, "rfind", true)? as i32))
+}
+
+fn str_index(args: Vec<PyObject>) -> PyResult {
+    match find_impl(&args, "index", false)? {
+        -1 => ExceptionObject::raise(ExceptionKind::ValueError, "substring not found"),
+        pos => Ok(IntObject::new_from_i32(pos as i32))
+    }
+}
+
+fn str_rindex(args: Vec<PyObject>) -> PyResult {
+    match find_impl(&args, "rindex", true)? {
+        -1 => ExceptionObject::raise(ExceptionKind::ValueError, "substring not found"),
+        pos => Ok(IntObject::new_from_i32(pos as i32))
+    }
+}
+
+fn str_count(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "count", 1, 3)?;
+    let sub = StringObject::extract(&args[1])?;
+    let this = args[0].downcast_refcell::<StringObject>().expect("invalid str object");
+    let (start, end) = range_args(&args, 2, this.length)?;
+    if start > end || start > this.length as i64 {
+        return Ok(IntObject::new_from_i32(0));
+    }
+    let haystack = this.substr(start as usize, end as usize);
+    Ok(IntObject::new_from_i32(haystack.matches(sub.as_str()).count() as i32))
+}
+
+fn affix_impl(args: Vec<PyObject>, name: &str, suffix: bool) -> PyResult {
+    check_args(&args, name, 1, 3)?;
+    let this = args[0].downcast_refcell::<StringObject>().expect("invalid str object");
+    let (start, end) = range_args(&args, 2, this.length)?;
+    let candidates = match args[1].downcast_refcell::<TupleObject>() {
+        Some(tuple) => tuple.values().clone(),
+        None => vec![args[1].clone()]
+    };
+    if start > this.length as i64 {
+        return bool_result(false);
+    }
+    let haystack = this.substr(start as usize, end.max(start) as usize);
+    for candidate in candidates {
+        let candidate = match candidate.downcast_refcell::<StringObject>() {
+            Some(candidate) => candidate.string(),
+            None => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("{} first arg must be str or a tuple of str, not {}", name, candidate.borrow().type_name())
+            )
+        };
+        let matched = if suffix { haystack.ends_with(&candidate) } else { haystack.starts_with(&candidate) };
+        if matched {
+            return bool_result(true);
+        }
+    }
+    bool_result(false)
+}
+
+fn str_startswith(args: Vec<PyObject>) -> PyResult {
+    affix_impl(args, "startswith", false)
+}
+
+fn str_endswith(args: Vec<PyObject>) -> PyResult {
+    affix_impl(args, "endswith", true)
+}
+
+fn str_upper(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "upper", 0, 0)?;
+    Ok(StringObject::new_from_string(this(&args).to_uppercase()))
+}
+
+fn str_lower(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "lower", 0, 0)?;
+    Ok(StringObject::new_from_string(this(&args).to_lowercase()))
+}
+
+fn str_casefold(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "casefold", 0, 0)?;
+    // full case folding differs from lowercasing only for a handful of code points
+    let folded = this(&args).to_lowercase().replace('ß', "ss").replace('ς', "σ");
+    Ok(StringObject::new_from_string(folded))
+}
+
+fn is_cased(c: char) -> bool {
+    c.is_lowercase() || c.is_uppercase()
+}
+
+fn str_title(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "title", 0, 0)?;
+    let mut res = String::new();
+    let mut previous_cased = false;
+    for c in this(&args).chars() {
+        if previous_cased {
+            res.extend(c.to_lowercase());
+        } else {
+            res.extend(c.to_uppercase());
+        }
+        previous_cased = is_cased(c);
+    }
+    Ok(StringObject::new_from_string(res))
+}
+
+fn str_capitalize(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "capitalize", 0, 0)?;
+    let s = this(&args);
+    let mut chars = s.chars();
+    let res = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+        None => String::new()
+    };
+    Ok(StringObject::new_from_string(res))
+}
+
+fn str_swapcase(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "swapcase", 0, 0)?;
+    let mut res = String::new();
+    for c in this(&args).chars() {
+        if c.is_uppercase() {
+            res.extend(c.to_lowercase());
+        } else if c.is_lowercase() {
+            res.extend(c.to_uppercase());
+        } else {
+            res.push(c);
+        }
+    }
+    Ok(StringObject::new_from_string(res))
+}
+
+/// true if `s` is not empty and every code point satisfies `pred`
+fn all_chars(args: &[PyObject], name: &str, pred: fn(char) -> bool) -> PyResult {
+    check_args(args, name, 0, 0)?;
+    let s = this(args);
+    bool_result(!s.is_empty() && s.chars().all(pred))
+}
+
+fn is_digit(c: char) -> bool {
+    c.is_ascii_digit() || matches!(c, '¹' | '²' | '³' | '⁰' | '⁴'..='⁹' | '₀'..='₉')
+}
+
+fn str_isdigit(args: Vec<PyObject>) -> PyResult {
+    all_chars(&args, "isdigit", is_digit)
+}
+
+fn str_isdecimal(args: Vec<PyObject>) -> PyResult {
+    all_chars(&args, "isdecimal", |c| c.is_ascii_digit())
+}
+
+fn str_isnumeric(args: Vec<PyObject>) -> PyResult {
+    all_chars(&args, "isnumeric", char::is_numeric)
+}
+
+fn str_isalpha(args: Vec<PyObject>) -> PyResult {
+    all_chars(&args, "isalpha", char::is_alphabetic)
+}
+
+fn str_isalnum(args: Vec<PyObject>) -> PyResult {
+    all_chars(&args, "isalnum", char::is_alphanumeric)
+}
+
+fn str_isspace(args: Vec<PyObject>) -> PyResult {
+    all_chars(&args, "isspace", char::is_whitespace)
+}
+
+fn str_isascii(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "isascii", 0, 0)?;
+    bool_result(this(&args).is_ascii())
+}
+
+fn str_isupper(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "isupper", 0, 0)?;
+    let s = this(&args);
+    bool_result(s.chars().any(is_cased) && !s.chars().any(char::is_lowercase))
+}
+
+fn str_islower(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "islower", 0, 0)?;
+    let s = this(&args);
+    bool_result(s.chars().any(is_cased) && !s.chars().any(char::is_uppercase))
+}
+
+fn str_istitle(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "istitle", 0, 0)?;
+    let mut previous_cased = false;
+    let mut found_cased = false;
+    for c in this(&args).chars() {
+        if c.is_uppercase() {
+            if previous_cased {
+                return bool_result(false);
+            }
+            previous_cased = true;
+            found_cased = true;
+        } else if c.is_lowercase() {
+            if !previous_cased {
+                return bool_result(false);
+            }
+            previous_cased = true;
+            found_cased = true;
+        } else {
+            previous_cased = false;
+        }
+    }
+    bool_result(found_cased)
+}
+
+fn partition_impl(args: Vec<PyObject>, name: &str, reverse: bool) -> PyResult {
+    check_args(&args, name, 1, 1)?;
+    let s = this(&args);
+    let sep = StringObject::extract(&args[1])?;
+    if sep.is_empty() {
+        return ExceptionObject::raise(ExceptionKind::ValueError, "empty separator");
+    }
+    let pos = if reverse { s.rfind(&sep) } else { s.find(&sep) };
+    let parts = match pos {
+        Some(pos) => [&s[..pos], sep.as_str(), &s[pos + sep.len()..]],
+        None if reverse => ["", "", s.as_str()],
+        None => [s.as_str(), "", ""]
+    };
+    Ok(TupleObject::new_from_values(parts.into_iter().map(StringObject::new_from_str).collect()))
+}
+
+fn str_partition(args: Vec<PyObject>) -> PyResult {
+    partition_impl(args, "partition", false)
+}
+
+fn str_rpartition(args: Vec<PyObject>) -> PyResult {
+    partition_impl(args, "rpartition", true)
+}
+
+fn str_splitlines(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "splitlines", 0, 1)?;
+    let s = this(&args);
+    let keepends = match opt_arg(&args, 1) {
+        Some(arg) => arg.borrow().object_type() == ObjectType::TRUE || IntObject::extract(&arg).unwrap_or(0) != 0,
+        None => false
+    };
+    let mut lines = vec![];
+    let mut start = 0;
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if !is_line_break(c) {
+            continue;
+        }
+        let mut end = i + c.len_utf8();
+        if c == '\r' {
+            if let Some((_, '\n')) = chars.peek() {
+                chars.next();
+                end += 1;
+            }
+        }
+        lines.push(if keepends { &s[start..end] } else { &s[start..i] });
+        start = end;
+    }
+    if start < s.len() {
+        lines.push(&s[start..]);
+    }
+    Ok(str_list(lines))
+}
+
+fn str_zfill(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "zfill", 1, 1)?;
+    let s = this(&args);
+    let width = int_arg(&args, 1, 0)?;
+    let length = s.chars().count() as i64;
+    if width <= length {
+        return Ok(StringObject::new_from_string(s));
+    }
+    let zeros = "0".repeat((width - length) as usize);
+    let res = match s.chars().next() {
+        Some(sign @ ('+' | '-')) => format!("{}{}{}", sign, zeros, &s[1..]),
+        _ => zeros + &s
+    };
+    Ok(StringObject::new_from_string(res))
+}
+
+fn fill_char(args: &[PyObject]) -> PyResult<char> {
+    match opt_arg(args, 2) {
+        None => Ok(' '),
+        Some(arg) => {
+            let fill = StringObject::extract(&arg)?;
+            let mut chars = fill.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(c),
+                _ => ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    "The fill character must be exactly one character long"
+                )
+            }
+        }
+    }
+}
+
+/// pad `args[0]` to the requested width, `left` is the number of fill characters put before the string
+fn justify_impl(args: Vec<PyObject>, name: &str, left: fn(usize, usize) -> usize) -> PyResult {
+    check_args(&args, name, 1, 2)?;
+    let s = this(&args);
+    let width = int_arg(&args, 1, 0)?;
+    let fill = fill_char(&args)?;
+    let length = s.chars().count();
+    if width <= length as i64 {
+        return Ok(StringObject::new_from_string(s));
+    }
+    let pad = width as usize - length;
+    let left = left(pad, width as usize);
+    let mut res: String = std::iter::repeat_n(fill, left).collect();
+    res.push_str(&s);
+    res.extend(std::iter::repeat_n(fill, pad - left));
+    Ok(StringObject::new_from_string(res))
+}
+
+fn str_center(args: Vec<PyObject>) -> PyResult {
+    // refer to CPython(pad in unicodeobject.c) for the odd width rounding
+    justify_impl(args, "center", |pad, width| pad / 2 + (pad & width & 1))
+}
+
+fn str_ljust(args: Vec<PyObject>) -> PyResult {
+    justify_impl(args, "ljust", |_, _| 0)
+}
+
+fn str_rjust(args: Vec<PyObject>) -> PyResult {
+    justify_impl(args, "rjust", |pad, _| pad)
+}
+
+fn str_encode(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "encode", 0, 2)?;
+    let s = this(&args);
+    let encoding = str_arg(&args, 1)?.unwrap_or("utf-8".to_string()).to_lowercase();
+    match encoding.as_str() {
+        "utf-8" | "utf8" => Ok(BytesObject::new_from_vec(s.into_bytes())),
+        "ascii" | "latin-1" | "latin1" => {
+            let limit = if encoding == "ascii" { 0x80 } else { 0x100 };
+            let mut data = vec![];
+            for (i, c) in s.chars().enumerate() {
+                if c as u32 >= limit {
+                    return ExceptionObject::raise(
+                        ExceptionKind::ValueError,
+                        &format!("'{}' codec can't encode character '\\u{:04x}' in position {}", encoding, c as u32, i)
+                    );
+                }
+                data.push(c as u8);
+            }
+            Ok(BytesObject::new_from_vec(data))
+        },
+        _ => ExceptionObject::raise(ExceptionKind::ValueError, &format!("unknown encoding: {}", encoding))
+    }
 }
 
 impl PartialEq<Self> for StringObject {
     fn eq(&self, other: &Self) -> bool {
-        self.data == other.data
+        self.value == other.value
     }
 }
 
 impl Eq for StringObject {}
 impl Hash for StringObject {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.data.hash(state)
+        self.value.hash(state)
     }
 }
 impl PyObjectTrait for StringObject {
@@ -74,30 +681,110 @@ impl PyObjectTrait for StringObject {
     fn base_object(&self) -> &BasePycObject {
         &self.base
     }
-    fn hash_key(&self) -> String {
-        let mut res = "$String_".to_string();
-        res.push_str(&self.string());
-        res
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(self.length()? > 0)
     }
-    fn to_iter(&self) -> PyObject {
-        let s = self.string();
-        let mut values = vec![];
-        for c in s.chars() {
-            values.push(StringObject::new_from_str(&c.to_string()));
+    fn hash(&self) -> PyResult<i64> {
+        Ok(hash::hash_str(&self.value))
+    }
+    fn compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        // utf-8 byte order agrees with code point order
+        Ok(other.downcast_refcell::<StringObject>().map(|other| op.matches(self.value.cmp(&other.value))))
+    }
+    fn contains(&self, item: &PyObject) -> PyResult<bool> {
+        match item.downcast_refcell::<StringObject>() {
+            Some(item) => Ok(self.value.contains(&item.value)),
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("'in <string>' requires string as left operand, not {}", item.borrow().type_name())
+            )
         }
-        IterObject::new(values)
+    }
+    fn repr(&self) -> PyResult<String> {
+        Ok(Self::repr_str(&self.value))
+    }
+    fn str(&self) -> PyResult<String> {
+        Ok(self.string())
+    }
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "split" => str_split,
+            "rsplit" => str_rsplit,
+            "join" => str_join,
+            "strip" => str_strip,
+            "lstrip" => str_lstrip,
+            "rstrip" => str_rstrip,
+            "replace" => str_replace,
+            "find" => str_find,
+            "rfind" => str_rfind,
+            "index" => str_index,
+            "rindex" => str_rindex,
+            "count" => str_count,
+            "startswith" => str_startswith,
+            "endswith" => str_endswith,
+            "upper" => str_upper,
+            "lower" => str_lower,
+            "title" => str_title,
+            "capitalize" => str_capitalize,
+            "swapcase" => str_swapcase,
+            "casefold" => str_casefold,
+            "isdigit" => str_isdigit,
+            "isdecimal" => str_isdecimal,
+            "isnumeric" => str_isnumeric,
+            "isalpha" => str_isalpha,
+            "isalnum" => str_isalnum,
+            "isspace" => str_isspace,
+            "isascii" => str_isascii,
+            "isupper" => str_isupper,
+            "islower" => str_islower,
+            "istitle" => str_istitle,
+            "partition" => str_partition,
+            "rpartition" => str_rpartition,
+            "splitlines" => str_splitlines,
+            "zfill" => str_zfill,
+            "center" => str_center,
+            "ljust" => str_ljust,
+            "rjust" => str_rjust,
+            "encode" => str_encode,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
+    fn get_item(&self, key: &PyObject) -> PyResult {
+        if let Some(slice) = key.downcast_refcell::<SliceObject>() {
+            let (start, stop, step, _) = slice.indices(self.length)?;
+            if step == 1 {
+                return Ok(StringObject::new_from_str(self.substr(start as usize, stop.max(start) as usize)));
+            }
+            let chars: Vec<char> = self.value.chars().collect();
+            let res: String = slice.positions(self.length)?.into_iter().map(|i| chars[i]).collect();
+            return Ok(StringObject::new_from_string(res));
+        }
+        let index = match IntObject::extract(key) {
+            Some(index) => index as i64,
+            None => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("string indices must be integers, not '{}'", key.borrow().type_name())
+            )
+        };
+        match normalize_index(index, self.length) {
+            Some(index) => Ok(StringObject::new_from_str(self.substr(index, index + 1))),
+            None => ExceptionObject::raise(ExceptionKind::IndexError, "string index out of range")
+        }
+    }
+    fn length(&self) -> PyResult<usize> {
+        Ok(self.length)
     }
 }
 
 impl fmt::Display for StringObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "{}", self.string())
+        write!(f, "{}", self.value)
     }
 }
 
 impl fmt::Debug for StringObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "StringObject({:?})", self.string())
+        write!(f, "StringObject({:?})", self.value)
     }
 }
-
@@ -0,0 +1,116 @@
+use std::rc::Rc;
+use crate::object::{descriptor_get, BasePycObject, ExceptionKind, ExceptionObject, TypeObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::utils::{DowncastTrait, PyObject, PyResult};
+
+/// the proxy returned by super(), which looks attributes up in the classes after `class` in the
+/// method resolution order of the object
+pub struct SuperObject {
+    base: BasePycObject,
+    class: PyObject,
+    obj: Option<PyObject>,
+    /// the class whose method resolution order is searched, the object itself when it is a class
+    obj_type: Option<PyObject>
+}
+
+/// the class to search for `obj`, refer to CPython(supercheck)
+fn supercheck(class: &PyObject, obj: &PyObject) -> PyResult {
+    if obj.downcast_refcell::<TypeObject>().is_some() && TypeObject::is_subclass(obj, class) {
+        return Ok(obj.clone());
+    }
+    let obj_class = TypeObject::type_of(obj);
+    match obj_class {
+        obj_class if TypeObject::is_subclass(&obj_class, class) => Ok(obj_class),
+        _ => ExceptionObject::raise(ExceptionKind::TypeError, "super(type, obj): obj must be an instance or subtype of type")
+    }
+}
+
+impl SuperObject {
+    /// super(type) or super(type, obj), the arguments of the zero argument form are filled in
+    /// from the calling frame, refer to CPython(super_init)
+    pub fn new_from_args(args: Vec<PyObject>) -> PyResult {
+        if args.is_empty() || args.len() > 2 {
+            return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("super() takes at most 2 arguments ({} given)", args.len())
+            );
+        }
+        let class = args[0].clone();
+        if class.downcast_refcell::<TypeObject>().is_none() {
+            return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("super() argument 1 must be a type, not {}", class.borrow().type_name())
+            );
+        }
+        let obj = args.get(1).cloned();
+        let obj_type = match &obj {
+            Some(obj) => Some(supercheck(&class, obj)?),
+            None => None
+        };
+        Ok(BasePycObject::new_py_object(Self {
+            base: BasePycObject::new(ObjectType::SUPER),
+            class,
+            obj,
+            obj_type
+        }))
+    }
+}
+
+impl PartialEq<Self> for SuperObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for SuperObject {}
+
+impl PyObjectTrait for SuperObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+
+    /// refer to CPython(super_getattro)
+    fn get_attr(&self, name: String) -> PyResult {
+        if let (Some(obj), Some(obj_type)) = (&self.obj, &self.obj_type) {
+            let mro = TypeObject::mro(obj_type);
+            let start = mro.iter().position(|cls| Rc::ptr_eq(cls, &self.class)).map_or(mro.len(), |i| i + 1);
+            let found = mro[start..].iter().find_map(|cls| cls.downcast_refcell::<TypeObject>().unwrap().lookup_own(&name));
+            if let Some(value) = found {
+                // a class looks up its own attributes, so nothing is bound to it
+                let obj = Some(obj).filter(|obj| !Rc::ptr_eq(obj, obj_type));
+                return descriptor_get(value, obj, obj_type);
+            }
+        }
+        ExceptionObject::raise(
+            ExceptionKind::AttributeError,
+            &format!("'super' object has no attribute '{}'", name)
+        )
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        let class = self.class.downcast_refcell::<TypeObject>().unwrap().name().to_string();
+        match &self.obj_type {
+            Some(obj_type) => {
+                let name = obj_type.downcast_refcell::<TypeObject>().unwrap().name().to_string();
+                Ok(format!("<super: <class '{}'>, <{} object>>", class, name))
+            },
+            None => Ok(format!("<super: <class '{}'>, NULL>", class))
+        }
+    }
+}
+
+impl fmt::Display for SuperObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for SuperObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "SuperObject({:?}, {:?})", self.class, self.obj)
+    }
+}
@@ -1,18 +1,23 @@
-use crate::object::BasePycObject;
+use crate::object::{BasePycObject, Real};
 use crate::object::PyObjectTrait;
 use crate::object::ObjectType;
 use std::fmt;
-use crate::utils::PyObject;
+use crate::utils::{CmpOP, PyObject, PyResult};
 
 pub struct TrueObject {
     base: BasePycObject,
 }
 
+thread_local! {
+    static TRUE: PyObject = BasePycObject::new_py_object(TrueObject {
+        base: BasePycObject::new_from_char('T'),
+    });
+}
+
 impl TrueObject {
+    /// the True singleton
     pub fn new() -> PyObject {
-        BasePycObject::new_py_object(Self {
-            base: BasePycObject::new_from_char('T'),
-        })
+        TRUE.with(|obj| obj.clone())
     }
 }
 
@@ -31,6 +36,13 @@ impl PyObjectTrait for TrueObject {
     fn base_object(&self) -> &BasePycObject {
         &self.base
     }
+    fn hash(&self) -> PyResult<i64> {
+        Ok(1)
+    }
+    fn compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        // bool is an int subtype, so `True == 1` and `False < 0.5`
+        Ok(Real::Int(1).compare(other, op))
+    }
 }
 
 impl fmt::Display for TrueObject {
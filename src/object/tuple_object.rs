@@ -1,12 +1,12 @@
-use crate::object::{BasePycObject, IterObject};
+use crate::object::{check_args, compare_sequences, join_repr, normalize_index, object_address, range_args, repr_guard, BasePycObject, CallableObject, ExceptionKind, ExceptionObject, IntObject, SliceObject};
 use crate::object::PyObjectTrait;
 use crate::object::ObjectType;
 use std::fmt;
-use std::hash::{Hash, Hasher};
 use crate::{InputStream, PycParser};
-use crate::utils::Magic;
+use crate::interpreter::Interpreter;
+use crate::utils::{hash, CmpOP, DowncastTrait, Magic};
 
-use crate::utils::PyObject;
+use crate::utils::{PyObject, PyResult};
 
 pub struct TupleObject {
     base: BasePycObject,
@@ -35,6 +35,13 @@ impl TupleObject {
         }
     }
 
+    pub fn new_from_values(values: Vec<PyObject>) -> PyObject {
+        BasePycObject::new_py_object(Self {
+            base: BasePycObject::new_from_char('('),
+            values
+        })
+    }
+
     #[allow(dead_code)]
     pub fn take_values(self) -> Vec<PyObject> {
         self.values
@@ -42,6 +49,57 @@ impl TupleObject {
     pub fn values(&self) -> &Vec<PyObject> {
         &self.values
     }
+
+    /// `lhs + rhs`
+    pub fn concat(&self, other: &PyObject) -> PyResult {
+        match other.downcast_refcell::<TupleObject>() {
+            Some(other) => {
+                let mut values = self.values.clone();
+                values.extend(other.values.iter().cloned());
+                Ok(Self::new_from_values(values))
+            },
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("can only concatenate tuple (not \"{}\") to tuple", other.borrow().type_name())
+            )
+        }
+    }
+
+    /// `tuple * times`, a non-positive count gives the empty tuple
+    pub fn repeat(&self, times: i32) -> PyObject {
+        let mut values = vec![];
+        for _ in 0..times.max(0) {
+            values.extend(self.values.iter().cloned());
+        }
+        Self::new_from_values(values)
+    }
+}
+
+fn this(args: &[PyObject]) -> Vec<PyObject> {
+    args[0].downcast_refcell::<TupleObject>().expect("invalid tuple object").values.clone()
+}
+
+fn tuple_count(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "count", 1, 1)?;
+    let mut count = 0;
+    for value in this(&args) {
+        if Interpreter::equals(&value, &args[1])? {
+            count += 1;
+        }
+    }
+    Ok(IntObject::new_from_i32(count))
+}
+
+fn tuple_index(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "index", 1, 3)?;
+    let values = this(&args);
+    let (start, end) = range_args(&args, 2, values.len())?;
+    for i in start..end {
+        if Interpreter::equals(&values[i as usize], &args[1])? {
+            return Ok(IntObject::new_from_i32(i as i32));
+        }
+    }
+    ExceptionObject::raise(ExceptionKind::ValueError, "tuple.index(x): x not in tuple")
 }
 
 impl PartialEq<Self> for TupleObject {
@@ -62,11 +120,6 @@ impl PartialEq<Self> for TupleObject {
 
 impl Eq for TupleObject {}
 
-impl Hash for TupleObject {
-    fn hash<H: Hasher>(&self, _state: &mut H) {
-    }
-}
-
 impl PyObjectTrait for TupleObject {
     fn object_type(&self) -> ObjectType {
         self.base.object_type()
@@ -74,31 +127,82 @@ impl PyObjectTrait for TupleObject {
     fn base_object(&self) -> &BasePycObject {
         &self.base
     }
+    fn is_true(&self) -> PyResult<bool> {
+        Ok(self.length()? > 0)
+    }
+
+    fn hash(&self) -> PyResult<i64> {
+        let mut lanes = vec![];
+        for value in &self.values {
+            lanes.push(value.borrow().hash()?);
+        }
+        Ok(hash::hash_tuple(&lanes))
+    }
+
+    fn compare(&self, other: &PyObject, op: &CmpOP) -> PyResult<Option<bool>> {
+        match other.downcast_refcell::<TupleObject>() {
+            Some(other) => Ok(Some(compare_sequences(&self.values, &other.values, op)?)),
+            None => Ok(None)
+        }
+    }
+
+    fn length(&self) -> PyResult<usize> {
+        Ok(self.values.len())
+    }
+
+    fn get_method(&self, name: &str) -> Option<PyObject> {
+        let method: fn(Vec<PyObject>) -> PyResult = match name {
+            "count" => tuple_count,
+            "index" => tuple_index,
+            _ => return None
+        };
+        Some(CallableObject::new_native(name, Box::new(method)))
+    }
 
-    fn hash_key(&self) -> String {
-        // TODO: tuple should be hashable
-        unimplemented!()
+    fn get_item(&self, key: &PyObject) -> PyResult {
+        if let Some(slice) = key.downcast_refcell::<SliceObject>() {
+            let values = slice.positions(self.values.len())?.into_iter().map(|i| self.values[i].clone()).collect();
+            return Ok(Self::new_from_values(values));
+        }
+        let index = match IntObject::extract(key) {
+            Some(index) => index as i64,
+            None => return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("tuple indices must be integers or slices, not {}", key.borrow().type_name())
+            )
+        };
+        match normalize_index(index, self.values.len()) {
+            Some(index) => Ok(self.values[index].clone()),
+            None => ExceptionObject::raise(ExceptionKind::IndexError, "tuple index out of range")
+        }
     }
 
-    fn to_iter(&self) -> PyObject {
-        IterObject::new(self.values.clone())
+    fn contains(&self, item: &PyObject) -> PyResult<bool> {
+        for value in &self.values {
+            if Interpreter::equals(value, item)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        repr_guard(object_address(self), "(...)", || {
+            if self.values.len() == 1 {
+                return Ok(format!("({},)", Interpreter::repr(&self.values[0])?));
+            }
+            Ok(format!("({})", join_repr(&self.values)?))
+        })
     }
 }
 
 impl fmt::Display for TupleObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "(").unwrap();
-        for (i, entry) in self.values.iter().enumerate() {
-            if i != 0 {
-                write!(f, ", ").unwrap();
-            }
-            write!(f, "{}", entry.borrow()).unwrap();
-        }
-        write!(f, ")")
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
     }
 }
 impl fmt::Debug for TupleObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         writeln!(f, "TupleObject({:?})", self.values)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,757 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::collections::HashMap;
+use crate::frame::{get_name, new_namespace, remove_name, set_name};
+use crate::object::{check_args, descriptor_get, descriptor_set, is_data_descriptor, object_address, BasePycObject, CallableObject, CellObject, ClassMethodObject, DictObject, ExceptionKind, ExceptionObject, InstanceObject, IntLongObject, IterObject, Kwargs, MemberDescriptorObject, NoneObject, StaticMethodObject, StringObject, TupleObject};
+use crate::object::PyObjectTrait;
+use crate::object::ObjectType;
+use std::fmt;
+use crate::interpreter::Interpreter;
+use crate::utils::{DowncastTrait, PyObject, PyResult};
+
+/// a class, created by a `class` statement or builtin like `object`
+pub struct TypeObject {
+    base: BasePycObject,
+    name: String,
+    bases: Vec<PyObject>,
+    /// the class of the class, `None` for `type` itself
+    metaclass: Option<PyObject>,
+    /// the method resolution order without the type itself, which would make a reference cycle
+    mro: Vec<PyObject>,
+    /// the namespace the class body was executed in
+    dict: PyObject,
+    /// the number of `__slots__` the class adds to the instances of its bases
+    own_slots: usize,
+    /// the number of slots of the instances, inherited ones included
+    num_slots: usize,
+    /// whether the instances have a `__dict__`, which every class defining no `__slots__` gives them
+    has_dict: bool,
+    /// the type itself, which leads its method resolution order
+    this: Weak<RefCell<TypeObject>>
+}
+
+thread_local! {
+    static OBJECT: PyObject = TypeObject::new_object_type();
+    static TYPE: PyObject = TypeObject::new_type_type();
+    /// the classes standing for the builtin types in `type()` and `isinstance()`, by type name
+    static BUILTIN_TYPES: RefCell<HashMap<String, PyObject>> = RefCell::new(HashMap::new());
+}
+
+/// the construction arguments beyond the type or instance itself
+fn excess_args(args: &[PyObject], kwargs: &Kwargs) -> bool {
+    args.len() > 1 || !kwargs.is_empty()
+}
+
+/// refer to CPython(object_new)
+fn object_new(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    let cls = args[0].clone();
+    if excess_args(&args, &kwargs) {
+        if TypeObject::overrides(&cls, "__new__") {
+            return ExceptionObject::raise(ExceptionKind::TypeError, "object.__new__() takes exactly one argument (the type to instantiate)");
+        }
+        if !TypeObject::overrides(&cls, "__init__") {
+            return takes_no_arguments(&cls);
+        }
+    }
+    if cls.downcast_refcell::<TypeObject>().is_none() {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("object.__new__(X): X is not a type object ({})", cls.borrow().type_name())
+        );
+    }
+    Ok(InstanceObject::new(cls))
+}
+
+/// refer to CPython(object_init)
+fn object_init(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    if excess_args(&args, &kwargs) {
+        let cls = match args[0].downcast_refcell::<InstanceObject>() {
+            Some(instance) => instance.class(),
+            None => return Ok(NoneObject::new())
+        };
+        if TypeObject::overrides(&cls, "__init__") {
+            return ExceptionObject::raise(ExceptionKind::TypeError, "object.__init__() takes exactly one argument (the instance to initialize)");
+        }
+        if !TypeObject::overrides(&cls, "__new__") {
+            return takes_no_arguments(&cls);
+        }
+    }
+    Ok(NoneObject::new())
+}
+
+/// the attribute name argument of the attribute hooks of `object`
+fn attr_name(args: &[PyObject], index: usize) -> PyResult<String> {
+    StringObject::extract(&args[index]).or_else(|_| ExceptionObject::raise(
+        ExceptionKind::TypeError,
+        &format!("attribute name must be string, not '{}'", args[index].borrow().type_name())
+    ))
+}
+
+fn object_getattribute(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__getattribute__", 1, 1)?;
+    let name = attr_name(&args, 1)?;
+    if let Some(instance) = args[0].downcast_refcell::<InstanceObject>() {
+        return instance.generic_get_attr(&name);
+    }
+    Interpreter::load_attr(&args[0], &name)
+}
+
+fn object_setattr(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__setattr__", 2, 2)?;
+    let name = attr_name(&args, 1)?;
+    match args[0].downcast_refcell::<InstanceObject>() {
+        Some(instance) => instance.generic_set_attr(&name, Some(args[2].clone()))?,
+        None => args[0].borrow().set_attr(name, args[2].clone())?
+    }
+    Ok(NoneObject::new())
+}
+
+fn object_delattr(args: Vec<PyObject>) -> PyResult {
+    check_args(&args, "__delattr__", 1, 1)?;
+    let name = attr_name(&args, 1)?;
+    match args[0].downcast_refcell::<InstanceObject>() {
+        Some(instance) => instance.generic_set_attr(&name, None)?,
+        None => args[0].borrow().del_attr(name)?
+    }
+    Ok(NoneObject::new())
+}
+
+fn takes_no_arguments(cls: &PyObject) -> PyResult {
+    let name = cls.downcast_refcell::<TypeObject>().map(|cls| cls.name.clone()).unwrap_or_default();
+    ExceptionObject::raise(ExceptionKind::TypeError, &format!("{}() takes no arguments", name))
+}
+
+/// the default hook run when a class is subclassed, which accepts no class keyword arguments,
+/// refer to CPython(object_init_subclass)
+fn object_init_subclass(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    let name = args[0].downcast_refcell::<TypeObject>().map(|cls| cls.name.clone()).unwrap_or_default();
+    if !kwargs.is_empty() {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("{}.__init_subclass__() takes no keyword arguments", name)
+        );
+    }
+    if args.len() > 1 {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("{}.__init_subclass__() takes no arguments ({} given)", name, args.len() - 1)
+        );
+    }
+    Ok(NoneObject::new())
+}
+
+/// type(obj) gives the class of `obj`, type(name, bases, dict) creates a class, refer to CPython(type_new)
+fn type_new(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    let metatype = args[0].clone();
+    if args.len() == 2 && kwargs.is_empty() && Rc::ptr_eq(&metatype, &TypeObject::type_type()) {
+        return Ok(TypeObject::type_of(&args[1]));
+    }
+    if args.len() != 4 {
+        return ExceptionObject::raise(ExceptionKind::TypeError, "type() takes 1 or 3 arguments");
+    }
+    let name = StringObject::extract(&args[1]).or_else(|_| ExceptionObject::raise(
+        ExceptionKind::TypeError,
+        &format!("type.__new__() argument 1 must be str, not {}", args[1].borrow().type_name())
+    ))?;
+    let bases = match args[2].downcast_refcell::<TupleObject>() {
+        Some(bases) => bases.values().to_vec(),
+        None => return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("type.__new__() argument 2 must be tuple, not {}", args[2].borrow().type_name())
+        )
+    };
+    // the class gets a copy of the namespace the body was executed in
+    let dict = new_namespace();
+    match args[3].downcast_refcell::<DictObject>() {
+        Some(namespace) => {
+            for (key, value) in namespace.items() {
+                let key = StringObject::extract(&key)?;
+                set_name(&dict, &key, value);
+            }
+        },
+        None => return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("type.__new__() argument 3 must be dict, not {}", args[3].borrow().type_name())
+        )
+    }
+    // the class belongs to the calling module, `__main__` being the only one, refer to CPython(type_new_set_module)
+    if get_name(&dict, "__module__").is_none() {
+        set_name(&dict, "__module__", StringObject::new_from_str("__main__"));
+    }
+    let metatype = TypeObject::calculate_metaclass(&metatype, &bases)?;
+    let cls = TypeObject::new(&name, bases, dict, metatype)?;
+    TypeObject::init_subclass(&cls, kwargs)?;
+    Ok(cls)
+}
+
+/// refer to CPython(type_init)
+fn type_init(args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    if args.len() == 2 && !kwargs.is_empty() {
+        return ExceptionObject::raise(ExceptionKind::TypeError, "type.__init__() takes no keyword arguments");
+    }
+    if args.len() != 2 && args.len() != 4 {
+        return ExceptionObject::raise(ExceptionKind::TypeError, "type.__init__() takes 1 or 3 arguments");
+    }
+    Ok(NoneObject::new())
+}
+
+/// calling a class, which metaclasses may override, refer to CPython(type_call)
+fn type_call(mut args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+    let cls = args.remove(0);
+    if cls.downcast_refcell::<TypeObject>().is_none() {
+        return ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("descriptor '__call__' requires a 'type' object but received a '{}'", cls.borrow().type_name())
+        );
+    }
+    // type(obj) is no construction
+    if Rc::ptr_eq(&cls, &TypeObject::type_type()) && args.len() == 1 && kwargs.is_empty() {
+        return Ok(TypeObject::type_of(&args[0]));
+    }
+    TypeObject::call(&cls, args, kwargs)
+}
+
+/// the namespace a class body is executed in, refer to CPython(type_prepare)
+fn type_prepare(_args: Vec<PyObject>, _kwargs: Kwargs) -> PyResult {
+    Ok(new_namespace())
+}
+
+impl TypeObject {
+    fn new_object_type() -> PyObject {
+        let dict = new_namespace();
+        let new = CallableObject::new_native_kw("__new__", Box::new(object_new));
+        set_name(&dict, "__new__", StaticMethodObject::new_from_args(vec![new]).unwrap());
+        set_name(&dict, "__init__", CallableObject::new_native_kw("__init__", Box::new(object_init)));
+        set_name(&dict, "__getattribute__", CallableObject::new_native("__getattribute__", Box::new(object_getattribute)));
+        set_name(&dict, "__setattr__", CallableObject::new_native("__setattr__", Box::new(object_setattr)));
+        set_name(&dict, "__delattr__", CallableObject::new_native("__delattr__", Box::new(object_delattr)));
+        let init_subclass = CallableObject::new_native_kw("__init_subclass__", Box::new(object_init_subclass));
+        set_name(&dict, "__init_subclass__", ClassMethodObject::new_from_args(vec![init_subclass]).unwrap());
+        Self::new_builtin("object", vec![], dict)
+    }
+
+    fn new_type_type() -> PyObject {
+        let dict = new_namespace();
+        let new = CallableObject::new_native_kw("__new__", Box::new(type_new));
+        set_name(&dict, "__new__", StaticMethodObject::new_from_args(vec![new]).unwrap());
+        set_name(&dict, "__init__", CallableObject::new_native_kw("__init__", Box::new(type_init)));
+        set_name(&dict, "__call__", CallableObject::new_native_kw("__call__", Box::new(type_call)));
+        let prepare = CallableObject::new_native_kw("__prepare__", Box::new(type_prepare));
+        set_name(&dict, "__prepare__", ClassMethodObject::new_from_args(vec![prepare]).unwrap());
+        Self::new_builtin("type", vec![Self::object()], dict)
+    }
+
+    /// a class without a module, whose instances are no `InstanceObject`s
+    fn new_builtin(name: &str, mro: Vec<PyObject>, dict: PyObject) -> PyObject {
+        Rc::new_cyclic(|this| RefCell::new(Self {
+            base: BasePycObject::new(ObjectType::TYPE),
+            name: name.to_string(),
+            bases: mro.clone(),
+            metaclass: None,
+            mro,
+            dict,
+            own_slots: 0,
+            num_slots: 0,
+            has_dict: false,
+            this: this.clone()
+        }))
+    }
+
+    /// the builtin `object`, the last base of every class
+    pub fn object() -> PyObject {
+        OBJECT.with(|obj| obj.clone())
+    }
+
+    /// the builtin `type`, the class of the classes
+    pub fn type_type() -> PyObject {
+        TYPE.with(|ty| ty.clone())
+    }
+
+    /// the class of any object, builtin objects get a class named after their type, refer to CPython(Py_TYPE)
+    pub fn type_of(obj: &PyObject) -> PyObject {
+        if let Some(instance) = obj.downcast_refcell::<InstanceObject>() {
+            return instance.class();
+        }
+        if let Some(cls) = obj.downcast_refcell::<TypeObject>() {
+            return cls.metaclass();
+        }
+        let name = obj.borrow().type_name();
+        BUILTIN_TYPES.with(|types| {
+            types.borrow_mut()
+                .entry(name.clone())
+                .or_insert_with(|| Self::new_builtin(&name, vec![Self::object()], new_namespace()))
+                .clone()
+        })
+    }
+
+    /// the most derived of `metatype` and the metaclasses of the bases, refer to CPython(_PyType_CalculateMetaclass)
+    pub fn calculate_metaclass(metatype: &PyObject, bases: &[PyObject]) -> PyResult {
+        let mut winner = metatype.clone();
+        for base in bases {
+            let base_type = Self::type_of(base);
+            if Self::is_subclass(&winner, &base_type) {
+                continue;
+            }
+            if Self::is_subclass(&base_type, &winner) {
+                winner = base_type;
+                continue;
+            }
+            return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                "metaclass conflict: the metaclass of a derived class must be a (non-strict) subclass of the metaclasses of all its bases"
+            );
+        }
+        Ok(winner)
+    }
+
+    /// the class created by a `class` statement as an instance of `metatype`, refer to CPython(type_new_impl)
+    pub fn new(name: &str, bases: Vec<PyObject>, dict: PyObject, metatype: PyObject) -> PyResult {
+        let bases = if bases.is_empty() { vec![Self::object()] } else { bases };
+        for (i, base) in bases.iter().enumerate() {
+            let base_type = match base.downcast_refcell::<TypeObject>() {
+                Some(base_type) => base_type,
+                None => return ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    &format!("bases must be types, not '{}'", base.borrow().type_name())
+                )
+            };
+            if bases[..i].iter().any(|other| Rc::ptr_eq(other, base)) {
+                return ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    &format!("duplicate base class {}", base_type.name)
+                );
+            }
+        }
+        // __new__ is a static method even when it is not declared as one
+        if let Some(new) = get_name(&dict, "__new__").filter(|new| new.downcast_refcell::<CallableObject>().is_some()) {
+            set_name(&dict, "__new__", StaticMethodObject::new_from_args(vec![new])?);
+        }
+        // and so are the hooks called on the class itself
+        for name in ["__init_subclass__", "__class_getitem__"] {
+            if let Some(func) = get_name(&dict, name).filter(|func| func.downcast_refcell::<CallableObject>().is_some()) {
+                set_name(&dict, name, ClassMethodObject::new_from_args(vec![func])?);
+            }
+        }
+        // defining __eq__ alone makes the instances unhashable
+        if get_name(&dict, "__eq__").is_some() && get_name(&dict, "__hash__").is_none() {
+            set_name(&dict, "__hash__", NoneObject::new());
+        }
+        let mro = Self::linearize(&bases)?;
+        let num_base_slots = Self::best_base_slots(&bases)?;
+        let base_has_dict = bases.iter().any(|base| base.downcast_refcell::<TypeObject>().unwrap().has_dict);
+        let (own_slots, has_dict) = Self::create_slots(name, &dict, num_base_slots, base_has_dict)?;
+        // methods using super() or __class__ share the cell of the class, refer to CPython(type_new_set_classcell)
+        let cell = remove_name(&dict, "__classcell__");
+        let metaclass = Some(metatype).filter(|metatype| !Rc::ptr_eq(metatype, &Self::type_type()));
+        let cls: PyObject = Rc::new_cyclic(|this| RefCell::new(Self {
+            base: BasePycObject::new(ObjectType::TYPE),
+            name: name.to_string(),
+            bases,
+            metaclass,
+            mro,
+            dict: dict.clone(),
+            own_slots,
+            num_slots: num_base_slots + own_slots,
+            has_dict,
+            this: this.clone()
+        }));
+        if let Some(cell) = cell {
+            match cell.downcast_refcell_mut::<CellObject>() {
+                Some(mut cell) => cell.set(Some(cls.clone())),
+                None => return ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    &format!("__classcell__ must be a nonlocal cell, not {}", cell.borrow().type_name())
+                )
+            }
+        }
+        Self::set_names(&cls, &dict)?;
+        Ok(cls)
+    }
+
+    /// the number of slots the instances of all `bases` share, which fails when two bases add slots
+    /// independently, refer to CPython(best_base)
+    fn best_base_slots(bases: &[PyObject]) -> PyResult<usize> {
+        let mut winner: Option<PyObject> = None;
+        for base in bases {
+            let candidate = Self::solid_base(base);
+            winner = match winner {
+                Some(winner) if Self::is_subclass(&winner, &candidate) => Some(winner),
+                Some(winner) if !Self::is_subclass(&candidate, &winner) => return ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    "multiple bases have instance lay-out conflict"
+                ),
+                _ => Some(candidate)
+            };
+        }
+        Ok(winner.map_or(0, |winner| winner.downcast_refcell::<TypeObject>().unwrap().num_slots))
+    }
+
+    /// the nearest class along the method resolution order adding slots, refer to CPython(solid_base)
+    fn solid_base(cls: &PyObject) -> PyObject {
+        Self::mro(cls).into_iter()
+            .find(|base| base.downcast_refcell::<TypeObject>().unwrap().own_slots > 0)
+            .unwrap_or_else(Self::object)
+    }
+
+    /// put a member descriptor into `dict` for each name in `__slots__`, numbering them from
+    /// `first_index`, which gives the number of slots and whether the instances have a `__dict__`,
+    /// refer to CPython(type_new_slots)
+    fn create_slots(class_name: &str, dict: &PyObject, first_index: usize, base_has_dict: bool) -> PyResult<(usize, bool)> {
+        let slots = match get_name(dict, "__slots__") {
+            Some(slots) => slots,
+            None => return Ok((0, true))
+        };
+        let items = if slots.downcast_refcell::<StringObject>().is_some() { vec![slots] } else { IterObject::collect(&slots)? };
+        let mut has_dict = base_has_dict;
+        let mut names: Vec<String> = vec![];
+        for item in items {
+            let name = StringObject::extract(&item).or_else(|_| ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("__slots__ items must be strings, not '{}'", item.borrow().type_name())
+            ))?;
+            let mut chars = name.chars();
+            let is_identifier = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+                && chars.all(|c| c.is_alphanumeric() || c == '_');
+            if !is_identifier {
+                return ExceptionObject::raise(ExceptionKind::TypeError, "__slots__ must be identifiers");
+            }
+            match name.as_str() {
+                "__dict__" if has_dict => return ExceptionObject::raise(
+                    ExceptionKind::TypeError,
+                    "__dict__ slot disallowed: we already got one"
+                ),
+                "__dict__" => has_dict = true,
+                // instances cannot be weakly referenced, there is nothing to reserve
+                "__weakref__" => {},
+                _ => names.push(Self::mangle(class_name, name))
+            }
+        }
+        for name in &names {
+            if get_name(dict, name).is_some() {
+                return ExceptionObject::raise(
+                    ExceptionKind::ValueError,
+                    &format!("'{}' in __slots__ conflicts with class variable", name)
+                );
+            }
+        }
+        for (i, name) in names.iter().enumerate() {
+            set_name(dict, name, MemberDescriptorObject::new(name, class_name, first_index + i));
+        }
+        Ok((names.len(), has_dict))
+    }
+
+    /// the private name `__x` of a class body becomes `_Class__x`, refer to CPython(_Py_Mangle)
+    fn mangle(class_name: &str, name: String) -> String {
+        let class_name = class_name.trim_start_matches('_');
+        if !name.starts_with("__") || name.ends_with("__") || class_name.is_empty() {
+            return name;
+        }
+        format!("_{}{}", class_name, name)
+    }
+
+    /// run `__init_subclass__` of the parent of a new class with the class keyword arguments,
+    /// refer to CPython(type_new_init_subclass)
+    fn init_subclass(cls: &PyObject, kwargs: Kwargs) -> PyResult<()> {
+        let mro = Self::mro(cls);
+        let hook = mro[1..].iter().find_map(|base| base.downcast_refcell::<TypeObject>().unwrap().lookup_own("__init_subclass__"));
+        if let Some(hook) = hook {
+            let hook = descriptor_get(hook, None, cls)?;
+            Interpreter::call_kw(&hook, vec![], kwargs)?;
+        }
+        Ok(())
+    }
+
+    /// tell the attributes which define `__set_name__` the class and name they are bound to, refer to CPython(type_new_set_names)
+    fn set_names(cls: &PyObject, dict: &PyObject) -> PyResult<()> {
+        let items = dict.downcast_refcell::<DictObject>().unwrap().items();
+        for (name, value) in items {
+            let set_name = value.borrow().get_method("__set_name__");
+            if let Some(set_name) = set_name {
+                Interpreter::call(&set_name, vec![value, cls.clone(), name])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// the C3 linearization of the bases: a class comes before its bases, and the bases keep
+    /// their order in every list merged, refer to CPython(mro_implementation)
+    fn linearize(bases: &[PyObject]) -> PyResult<Vec<PyObject>> {
+        let mut sequences: Vec<Vec<PyObject>> = bases.iter().map(Self::mro).collect();
+        sequences.push(bases.to_vec());
+        let mut mro: Vec<PyObject> = vec![];
+        loop {
+            sequences.retain(|seq| !seq.is_empty());
+            if sequences.is_empty() {
+                return Ok(mro);
+            }
+            // the next class is the first head which appears in no tail
+            let head = sequences.iter().map(|seq| seq[0].clone()).find(|head| {
+                !sequences.iter().any(|seq| seq[1..].iter().any(|cls| Rc::ptr_eq(cls, head)))
+            });
+            let head = match head {
+                Some(head) => head,
+                None => {
+                    // refer to CPython(set_mro_error)
+                    let mut names: Vec<String> = vec![];
+                    for seq in &sequences {
+                        let name = seq[0].downcast_refcell::<TypeObject>().unwrap().name.clone();
+                        if !names.contains(&name) {
+                            names.push(name);
+                        }
+                    }
+                    return ExceptionObject::raise(
+                        ExceptionKind::TypeError,
+                        &format!("Cannot create a consistent method resolution\norder (MRO) for bases {}", names.join(", "))
+                    );
+                }
+            };
+            for seq in sequences.iter_mut() {
+                if Rc::ptr_eq(&seq[0], &head) {
+                    seq.remove(0);
+                }
+            }
+            mro.push(head);
+        }
+    }
+
+    /// the method resolution order of `cls`, starting with `cls` itself
+    pub fn mro(cls: &PyObject) -> Vec<PyObject> {
+        let mut mro = vec![cls.clone()];
+        mro.extend(cls.downcast_refcell::<TypeObject>().expect("invalid type object").mro.iter().cloned());
+        mro
+    }
+
+    fn this(&self) -> PyObject {
+        self.this.upgrade().expect("type should be alive")
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// find `name` in the dicts along the method resolution order of `cls`
+    pub fn lookup(cls: &PyObject, name: &str) -> Option<PyObject> {
+        Self::mro(cls).iter().find_map(|base| base.downcast_refcell::<TypeObject>().unwrap().lookup_own(name))
+    }
+
+    /// whether `cls` has its own `name` method instead of the one of `object`
+    pub fn overrides(cls: &PyObject, name: &str) -> bool {
+        let method = Self::lookup(cls, name);
+        let default = Self::lookup(&Self::object(), name);
+        match (method, default) {
+            (Some(method), Some(default)) => !Rc::ptr_eq(&method, &default),
+            _ => true
+        }
+    }
+
+    /// find `name` in the dict of the class alone
+    pub fn lookup_own(&self, name: &str) -> Option<PyObject> {
+        get_name(&self.dict, name)
+    }
+
+    /// whether `cls` is `other` or derives from it
+    pub fn is_subclass(cls: &PyObject, other: &PyObject) -> bool {
+        if Rc::ptr_eq(cls, other) {
+            return true;
+        }
+        match cls.downcast_refcell::<TypeObject>() {
+            Some(cls) => cls.mro.iter().any(|base| Rc::ptr_eq(base, other)),
+            None => false
+        }
+    }
+
+    /// calling a class creates an instance with `__new__` and initializes it with `__init__`, refer to CPython(type_call)
+    pub fn call(cls: &PyObject, args: Vec<PyObject>, kwargs: Kwargs) -> PyResult {
+        let new = Self::lookup(cls, "__new__").expect("object defines __new__");
+        let mut new_args = vec![cls.clone()];
+        new_args.extend(args.iter().cloned());
+        let obj = Interpreter::call_kw(&new, new_args, kwargs.clone())?;
+        // __new__ may return an object of another class, which is not initialized
+        let class = Self::type_of(&obj);
+        if !Self::is_subclass(&class, cls) {
+            return Ok(obj);
+        }
+        let init = Self::lookup(&class, "__init__").expect("object defines __init__");
+        let mut init_args = vec![obj.clone()];
+        init_args.extend(args);
+        let res = Interpreter::call_kw(&init, init_args, kwargs)?;
+        if res.borrow().object_type() != ObjectType::NONE {
+            return ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("__init__() should return None, not '{}'", res.borrow().type_name())
+            );
+        }
+        Ok(obj)
+    }
+
+    pub fn qualified_name(&self) -> String {
+        get_name(&self.dict, "__qualname__")
+            .and_then(|name| StringObject::extract(&name).ok())
+            .unwrap_or_else(|| self.name.clone())
+    }
+
+    pub fn module(&self) -> Option<String> {
+        get_name(&self.dict, "__module__").and_then(|name| StringObject::extract(&name).ok())
+    }
+
+    pub fn num_slots(&self) -> usize {
+        self.num_slots
+    }
+
+    pub fn has_dict(&self) -> bool {
+        self.has_dict
+    }
+
+    pub fn metaclass(&self) -> PyObject {
+        self.metaclass.clone().unwrap_or_else(Self::type_type)
+    }
+
+    /// call the method `name` of a user-defined metaclass bound to the class, `None` when it lacks it
+    fn call_meta(&self, name: &str, args: Vec<PyObject>) -> Option<PyResult> {
+        let metaclass = self.metaclass.clone()?;
+        let method = Self::lookup(&metaclass, name)?;
+        Some(descriptor_get(method, Some(&self.this()), &metaclass).and_then(|method| Interpreter::call(&method, args)))
+    }
+
+    fn no_attribute<T>(&self, name: &str) -> PyResult<T> {
+        ExceptionObject::raise(
+            ExceptionKind::AttributeError,
+            &format!("type object '{}' has no attribute '{}'", self.name, name)
+        )
+    }
+}
+
+impl PartialEq<Self> for TypeObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for TypeObject {}
+
+impl PyObjectTrait for TypeObject {
+    fn object_type(&self) -> ObjectType {
+        self.base.object_type()
+    }
+    fn base_object(&self) -> &BasePycObject {
+        &self.base
+    }
+
+    /// data descriptors of the metaclass, then the attributes along the method resolution order,
+    /// then the other attributes of the metaclass bound to the class, refer to CPython(type_getattro)
+    fn get_attr(&self, name: String) -> PyResult {
+        match name.as_str() {
+            "__name__" => return Ok(StringObject::new_from_str(&self.name)),
+            "__qualname__" => return Ok(StringObject::new_from_string(self.qualified_name())),
+            "__dict__" => return Ok(self.dict.clone()),
+            "__bases__" => return Ok(TupleObject::new_from_values(self.bases.clone())),
+            "__mro__" => return Ok(TupleObject::new_from_values(Self::mro(&self.this()))),
+            "__class__" => return Ok(self.metaclass()),
+            _ => {}
+        }
+        let cls = self.this();
+        let metaclass = self.metaclass();
+        let meta_attr = Self::lookup(&metaclass, &name);
+        if let Some(attr) = meta_attr.as_ref().filter(|attr| is_data_descriptor(attr)) {
+            return descriptor_get(attr.clone(), Some(&cls), &metaclass);
+        }
+        if let Some(value) = Self::lookup(&cls, &name) {
+            return descriptor_get(value, None, &cls);
+        }
+        match meta_attr {
+            Some(attr) => descriptor_get(attr, Some(&cls), &metaclass),
+            None => self.no_attribute(&name)
+        }
+    }
+
+    fn set_attr(&self, name: String, value: PyObject) -> PyResult<()> {
+        if let Some(attr) = Self::lookup(&self.metaclass(), &name) {
+            if let Some(res) = descriptor_set(&attr, &self.this(), Some(value.clone())) {
+                return res;
+            }
+        }
+        set_name(&self.dict, &name, value);
+        Ok(())
+    }
+
+    fn del_attr(&self, name: String) -> PyResult<()> {
+        if let Some(attr) = Self::lookup(&self.metaclass(), &name) {
+            if let Some(res) = descriptor_set(&attr, &self.this(), None) {
+                return res;
+            }
+        }
+        match remove_name(&self.dict, &name) {
+            Some(_) => Ok(()),
+            None => self.no_attribute(&name)
+        }
+    }
+
+    fn repr(&self) -> PyResult<String> {
+        if let Some(res) = self.call_meta("__repr__", vec![]) {
+            let res = res?;
+            return StringObject::extract(&res).or_else(|_| ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("__repr__ returned non-string (type {})", res.borrow().type_name())
+            ));
+        }
+        match self.module() {
+            Some(module) => Ok(format!("<class '{}.{}'>", module, self.qualified_name())),
+            None => Ok(format!("<class '{}'>", self.name))
+        }
+    }
+
+    /// `__getitem__` of the metaclass, or else `__class_getitem__` of the class, refer to CPython(PyObject_GetItem)
+    fn get_item(&self, key: &PyObject) -> PyResult {
+        if let Some(res) = self.call_meta("__getitem__", vec![key.clone()]) {
+            return res;
+        }
+        let cls = self.this();
+        match Self::lookup(&cls, "__class_getitem__") {
+            Some(method) => Interpreter::call(&descriptor_get(method, None, &cls)?, vec![key.clone()]),
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("type '{}' is not subscriptable", self.name)
+            )
+        }
+    }
+
+    fn contains(&self, item: &PyObject) -> PyResult<bool> {
+        match self.call_meta("__contains__", vec![item.clone()]) {
+            Some(res) => Interpreter::is_true(&res?),
+            None => ExceptionObject::raise(ExceptionKind::TypeError, "argument of type 'type' is not iterable")
+        }
+    }
+
+    fn to_iter(&self) -> PyResult {
+        match self.call_meta("__iter__", vec![]) {
+            Some(iter) => iter,
+            None => ExceptionObject::raise(ExceptionKind::TypeError, "'type' object is not iterable")
+        }
+    }
+
+    fn length(&self) -> PyResult<usize> {
+        let res = match self.call_meta("__len__", vec![]) {
+            Some(res) => res?,
+            None => return ExceptionObject::raise(ExceptionKind::TypeError, "object of type 'type' has no len()")
+        };
+        match IntLongObject::extract(&res) {
+            Some(len) if len < 0 => ExceptionObject::raise(ExceptionKind::ValueError, "__len__() should return >= 0"),
+            Some(len) => Ok(len as usize),
+            None => ExceptionObject::raise(
+                ExceptionKind::TypeError,
+                &format!("'{}' object cannot be interpreted as an integer", res.borrow().type_name())
+            )
+        }
+    }
+}
+
+impl fmt::Display for TypeObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.repr().map_err(|_| fmt::Error)?)
+    }
+}
+impl fmt::Debug for TypeObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "TypeObject({} at {:#x})", self.name, object_address(self))
+    }
+}
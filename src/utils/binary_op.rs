@@ -1,7 +1,9 @@
 use std::fmt::{Debug, Formatter};
-use crate::object::{IntObject};
+use std::rc::Rc;
+use crate::interpreter::Interpreter;
+use crate::object::{ByteArrayObject, BytesObject, DictObject, ExceptionKind, ExceptionObject, IntObject, ListObject, ObjectType, Real, SetObject, StringObject, TupleObject, TypeObject};
 
-use crate::utils::{DowncastTrait, PyObject};
+use crate::utils::{DowncastTrait, PyObject, PyResult};
 pub enum BinaryOp{
     Add(bool),
     And(bool),
@@ -86,71 +88,369 @@ impl From<u8> for BinaryOp {
     }
 }
 
+/// the quotient rounded towards negative infinity and the remainder with the sign of the divisor
+fn floor_divmod(lhs: i64, rhs: i64) -> Option<(i64, i64)> {
+    let (mut div, mut rem) = (lhs.checked_div(rhs)?, lhs % rhs);
+    if rem != 0 && (rem < 0) != (rhs < 0) {
+        div -= 1;
+        rem += rhs;
+    }
+    Some((div, rem))
+}
+
+/// refer to CPython(_float_div_mod)
+fn float_divmod(lhs: f64, rhs: f64) -> (f64, f64) {
+    let mut rem = lhs % rhs;
+    let mut div = (lhs - rem) / rhs;
+    if rem != 0.0 {
+        if (rhs < 0.0) != (rem < 0.0) {
+            rem += rhs;
+            div -= 1.0;
+        }
+    } else {
+        rem = 0.0f64.copysign(rhs);
+    }
+    let floor = if div != 0.0 {
+        let floor = div.floor();
+        if div - floor > 0.5 { floor + 1.0 } else { floor }
+    } else {
+        0.0f64.copysign(lhs / rhs)
+    };
+    (floor, rem)
+}
+
 impl BinaryOp {
-    #[allow(unreachable_code)]
-    pub fn handle(&self, lhs: PyObject, rhs: PyObject) -> PyObject {
+    fn is_inplace(&self) -> bool {
+        match self {
+            BinaryOp::Add(inplace)
+            | BinaryOp::And(inplace)
+            | BinaryOp::FloorDivide(inplace)
+            | BinaryOp::ShiftLeft(inplace)
+            | BinaryOp::MatrixMultiply(inplace)
+            | BinaryOp::Multiply(inplace)
+            | BinaryOp::Modulo(inplace)
+            | BinaryOp::OR(inplace)
+            | BinaryOp::POWER(inplace)
+            | BinaryOp::ShiftRight(inplace)
+            | BinaryOp::Minus(inplace)
+            | BinaryOp::Divide(inplace)
+            | BinaryOp::XOR(inplace) => *inplace
+        }
+    }
+
+    /// the operator method name without underscores, e.g. `add` for `__add__` and `__iadd__`
+    fn method_stem(&self) -> &'static str {
+        match self {
+            BinaryOp::Add(_) => "add",
+            BinaryOp::And(_) => "and",
+            BinaryOp::FloorDivide(_) => "floordiv",
+            BinaryOp::ShiftLeft(_) => "lshift",
+            BinaryOp::MatrixMultiply(_) => "matmul",
+            BinaryOp::Multiply(_) => "mul",
+            BinaryOp::Modulo(_) => "mod",
+            BinaryOp::OR(_) => "or",
+            BinaryOp::POWER(_) => "pow",
+            BinaryOp::ShiftRight(_) => "rshift",
+            BinaryOp::Minus(_) => "sub",
+            BinaryOp::Divide(_) => "truediv",
+            BinaryOp::XOR(_) => "xor",
+        }
+    }
+
+    /// an augmented assignment first tries the in-place form of the operator and falls back to
+    /// the binary one, refer to CPython(binary_iop1)
+    pub fn handle(&self, lhs: PyObject, rhs: PyObject) -> PyResult {
+        if self.is_inplace() {
+            if let Some(res) = self.handle_inplace(&lhs, &rhs) {
+                return res;
+            }
+        }
+        if let Some(res) = self.handle_overloaded(&lhs, &rhs) {
+            return res;
+        }
+        if let Some(res) = self.handle_str(&lhs, &rhs) {
+            return res;
+        }
+        if let Some(res) = self.handle_list(&lhs, &rhs) {
+            return res;
+        }
+        if let Some(res) = self.handle_tuple(&lhs, &rhs) {
+            return res;
+        }
+        if let Some(res) = self.handle_bytes(&lhs, &rhs) {
+            return res;
+        }
+        if let Some(res) = self.handle_dict(&lhs, &rhs) {
+            return res;
+        }
+        if let Some(res) = self.handle_set(&lhs, &rhs) {
+            return res;
+        }
+        if let Some(res) = self.handle_number(&lhs, &rhs) {
+            return res;
+        }
+        let (lhs, rhs) = (lhs.borrow().type_name(), rhs.borrow().type_name());
+        // pow() shares the error of `**`
+        let symbol = match self {
+            BinaryOp::POWER(false) => "** or pow()".to_string(),
+            op => format!("{:?}", op)
+        };
+        ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("unsupported operand type(s) for {}: '{}' and '{}'", symbol, lhs, rhs)
+        )
+    }
+
+    /// the operator methods `__add__` of `lhs` and the reflected `__radd__` of `rhs`, a method
+    /// returning NotImplemented passes the turn to the other operand, refer to CPython(binary_op1)
+    fn handle_overloaded(&self, lhs: &PyObject, rhs: &PyObject) -> Option<PyResult> {
+        let stem = self.method_stem();
+        let reflected_name = format!("__r{}__", stem);
+        let method = lhs.borrow().get_method(&format!("__{}__", stem));
+        // the reflected method is skipped for operands of the same type
+        let (lhs_type, rhs_type) = (TypeObject::type_of(lhs), TypeObject::type_of(rhs));
+        let same_type = Rc::ptr_eq(&lhs_type, &rhs_type);
+        let reflected = if same_type { None } else { rhs.borrow().get_method(&reflected_name) };
+        let mut calls = vec![];
+        if let Some(method) = method {
+            calls.push((method, lhs.clone(), rhs.clone()));
+        }
+        if let Some(reflected) = reflected {
+            // a subclass overriding the reflected method gets the first chance, refer to CPython(method_is_overloaded)
+            let rhs_first = rhs.borrow().is_proper_subtype_of(lhs) && {
+                match (TypeObject::lookup(&rhs_type, &reflected_name), TypeObject::lookup(&lhs_type, &reflected_name)) {
+                    (Some(rhs_method), Some(lhs_method)) => !Rc::ptr_eq(&rhs_method, &lhs_method),
+                    _ => true
+                }
+            };
+            let call = (reflected, rhs.clone(), lhs.clone());
+            if rhs_first { calls.insert(0, call) } else { calls.push(call) }
+        }
+        for (method, this, other) in calls {
+            match Interpreter::call(&method, vec![this, other]) {
+                Ok(res) if res.borrow().object_type() == ObjectType::NOT_IMPLEMENTED => continue,
+                res => return Some(res)
+            }
+        }
+        None
+    }
+
+    /// arithmetic on ints, floats and bools, an int result only when both operands are ints
+    fn handle_number(&self, lhs: &PyObject, rhs: &PyObject) -> Option<PyResult> {
+        let (lhs_value, rhs_value) = (Real::extract(lhs)?, Real::extract(rhs)?);
+        let bools = [lhs, rhs].iter().all(|obj| matches!(obj.borrow().object_type(), ObjectType::TRUE | ObjectType::FALSE));
+        let res = match (lhs_value, rhs_value) {
+            (Real::Int(lhs), Real::Int(rhs)) => match self {
+                // the bitwise operators keep bool operands a bool, `True & False is False`
+                BinaryOp::And(_) if bools => return Some(Ok(Interpreter::new_bool_object(lhs & rhs != 0))),
+                BinaryOp::OR(_) if bools => return Some(Ok(Interpreter::new_bool_object(lhs | rhs != 0))),
+                BinaryOp::XOR(_) if bools => return Some(Ok(Interpreter::new_bool_object(lhs ^ rhs != 0))),
+                _ => self.int_op(lhs, rhs)?
+            },
+            (lhs, rhs) => self.float_op(lhs.as_f64(), rhs.as_f64())?
+        };
+        Some(res.map(Real::into_object))
+    }
+
+    /// refer to CPython(long_as_number), ints are limited to 64 bits here
+    fn int_op(&self, lhs: i64, rhs: i64) -> Option<PyResult<Real>> {
+        let overflow = || ExceptionObject::raise(ExceptionKind::OverflowError, "int too large to represent in 64 bits");
+        let checked = |value: Option<i64>| value.map_or_else(overflow, |value| Ok(Real::Int(value)));
+        let zero_division = |message| ExceptionObject::raise(ExceptionKind::ZeroDivisionError, message);
+        let negative_shift = || ExceptionObject::raise(ExceptionKind::ValueError, "negative shift count");
+        Some(match self {
+            BinaryOp::Add(_) => checked(lhs.checked_add(rhs)),
+            BinaryOp::Minus(_) => checked(lhs.checked_sub(rhs)),
+            BinaryOp::Multiply(_) => checked(lhs.checked_mul(rhs)),
+            BinaryOp::FloorDivide(_) if rhs == 0 => zero_division("integer division or modulo by zero"),
+            BinaryOp::Modulo(_) if rhs == 0 => zero_division("integer modulo by zero"),
+            BinaryOp::FloorDivide(_) => checked(floor_divmod(lhs, rhs).map(|(div, _)| div)),
+            BinaryOp::Modulo(_) => checked(floor_divmod(lhs, rhs).map(|(_, rem)| rem)),
+            BinaryOp::Divide(_) if rhs == 0 => ExceptionObject::raise(ExceptionKind::ZeroDivisionError, "division by zero"),
+            BinaryOp::Divide(_) => Ok(Real::Float(lhs as f64 / rhs as f64)),
+            // a negative exponent gives a float
+            BinaryOp::POWER(_) if rhs < 0 => return self.float_op(lhs as f64, rhs as f64),
+            BinaryOp::POWER(_) => checked(u32::try_from(rhs).ok().and_then(|rhs| lhs.checked_pow(rhs))
+                .or(if lhs == 0 || lhs == 1 { Some(lhs) } else { None })),
+            BinaryOp::ShiftLeft(_) if rhs < 0 => negative_shift(),
+            BinaryOp::ShiftLeft(_) if lhs == 0 => Ok(Real::Int(0)),
+            BinaryOp::ShiftLeft(_) => checked(if rhs < 64 && (lhs << rhs) >> rhs == lhs { Some(lhs << rhs) } else { None }),
+            BinaryOp::ShiftRight(_) if rhs < 0 => negative_shift(),
+            BinaryOp::ShiftRight(_) => Ok(Real::Int(lhs >> rhs.min(63))),
+            BinaryOp::And(_) => Ok(Real::Int(lhs & rhs)),
+            BinaryOp::OR(_) => Ok(Real::Int(lhs | rhs)),
+            BinaryOp::XOR(_) => Ok(Real::Int(lhs ^ rhs)),
+            BinaryOp::MatrixMultiply(_) => return None
+        })
+    }
+
+    /// refer to CPython(float_as_number), the bitwise operators are not defined for floats
+    fn float_op(&self, lhs: f64, rhs: f64) -> Option<PyResult<Real>> {
+        let float = |value: f64| Ok(Real::Float(value));
+        Some(match self {
+            BinaryOp::Add(_) => float(lhs + rhs),
+            BinaryOp::Minus(_) => float(lhs - rhs),
+            BinaryOp::Multiply(_) => float(lhs * rhs),
+            BinaryOp::Divide(_) if rhs == 0.0 => ExceptionObject::raise(ExceptionKind::ZeroDivisionError, "float division by zero"),
+            BinaryOp::Divide(_) => float(lhs / rhs),
+            BinaryOp::FloorDivide(_) if rhs == 0.0 => ExceptionObject::raise(ExceptionKind::ZeroDivisionError, "float floor division by zero"),
+            BinaryOp::FloorDivide(_) => float(float_divmod(lhs, rhs).0),
+            BinaryOp::Modulo(_) if rhs == 0.0 => ExceptionObject::raise(ExceptionKind::ZeroDivisionError, "float modulo"),
+            BinaryOp::Modulo(_) => float(float_divmod(lhs, rhs).1),
+            BinaryOp::POWER(_) if lhs == 0.0 && rhs < 0.0 => ExceptionObject::raise(
+                ExceptionKind::ZeroDivisionError,
+                "0.0 cannot be raised to a negative power"
+            ),
+            BinaryOp::POWER(_) if lhs < 0.0 && rhs.is_finite() && rhs.fract() != 0.0 => ExceptionObject::raise(
+                ExceptionKind::ValueError,
+                "negative number cannot be raised to a fractional power"
+            ),
+            BinaryOp::POWER(_) => float(lhs.powf(rhs)),
+            _ => return None
+        })
+    }
+
+    /// the in-place operators of the mutable builtins mutate `lhs` so every alias observes the
+    /// change, other objects get their `__iadd__` and friends called
+    fn handle_inplace(&self, lhs: &PyObject, rhs: &PyObject) -> Option<PyResult> {
+        if lhs.downcast_refcell::<ListObject>().is_some() {
+            match self {
+                BinaryOp::Add(_) => return Some(ListObject::extend(lhs, rhs).map(|_| lhs.clone())),
+                BinaryOp::Multiply(_) => if let Some(times) = IntObject::extract(rhs) {
+                    lhs.downcast_refcell_mut::<ListObject>().unwrap().repeat_in_place(times);
+                    return Some(Ok(lhs.clone()));
+                },
+                _ => {}
+            }
+        }
+        if matches!(self, BinaryOp::OR(_)) && lhs.downcast_refcell::<DictObject>().is_some() {
+            return Some(DictObject::update(lhs, rhs).map(|_| lhs.clone()));
+        }
+        let res = match self {
+            BinaryOp::OR(_) => SetObject::binary_op(lhs, rhs, '|', true),
+            BinaryOp::And(_) => SetObject::binary_op(lhs, rhs, '&', true),
+            BinaryOp::Minus(_) => SetObject::binary_op(lhs, rhs, '-', true),
+            BinaryOp::XOR(_) => SetObject::binary_op(lhs, rhs, '^', true),
+            _ => None
+        };
+        if res.is_some() {
+            return res;
+        }
+        let method = lhs.borrow().get_method(&format!("__i{}__", self.method_stem()))?;
+        match Interpreter::call(&method, vec![lhs.clone(), rhs.clone()]) {
+            Ok(res) if res.borrow().object_type() == ObjectType::NOT_IMPLEMENTED => None,
+            res => Some(res)
+        }
+    }
+
+    /// list concatenation and repetition
+    fn handle_list(&self, lhs: &PyObject, rhs: &PyObject) -> Option<PyResult> {
         match self {
             BinaryOp::Add(_) => {
-                // FIXME: when it is BinaryOp::Add(true), we should operate in-place
-                // but the PyObject is a Rc, which is immutable
-                // we should use RefCell
-                if let Some(lhs) = lhs.clone().downcast_refcell::<IntObject>() {
-                    if let Some(rhs) = rhs.clone().downcast_refcell::<IntObject>() {
-                        return IntObject::new_from_i32(lhs.value() + rhs.value())
-                    }
+                let lhs = lhs.downcast_refcell::<ListObject>()?;
+                Some(lhs.concat(rhs))
+            },
+            BinaryOp::Multiply(_) => {
+                if let Some(times) = IntObject::extract(rhs) {
+                    let list = lhs.downcast_refcell::<ListObject>()?;
+                    return Some(Ok(ListObject::new_from_values(list.repeat(times))));
                 }
-                panic!("cannot perform add on {lhs:?} and {rhs:?}");
+                let list = rhs.downcast_refcell::<ListObject>()?;
+                Some(Ok(ListObject::new_from_values(list.repeat(IntObject::extract(lhs)?))))
             },
-            _ => {
-                if let Some(lhs) = lhs.clone().downcast_refcell::<IntObject>() {
-                    if let Some(rhs) = rhs.clone().downcast_refcell::<IntObject>() {
-                        return match self {
-                            BinaryOp::And(_) => {
-                                return IntObject::new_from_i32(lhs.value() & rhs.value());
-                            },
-                            BinaryOp::FloorDivide(_) => {
-                                return IntObject::new_from_i32(lhs.value() / rhs.value());
-                            },
-                            BinaryOp::ShiftLeft(_) => {
-                                return IntObject::new_from_i32(lhs.value() << rhs.value());
-                            },
-                            BinaryOp::MatrixMultiply(_) => {
-                                unimplemented!()
-                            },
-                            BinaryOp::Multiply(_) => {
-                                return IntObject::new_from_i32(lhs.value() * rhs.value());
-                            },
-                            BinaryOp::Modulo(_) => {
-                                return IntObject::new_from_i32(lhs.value() % rhs.value());
-                            },
-                            BinaryOp::OR(_) => {
-                                return IntObject::new_from_i32(lhs.value() | rhs.value());
-                            },
-                            BinaryOp::POWER(_) => {
-                                if rhs.value() < 0 {
-                                    panic!("the exponent number must be positive integer")
-                                }
-                                return IntObject::new_from_i32(lhs.value().pow(rhs.value() as u32));
-                            },
-                            BinaryOp::ShiftRight(_) => {
-                                return IntObject::new_from_i32(lhs.value() >> rhs.value());
-                            },
-                            BinaryOp::Minus(_) => {
-                                return IntObject::new_from_i32(lhs.value() - rhs.value());
-                            },
-                            BinaryOp::Divide(_) => {
-                                unimplemented!()
-                            },
-                            BinaryOp::XOR(_) => {
-                                return IntObject::new_from_i32(lhs.value() ^ rhs.value());
-                            },
-                            _ => unreachable!()
-                        };
-                    }
+            _ => None
+        }
+    }
+
+    /// tuple concatenation and repetition
+    fn handle_tuple(&self, lhs: &PyObject, rhs: &PyObject) -> Option<PyResult> {
+        match self {
+            BinaryOp::Add(_) => {
+                let lhs = lhs.downcast_refcell::<TupleObject>()?;
+                Some(lhs.concat(rhs))
+            },
+            BinaryOp::Multiply(_) => {
+                if let (Some(t), Some(times)) = (lhs.downcast_refcell::<TupleObject>(), IntObject::extract(rhs)) {
+                    return Some(Ok(t.repeat(times)));
                 }
+                let t = rhs.downcast_refcell::<TupleObject>()?;
+                Some(Ok(t.repeat(IntObject::extract(lhs)?)))
+            },
+            _ => None
+        }
+    }
 
+    /// bytes and bytearray concatenation and repetition, the result has the type of the sequence
+    fn handle_bytes(&self, lhs: &PyObject, rhs: &PyObject) -> Option<PyResult> {
+        let new_like = |obj: &PyObject| -> Option<fn(Vec<u8>) -> PyObject> {
+            if obj.downcast_refcell::<BytesObject>().is_some() {
+                return Some(BytesObject::new_from_vec);
             }
+            obj.downcast_refcell::<ByteArrayObject>().map(|_| ByteArrayObject::new_from_vec as fn(Vec<u8>) -> PyObject)
         };
-        unreachable!()
+        match self {
+            BinaryOp::Add(_) => {
+                let new = new_like(lhs)?;
+                let mut data = BytesObject::extract(lhs)?;
+                match BytesObject::extract(rhs) {
+                    Some(other) => data.extend(other),
+                    None => return Some(ExceptionObject::raise(
+                        ExceptionKind::TypeError,
+                        &format!("can't concat {} to {}", rhs.borrow().type_name(), lhs.borrow().type_name())
+                    ))
+                }
+                Some(Ok(new(data)))
+            },
+            BinaryOp::Multiply(_) => {
+                let (seq, times) = match new_like(lhs) {
+                    Some(_) => (lhs, IntObject::extract(rhs)?),
+                    None => (rhs, IntObject::extract(lhs)?)
+                };
+                let new = new_like(seq)?;
+                Some(Ok(new(BytesObject::extract(seq)?.repeat(times.max(0) as usize))))
+            },
+            _ => None
+        }
+    }
+
+    /// dict merge
+    fn handle_dict(&self, lhs: &PyObject, rhs: &PyObject) -> Option<PyResult> {
+        match self {
+            BinaryOp::OR(_) => {
+                let lhs = lhs.downcast_refcell::<DictObject>()?;
+                let rhs = rhs.downcast_refcell::<DictObject>()?;
+                Some(DictObject::merge(&lhs, &rhs))
+            },
+            _ => None
+        }
+    }
+
+    /// set algebra
+    fn handle_set(&self, lhs: &PyObject, rhs: &PyObject) -> Option<PyResult> {
+        match self {
+            BinaryOp::OR(_) => SetObject::binary_op(lhs, rhs, '|', false),
+            BinaryOp::And(_) => SetObject::binary_op(lhs, rhs, '&', false),
+            BinaryOp::Minus(_) => SetObject::binary_op(lhs, rhs, '-', false),
+            BinaryOp::XOR(_) => SetObject::binary_op(lhs, rhs, '^', false),
+            _ => None
+        }
+    }
+
+    /// str concatenation and repetition
+    fn handle_str(&self, lhs: &PyObject, rhs: &PyObject) -> Option<PyResult> {
+        match self {
+            BinaryOp::Add(_) => {
+                let lhs = lhs.downcast_refcell::<StringObject>()?;
+                Some(lhs.concat(rhs))
+            },
+            BinaryOp::Multiply(_) => {
+                if let (Some(s), Some(times)) = (lhs.downcast_refcell::<StringObject>(), IntObject::extract(rhs)) {
+                    return Some(Ok(s.repeat(times)));
+                }
+                let s = rhs.downcast_refcell::<StringObject>()?;
+                Some(Ok(s.repeat(IntObject::extract(lhs)?)))
+            },
+            _ => None
+        }
     }
 }
@@ -1,5 +1,6 @@
 use std::fmt::{Debug, Formatter};
 
+#[derive(Clone, Copy, Eq, PartialEq)]
 pub enum CmpOP{
     LT,
     LE,
@@ -20,4 +21,30 @@ impl Debug for CmpOP {
             CmpOP::GE => write!(f, ">="),
         }
     }
+}
+
+impl CmpOP {
+    /// the operator with swapped operands, e.g. `a < b` is `b > a`
+    pub fn reflected(&self) -> CmpOP {
+        match self {
+            CmpOP::LT => CmpOP::GT,
+            CmpOP::LE => CmpOP::GE,
+            CmpOP::EQ => CmpOP::EQ,
+            CmpOP::NEQ => CmpOP::NEQ,
+            CmpOP::GT => CmpOP::LT,
+            CmpOP::GE => CmpOP::LE,
+        }
+    }
+
+    /// whether an ordering satisfies the operator
+    pub fn matches(&self, ordering: std::cmp::Ordering) -> bool {
+        match self {
+            CmpOP::LT => ordering.is_lt(),
+            CmpOP::LE => ordering.is_le(),
+            CmpOP::EQ => ordering.is_eq(),
+            CmpOP::NEQ => ordering.is_ne(),
+            CmpOP::GT => ordering.is_gt(),
+            CmpOP::GE => ordering.is_ge(),
+        }
+    }
 }
\ No newline at end of file
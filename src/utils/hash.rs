@@ -0,0 +1,100 @@
+// hash functions compatible with CPython(Python/pyhash.c, Objects/longobject.c) when
+// hash randomization is disabled, i.e. PYTHONHASHSEED=0 which leaves the SipHash key zeroed
+
+const MODULUS_BITS: u32 = 61;
+/// the prime modulus of numeric hashes, refer to CPython(_PyHASH_MODULUS)
+pub const MODULUS: i64 = (1 << MODULUS_BITS) - 1;
+
+/// -1 is reserved for errors in CPython, so it is never a valid hash
+fn fix_hash(hash: i64) -> i64 {
+    if hash == -1 { -2 } else { hash }
+}
+
+pub fn hash_int(value: i64) -> i64 {
+    let hash = (value.unsigned_abs() % MODULUS as u64) as i64;
+    fix_hash(if value < 0 { -hash } else { hash })
+}
+
+/// the default identity based hash, refer to CPython(_Py_HashPointer)
+pub fn hash_pointer(address: usize) -> i64 {
+    fix_hash(address.rotate_right(4) as i64)
+}
+
+pub fn hash_bytes(data: &[u8]) -> i64 {
+    if data.is_empty() {
+        return 0;
+    }
+    fix_hash(siphash13(0, 0, data) as i64)
+}
+
+/// strings are hashed over their PEP 393 representation, so the width of the widest code point matters
+pub fn hash_str(s: &str) -> i64 {
+    let max = s.chars().map(|c| c as u32).max().unwrap_or(0);
+    let data: Vec<u8> = if max < 0x100 {
+        s.chars().map(|c| c as u8).collect()
+    } else if max < 0x10000 {
+        s.chars().flat_map(|c| (c as u16).to_le_bytes()).collect()
+    } else {
+        s.chars().flat_map(|c| (c as u32).to_le_bytes()).collect()
+    };
+    hash_bytes(&data)
+}
+
+const XXPRIME_1: u64 = 11400714785074694791;
+const XXPRIME_2: u64 = 14029467366897019727;
+const XXPRIME_5: u64 = 2870177450012600261;
+
+/// combine the hashes of the items of a tuple, a variant of xxHash, refer to CPython(tuplehash)
+pub fn hash_tuple(lanes: &[i64]) -> i64 {
+    let mut acc = XXPRIME_5;
+    for lane in lanes {
+        acc = acc.wrapping_add((*lane as u64).wrapping_mul(XXPRIME_2));
+        acc = acc.rotate_left(31);
+        acc = acc.wrapping_mul(XXPRIME_1);
+    }
+    // the length is mangled to keep the historical value of hash(())
+    acc = acc.wrapping_add(lanes.len() as u64 ^ (XXPRIME_5 ^ 3527539));
+    if acc as i64 == -1 {
+        return 1546275796;
+    }
+    acc as i64
+}
+
+macro_rules! sip_round {
+    ($v0:ident, $v1:ident, $v2:ident, $v3:ident) => {
+        $v0 = $v0.wrapping_add($v1); $v1 = $v1.rotate_left(13); $v1 ^= $v0; $v0 = $v0.rotate_left(32);
+        $v2 = $v2.wrapping_add($v3); $v3 = $v3.rotate_left(16); $v3 ^= $v2;
+        $v0 = $v0.wrapping_add($v3); $v3 = $v3.rotate_left(21); $v3 ^= $v0;
+        $v2 = $v2.wrapping_add($v1); $v1 = $v1.rotate_left(17); $v1 ^= $v2; $v2 = $v2.rotate_left(32);
+    };
+}
+
+/// SipHash-1-3, refer to CPython(Python/pyhash.c:siphash13)
+fn siphash13(k0: u64, k1: u64, data: &[u8]) -> u64 {
+    let mut v0 = k0 ^ 0x736f6d6570736575;
+    let mut v1 = k1 ^ 0x646f72616e646f6d;
+    let mut v2 = k0 ^ 0x6c7967656e657261;
+    let mut v3 = k1 ^ 0x7465646279746573;
+
+    let mut chunks = data.chunks_exact(8);
+    for chunk in &mut chunks {
+        let m = u64::from_le_bytes(chunk.try_into().unwrap());
+        v3 ^= m;
+        sip_round!(v0, v1, v2, v3);
+        v0 ^= m;
+    }
+
+    let mut b = (data.len() as u64) << 56;
+    for (i, byte) in chunks.remainder().iter().enumerate() {
+        b |= (*byte as u64) << (8 * i);
+    }
+    v3 ^= b;
+    sip_round!(v0, v1, v2, v3);
+    v0 ^= b;
+
+    v2 ^= 0xff;
+    sip_round!(v0, v1, v2, v3);
+    sip_round!(v0, v1, v2, v3);
+    sip_round!(v0, v1, v2, v3);
+    v0 ^ v1 ^ v2 ^ v3
+}
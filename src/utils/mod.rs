@@ -2,6 +2,10 @@ mod magic;
 mod bytecode;
 mod cmp_op;
 mod binary_op;
+mod unary_op;
+pub mod hash;
+pub mod sort;
+pub mod unicode;
 
 use std::cell::{Ref, RefCell, RefMut};
 use std::rc::Rc;
@@ -9,13 +13,15 @@ pub use magic::Magic;
 pub use bytecode::ByteCode;
 pub use cmp_op::CmpOP;
 pub use binary_op::BinaryOp;
+pub use unary_op::UnaryOp;
 use std::any::TypeId;
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::Deref;
 
-use crate::object::{CallableObject, CodeObject, DictObject, FalseObject, IntLongObject, IntObject, IterObject, ListObject, NoneObject, NullObject, PyObjectTrait as PyObjectTrait, SetObject, StringObject, TrueObject, TupleObject};
+use crate::object::{CallableObject, CodeObject, DictObject, DictViewObject, EllipsisObject, FalseObject, FloatObject, NotImplementedObject, IntLongObject, IntObject, IterObject, ListObject, BytesObject, SliceObject, MethodObject, ExceptionObject, NoneObject, NullObject, PyObjectTrait as PyObjectTrait, RangeObject, SetObject, StringObject, TrueObject, TupleObject, TypeObject, InstanceObject, CellObject, SuperObject, PropertyObject, StaticMethodObject, ClassMethodObject, MemberDescriptorObject, GeneratorObject, AsyncGenValueObject, AsyncGenStepObject, NativeCoroutineObject, FutureObject, QueueObject, EventObject, ModuleObject, ByteArrayObject};
 pub type PyObject = Rc<RefCell<dyn PyObjectTrait>>;
+pub type PyResult<T = PyObject> = Result<T, PyObject>;
 
 pub trait DowncastTrait {
     fn downcast_refcell<T: PyObjectTrait>(&self) -> Option<Ref<T>>;
@@ -53,6 +59,32 @@ fn is_downcastable(type_id: TypeId) -> bool {
         || type_id == TypeId::of::<TrueObject>()
         || type_id == TypeId::of::<TupleObject>()
         || type_id == TypeId::of::<IterObject>()
+        || type_id == TypeId::of::<BytesObject>()
+        || type_id == TypeId::of::<SliceObject>()
+        || type_id == TypeId::of::<MethodObject>()
+        || type_id == TypeId::of::<ExceptionObject>()
+        || type_id == TypeId::of::<DictViewObject>()
+        || type_id == TypeId::of::<FloatObject>()
+        || type_id == TypeId::of::<EllipsisObject>()
+        || type_id == TypeId::of::<NotImplementedObject>()
+        || type_id == TypeId::of::<RangeObject>()
+        || type_id == TypeId::of::<TypeObject>()
+        || type_id == TypeId::of::<InstanceObject>()
+        || type_id == TypeId::of::<CellObject>()
+        || type_id == TypeId::of::<SuperObject>()
+        || type_id == TypeId::of::<PropertyObject>()
+        || type_id == TypeId::of::<StaticMethodObject>()
+        || type_id == TypeId::of::<ClassMethodObject>()
+        || type_id == TypeId::of::<MemberDescriptorObject>()
+        || type_id == TypeId::of::<GeneratorObject>()
+        || type_id == TypeId::of::<AsyncGenValueObject>()
+        || type_id == TypeId::of::<AsyncGenStepObject>()
+        || type_id == TypeId::of::<NativeCoroutineObject>()
+        || type_id == TypeId::of::<FutureObject>()
+        || type_id == TypeId::of::<QueueObject>()
+        || type_id == TypeId::of::<EventObject>()
+        || type_id == TypeId::of::<ModuleObject>()
+        || type_id == TypeId::of::<ByteArrayObject>()
 
 }
 
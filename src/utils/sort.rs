@@ -0,0 +1,133 @@
+// a stable timsort whose comparison may raise, refer to CPython(Objects/listsort.txt)
+// galloping is left out, the natural runs and the merge pattern are the same as CPython's
+use crate::utils::PyResult;
+
+/// runs shorter than this are extended by binary insertion sort
+const MIN_MERGE: usize = 64;
+
+/// sort `values` by the strict order `lt`, the first error raised by `lt` is returned
+/// and leaves `values` in some permutation of the input
+pub fn timsort<T: Clone, F: FnMut(&T, &T) -> PyResult<bool>>(values: &mut [T], mut lt: F) -> PyResult<()> {
+    let n = values.len();
+    if n < 2 {
+        return Ok(());
+    }
+    let min_run = compute_min_run(n);
+    // the pending runs as (start, length)
+    let mut runs: Vec<(usize, usize)> = vec![];
+    let mut start = 0;
+    while start < n {
+        let mut run_len = count_run(&mut values[start..], &mut lt)?;
+        if run_len < min_run {
+            let forced = min_run.min(n - start);
+            binary_insertion_sort(&mut values[start..start + forced], run_len, &mut lt)?;
+            run_len = forced;
+        }
+        runs.push((start, run_len));
+        merge_collapse(values, &mut runs, &mut lt)?;
+        start += run_len;
+    }
+    while runs.len() > 1 {
+        let i = runs.len() - 2;
+        merge_at(values, &mut runs, i, &mut lt)?;
+    }
+    Ok(())
+}
+
+/// refer to CPython(merge_compute_minrun)
+fn compute_min_run(mut n: usize) -> usize {
+    let mut r = 0;
+    while n >= MIN_MERGE {
+        r |= n & 1;
+        n >>= 1;
+    }
+    n + r
+}
+
+/// the length of the run at the start of `values`, a strictly descending run is reversed in place
+fn count_run<T, F: FnMut(&T, &T) -> PyResult<bool>>(values: &mut [T], lt: &mut F) -> PyResult<usize> {
+    let n = values.len();
+    if n == 1 {
+        return Ok(1);
+    }
+    let mut len = 2;
+    if lt(&values[1], &values[0])? {
+        // only strictly descending runs are reversed, so equal items keep their order
+        while len < n && lt(&values[len], &values[len - 1])? {
+            len += 1;
+        }
+        values[..len].reverse();
+    } else {
+        while len < n && !lt(&values[len], &values[len - 1])? {
+            len += 1;
+        }
+    }
+    Ok(len)
+}
+
+/// `values[..sorted]` is already sorted
+fn binary_insertion_sort<T, F: FnMut(&T, &T) -> PyResult<bool>>(values: &mut [T], sorted: usize, lt: &mut F) -> PyResult<()> {
+    for i in sorted.max(1)..values.len() {
+        let (mut lo, mut hi) = (0, i);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            // equal items go to the right of the existing ones
+            if lt(&values[i], &values[mid])? {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        values[lo..=i].rotate_right(1);
+    }
+    Ok(())
+}
+
+/// keep the run lengths growing like the Fibonacci numbers, refer to CPython(merge_collapse)
+fn merge_collapse<T: Clone, F: FnMut(&T, &T) -> PyResult<bool>>(values: &mut [T], runs: &mut Vec<(usize, usize)>, lt: &mut F) -> PyResult<()> {
+    while runs.len() > 1 {
+        let mut n = runs.len() - 2;
+        if (n > 0 && runs[n - 1].1 <= runs[n].1 + runs[n + 1].1) || (n > 1 && runs[n - 2].1 <= runs[n - 1].1 + runs[n].1) {
+            if runs[n - 1].1 < runs[n + 1].1 {
+                n -= 1;
+            }
+        } else if runs[n].1 > runs[n + 1].1 {
+            break;
+        }
+        merge_at(values, runs, n, lt)?;
+    }
+    Ok(())
+}
+
+/// merge the adjacent runs `i` and `i + 1`
+fn merge_at<T: Clone, F: FnMut(&T, &T) -> PyResult<bool>>(values: &mut [T], runs: &mut Vec<(usize, usize)>, i: usize, lt: &mut F) -> PyResult<()> {
+    let (start, left_len) = runs[i];
+    let end = start + left_len + runs[i + 1].1;
+    runs[i].1 = end - start;
+    runs.remove(i + 1);
+
+    let left = values[start..start + left_len].to_vec();
+    let (mut l, mut r, mut k) = (0, start + left_len, start);
+    let mut result = Ok(());
+    while l < left.len() && r < end {
+        // the left item wins ties, which keeps the merge stable
+        match lt(&values[r], &left[l]) {
+            Ok(true) => {
+                values[k] = values[r].clone();
+                r += 1;
+            },
+            Ok(false) => {
+                values[k] = left[l].clone();
+                l += 1;
+            },
+            Err(err) => {
+                result = Err(err);
+                break;
+            }
+        }
+        k += 1;
+    }
+    // the rest of the left run fills the gap, the rest of the right run is already in place
+    values[k..k + left.len() - l].clone_from_slice(&left[l..]);
+    result
+}
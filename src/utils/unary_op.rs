@@ -0,0 +1,58 @@
+use crate::interpreter::Interpreter;
+use crate::object::{ExceptionKind, ExceptionObject, Real};
+use crate::utils::{PyObject, PyResult};
+
+/// the unary operators apart from `not`, which is a truth test rather than an operator method
+pub enum UnaryOp {
+    Positive,
+    Negative,
+    Invert
+}
+
+impl UnaryOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            UnaryOp::Positive => "+",
+            UnaryOp::Negative => "-",
+            UnaryOp::Invert => "~",
+        }
+    }
+
+    fn method_name(&self) -> &'static str {
+        match self {
+            UnaryOp::Positive => "__pos__",
+            UnaryOp::Negative => "__neg__",
+            UnaryOp::Invert => "__invert__",
+        }
+    }
+
+    /// numbers are handled directly, other objects through their operator method, refer to CPython(PyNumber_Negative)
+    pub fn handle(&self, operand: PyObject) -> PyResult {
+        if let Some(value) = Real::extract(&operand) {
+            // a bool operand gives an int, `-True == -1`
+            let res = match (self, value) {
+                (UnaryOp::Positive, value) => Some(value),
+                (UnaryOp::Negative, Real::Int(value)) => value.checked_neg().map(Real::Int),
+                (UnaryOp::Negative, Real::Float(value)) => Some(Real::Float(-value)),
+                (UnaryOp::Invert, Real::Int(value)) => Some(Real::Int(!value)),
+                (UnaryOp::Invert, Real::Float(_)) => return self.unsupported(&operand),
+            };
+            return match res {
+                Some(res) => Ok(res.into_object()),
+                None => ExceptionObject::raise(ExceptionKind::OverflowError, "int too large to represent in 64 bits")
+            };
+        }
+        let method = operand.borrow().get_method(self.method_name());
+        match method {
+            Some(method) => Interpreter::call(&method, vec![operand]),
+            None => self.unsupported(&operand)
+        }
+    }
+
+    fn unsupported(&self, operand: &PyObject) -> PyResult {
+        ExceptionObject::raise(
+            ExceptionKind::TypeError,
+            &format!("bad operand type for unary {}: '{}'", self.symbol(), operand.borrow().type_name())
+        )
+    }
+}
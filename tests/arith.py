@@ -0,0 +1,103 @@
+t = True
+f = False
+assert t + t == 2
+assert t + t is not True
+assert t * 3 == 3
+assert 5 - t == 4
+assert f * 10 == 0
+assert repr(t + f) == "1"
+assert (t & f) is False
+assert (t | f) is True
+assert (t ^ t) is False
+assert repr(t & 1) == "1"
+assert repr(t << 3) == "8"
+
+x = 7
+y = 2
+assert x // y == 3
+assert x % y == 1
+assert -x // y == -4
+assert -x % y == 1
+assert x // -y == -4
+assert x % -y == -1
+assert -x // -y == 3
+assert x / y == 3.5
+assert repr(x / 7) == "1.0"
+assert x ** y == 49
+assert y ** -1 == 0.5
+assert x << y == 28
+assert x >> 1 == 3
+assert -x >> 1 == -4
+assert x >> 100 == 0
+assert x & 3 == 3
+assert x | 8 == 15
+assert x ^ 5 == 2
+m = 1 << 20
+big = m << 20
+assert big == m * m
+assert big // m == m
+assert big * 1024 == m * m * 1024
+assert big * 1024 > big
+assert repr(big) == "1099511627776"
+
+a = 2.5
+assert a + 1 == 3.5
+assert 1 + a == 3.5
+assert a * 2 == 5.0
+assert a - t == 1.5
+assert a / 2 == 1.25
+assert a // 1 == 2.0
+assert repr(a // 1) == "2.0"
+assert a % 1 == 0.5
+assert -a % 1 == 0.5
+assert -a // 1 == -3.0
+assert a % -1 == -0.5
+assert a ** 2 == 6.25
+assert 2 ** 0.5 > 1.41
+
+assert -x == -7
+assert +x == 7
+assert ~x == -8
+assert ~-x == 6
+assert -t == -1
+assert +t == 1
+assert repr(+t) == "1"
+assert ~t == -2
+assert ~f == -1
+assert -f == 0
+assert -a == -2.5
+assert +a == 2.5
+assert repr(-0.0) == "-0.0"
+z = 0.0
+assert repr(-z) == "-0.0"
+assert not -f
+assert -(-x) == x
+
+l = [1, 2, 3]
+assert l[t] == 2
+assert [0] * t == [0]
+assert "ab" * t == "ab"
+assert (1, 2)[f] == 1
+
+try:
+    x // 0
+    assert False
+except ZeroDivisionError as e:
+    assert str(e) == "integer division or modulo by zero"
+try:
+    x % 0
+    assert False
+except ZeroDivisionError as e:
+    assert str(e) == "integer modulo by zero"
+
+# ints are 64-bit here, a result outside of that range raises OverflowError where CPython
+# would promote it to an arbitrary-precision int
+half = m << 42
+limit = half - 1 + half
+for overflow in [lambda: y ** 63, lambda: limit + 1, lambda: -(-limit - 1), lambda: limit * 2, lambda: y << 62]:
+    try:
+        overflow()
+        assert False
+    except OverflowError as e:
+        assert str(e) == "int too large to represent in 64 bits"
+assert -limit - 1 == -half * 2
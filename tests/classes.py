@@ -0,0 +1,213 @@
+class Point:
+    dims = 2
+
+    def __init__(self, x, y=0):
+        self.x = x
+        self.y = y
+
+    def norm1(self):
+        return abs_(self.x) + abs_(self.y)
+
+    def moved(self, dx, dy):
+        return Point(self.x + dx, self.y + dy)
+
+    def __repr__(self):
+        return "Point(" + repr(self.x) + ", " + repr(self.y) + ")"
+
+    def __eq__(self, other):
+        if not isinstance(other, Point):
+            return NotImplemented
+        return self.x == other.x and self.y == other.y
+
+
+def abs_(n):
+    return -n if n < 0 else n
+
+
+p = Point(1, -2)
+assert p.x == 1 and p.y == -2
+assert p.norm1() == 3
+assert Point(5).y == 0
+assert Point(y=3, x=1).y == 3
+assert p.dims == 2 and Point.dims == 2
+assert repr(p) == "Point(1, -2)"
+assert str(p) == "Point(1, -2)"
+assert p.moved(1, 1) == Point(2, -1)
+assert p != Point(0, 0)
+assert p != 1
+assert isinstance(p, Point)
+assert isinstance(p, object)
+assert isinstance(1, object)
+assert not isinstance(1, Point)
+assert isinstance(p, (Point, object))
+
+m = p.norm1
+assert m() == 3
+assert Point.norm1(p) == 3
+
+p.z = 7
+assert p.z == 7
+assert p.__dict__ == {"x": 1, "y": -2, "z": 7}
+del p.z
+assert "z" not in p.__dict__
+assert p.__class__ is Point
+
+Point.dims = 3
+assert p.dims == 3
+p.dims = 4
+assert p.dims == 4 and Point.dims == 3
+del p.dims
+assert p.dims == 3
+Point.extra = lambda self: self.x * 10
+assert p.extra() == 10
+del Point.extra
+
+assert Point.__name__ == "Point"
+assert Point.__qualname__ == "Point"
+assert Point.__module__ == "__main__"
+assert repr(Point) == "<class '__main__.Point'>"
+assert repr(object) == "<class 'object'>"
+
+
+class Empty:
+    pass
+
+
+e = Empty()
+assert repr(e).startswith("<__main__.Empty object at 0x")
+assert bool(e)
+assert hash(e) == hash(e)
+assert e == e and e != Empty()
+e2 = object.__new__(Empty)
+assert isinstance(e2, Empty)
+assert {e: 1}[e] == 1
+
+
+class Base:
+    kind = "base"
+
+    def hello(self):
+        return "hello from " + self.name()
+
+    def name(self):
+        return "base"
+
+
+class Derived(Base):
+    def name(self):
+        return "derived"
+
+
+class Again(Derived, Base):
+    pass
+
+
+d = Derived()
+assert d.hello() == "hello from derived"
+assert d.kind == "base"
+assert isinstance(d, Base)
+assert issubclass(Derived, Base)
+assert issubclass(Again, Base)
+assert not issubclass(Base, Derived)
+assert issubclass(Base, (Derived, object))
+assert Again().hello() == "hello from derived"
+
+
+class Counter:
+    def __init__(self):
+        self.count = 0
+
+    def __call__(self, step=1):
+        self.count += step
+        return self.count
+
+
+c = Counter()
+c()
+c(5)
+assert c.count == 6
+
+
+class Box:
+    def __init__(self, items):
+        self.items = list(items)
+
+    def __len__(self):
+        return len(self.items)
+
+    def __getitem__(self, i):
+        return self.items[i]
+
+    def __setitem__(self, i, v):
+        self.items[i] = v
+
+    def __delitem__(self, i):
+        del self.items[i]
+
+    def __hash__(self):
+        return 42
+
+
+b = Box([1, 2, 3])
+assert len(b) == 3
+assert b[0] == 1
+b[0] = 9
+assert b.items == [9, 2, 3]
+del b[1]
+assert b.items == [9, 3]
+assert 3 in b and 2 not in b
+assert hash(b) == 42
+assert bool(b)
+assert not Box([])
+
+
+class Flag:
+    def __init__(self, value):
+        self.value = value
+
+    def __bool__(self):
+        return self.value
+
+
+assert Flag(True)
+assert not Flag(False)
+x = 1 if Flag(False) else 2
+assert x == 2
+
+
+class Ordered:
+    def __init__(self, v):
+        self.v = v
+
+    def __lt__(self, other):
+        return self.v < other.v
+
+    def __str__(self):
+        return "O" + str(self.v)
+
+
+assert Ordered(1) < Ordered(2)
+assert Ordered(3) > Ordered(2)
+assert str(Ordered(4)) == "O4"
+assert repr(Ordered(4)).startswith("<__main__.Ordered object")
+
+
+def make():
+    class Local:
+        def get(self):
+            return "local"
+    return Local
+
+
+L = make()
+assert L().get() == "local"
+assert L.__qualname__ == "make.<locals>.Local"
+
+
+class MinusOne:
+    def __hash__(self):
+        return -1
+
+
+assert hash(MinusOne()) == -2
+assert hash(-1) == -2
@@ -0,0 +1,94 @@
+assert "a" < "b"
+assert "abc" < "abd"
+assert "ab" < "abc"
+assert not "b" <= "a"
+assert "Z" < "a"
+assert "é" > "z"
+assert b"a" < b"b"
+x = None
+assert x == None
+assert not (x != None)
+assert not (x == 0)
+assert 1 != "1"
+assert [1] != (1,)
+
+assert 1.5 > 1
+assert 1 < 1.5
+assert 2 == 2.0
+assert 2.0 == 2
+assert -0.0 == 0
+assert 1.0 <= 1
+assert 3 >= 2.5
+nan = float("nan")
+assert not (nan == nan)
+assert nan != nan
+assert not (nan < 1)
+assert not (nan >= 1)
+inf = float("inf")
+assert inf > 2147483647
+assert float("-inf") < -2147483648
+
+assert True == 1
+assert False == 0
+assert True > False
+assert True < 2
+assert True == 1.0
+assert 0.5 > False
+assert 1 == True
+
+assert (1, "a") < (1, "b")
+assert [1, 2.5] < [1, 3]
+assert (1, 2) == (1.0, 2.0)
+assert [True] == [1]
+assert {1: "a"} == {1.0: "a"}
+assert {1, 2} == {2.0, True}
+assert hash(1.0) == hash(1)
+assert hash(True) == hash(1)
+assert hash(float("inf")) == 314159
+d = {1: "int"}
+d[1.0] = "float"
+assert d == {1: "float"}
+
+assert repr(1.0) == "1.0"
+assert repr(0.1) == "0.1"
+assert repr(-2.5) == "-2.5"
+assert repr(1e16) == "1e+16"
+assert repr(1.5e-7) == "1.5e-07"
+assert repr(123456789.125) == "123456789.125"
+assert repr(0.0001) == "0.0001"
+assert repr(1e22) == "1e+22"
+assert repr(float("-inf")) == "-inf"
+assert repr(float("nan")) == "nan"
+assert repr(float(" 2.5 ")) == "2.5"
+assert repr(float(3)) == "3.0"
+assert repr([1.25, -0.0]) == "[1.25, -0.0]"
+assert str(2.0) == "2.0"
+
+n = [3, 1.5, True, 2]
+n.sort()
+assert n == [True, 1.5, 2, 3]
+
+
+class Loose:
+    def __eq__(self, other):
+        return "loose"
+
+    def __lt__(self, other):
+        return [1]
+
+
+class Strict(Loose):
+    def __eq__(self, other):
+        return "strict " + type(other).__name__
+
+    def __gt__(self, other):
+        return NotImplemented
+
+
+assert (Loose() == 1) == "loose"
+assert (1 == Loose()) == "loose"
+assert (Loose() != 1) is False
+assert (Loose() < 1) == [1]
+assert (Loose() == Strict()) == "strict Loose"
+assert (Strict() == Loose()) == "strict Loose"
+assert (Loose() < Strict()) == [1]
@@ -0,0 +1,90 @@
+s = "hello world"
+assert "lo w" in s
+assert "" in s
+assert "xyz" not in s
+
+b = b"\x00abc"
+assert 97 in b
+assert 0 in b
+assert 100 not in b
+assert b"bc" in b
+assert b"" in b
+assert b"ca" not in b
+
+l = [1, "two", (3, 4), None]
+assert "two" in l
+assert (3, 4) in l
+assert None in l
+assert 2 not in l
+assert True in l
+assert 1.0 in l
+
+t = (1, 2.5, "x")
+assert 2.5 in t
+assert "y" not in t
+
+d = {"a": 1, (1, 2): 2}
+assert "a" in d
+assert (1, 2) in d
+assert 1 not in d
+assert 1 in d.values()
+assert ("a", 1) in d.items()
+assert ("a", 2) not in d.items()
+assert "a" in d.keys()
+
+st = {1, 2, 3}
+assert 2 in st
+assert 2.0 in st
+assert 4 not in st
+assert 1 in frozenset(st)
+
+r = range(0, 20, 3)
+assert 9 in r
+assert 10 not in r
+assert 21 not in r
+assert 0 in r
+assert 20 not in r
+assert 3.0 in r
+assert 3.5 not in r
+assert True not in range(2, 5)
+assert "3" not in r
+big = range(2000000000)
+assert 1999999999 in big
+assert 2000000000 not in big
+n = range(10, 0, -2)
+assert 10 in n
+assert 2 in n
+assert 0 not in n
+assert 5 not in n
+assert 7 not in range(7, 7)
+
+assert len(r) == 7
+assert len(range(10, 0, -3)) == 4
+assert len(range(5, 1)) == 0
+assert r[2] == 6
+assert r[-1] == 18
+assert list(r) == [0, 3, 6, 9, 12, 15, 18]
+assert list(n) == [10, 8, 6, 4, 2]
+assert repr(range(3)) == "range(0, 3)"
+assert repr(range(1, 9, 2)) == "range(1, 9, 2)"
+assert repr(range(10)[::-1]) == "range(9, -1, -1)"
+assert repr(range(10)[5:2]) == "range(5, 2)"
+assert repr(r[1:5]) == "range(3, 15, 3)"
+assert list(range(10)[::-3]) == [9, 6, 3, 0]
+assert r.start == 0
+assert r.stop == 20
+assert r.step == 3
+assert r.index(12) == 4
+assert r.count(12) == 1
+assert r.count(13) == 0
+assert range(0) == range(5, 2)
+assert range(0, 3, 2) == range(0, 4, 2)
+assert range(1, 2, 5) == range(1, 3, 9)
+assert range(3) != range(4)
+assert hash(range(0, 3, 2)) == hash(range(0, 4, 2))
+assert range(1)
+assert not range(0)
+
+x = 5
+assert (x in [4, 5]) is True
+assert (x not in [4, 5]) is False
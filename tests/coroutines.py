@@ -0,0 +1,255 @@
+import asyncio
+
+
+class Ticket:
+    def __init__(self, n):
+        self.n = n
+
+    def __await__(self):
+        got = yield ("ticket", self.n)
+        return got * 2
+
+
+async def inner(n):
+    v = await Ticket(n)
+    return v + 1
+
+
+async def outer():
+    a = await inner(1)
+    b = await inner(2)
+    return [a, b]
+
+
+def drive(coro):
+    value = None
+    try:
+        while True:
+            req = coro.send(value)
+            value = req[1] * 10
+    except StopIteration as e:
+        return e.value
+
+
+assert drive(outer()) == [21, 41]
+
+
+async def agen(n):
+    for i in range(n):
+        got = await Ticket(i)
+        yield got
+
+
+async def consume():
+    out = []
+    async for v in agen(3):
+        out.append(v)
+    return out
+
+
+assert drive(consume()) == [0, 20, 40]
+
+
+class Manager:
+    def __init__(self):
+        self.log = []
+
+    async def __aenter__(self):
+        self.log.append("enter")
+        await Ticket(7)
+        return self
+
+    async def __aexit__(self, exc_type, exc, tb):
+        self.log.append(("exit", exc_type))
+
+
+async def use(manager):
+    async with manager as m:
+        m.log.append("inside")
+    return "used"
+
+
+manager = Manager()
+assert drive(use(manager)) == "used"
+assert manager.log == ["enter", "inside", ("exit", None)]
+
+c = outer()
+assert type(c).__name__ == "coroutine"
+assert c.__name__ == "outer"
+assert not c.cr_running and c.cr_await is None
+c.close()
+try:
+    c.send(None)
+    assert False
+except RuntimeError as e:
+    assert str(e) == "cannot reuse already awaited coroutine"
+
+
+async def bad():
+    await 3
+
+
+try:
+    drive(bad())
+    assert False
+except TypeError as e:
+    assert str(e) == "object int can't be used in 'await' expression"
+
+
+async def counter(n):
+    for i in range(n):
+        yield i
+
+
+g = counter(2)
+assert type(g).__name__ == "async_generator"
+assert drive(g.__anext__()) == 0
+assert drive(g.__anext__()) == 1
+try:
+    drive(g.__anext__())
+    assert False
+except StopAsyncIteration:
+    pass
+
+
+log = []
+
+
+async def worker(name, delay):
+    log.append(("start", name))
+    await asyncio.sleep(delay)
+    log.append(("end", name))
+    return name.upper()
+
+
+async def main():
+    results = await asyncio.gather(worker("a", 0.2), worker("b", 0.1), worker("c", 0))
+    assert results == ["A", "B", "C"]
+    task = asyncio.create_task(worker("d", 0.05), name="worker-d")
+    assert task.get_name() == "worker-d"
+    assert not task.done()
+    assert await task == "D"
+    assert task.done() and task.result() == "D"
+    return "main done"
+
+
+assert asyncio.run(main()) == "main done"
+assert log == [
+    ("start", "a"), ("start", "b"), ("start", "c"),
+    ("end", "c"), ("end", "b"), ("end", "a"),
+    ("start", "d"), ("end", "d"),
+]
+
+
+async def producer(queue, n):
+    for i in range(n):
+        await queue.put(i)
+        log.append(("put", i))
+    await queue.put(None)
+
+
+async def consumer(queue):
+    total = 0
+    while True:
+        item = await queue.get()
+        queue.task_done()
+        if item is None:
+            return total
+        log.append(("got", item))
+        total += item
+
+
+async def pipeline():
+    queue = asyncio.Queue(maxsize=2)
+    assert queue.empty() and queue.maxsize == 2
+    prod = asyncio.create_task(producer(queue, 4))
+    total = await consumer(queue)
+    await prod
+    await queue.join()
+    return total
+
+
+log = []
+assert asyncio.run(pipeline()) == 6
+assert [entry for entry in log if entry[0] == "got"] == [("got", 0), ("got", 1), ("got", 2), ("got", 3)]
+
+
+async def waiter(event, name):
+    await event.wait()
+    log.append(name)
+
+
+async def signals():
+    event = asyncio.Event()
+    tasks = [asyncio.create_task(waiter(event, name)) for name in ("x", "y")]
+    await asyncio.sleep(1)
+    assert log == [] and not event.is_set()
+    event.set()
+    await asyncio.gather(tasks[0], tasks[1])
+    assert event.is_set()
+
+
+log = []
+asyncio.run(signals())
+assert log == ["x", "y"]
+
+
+async def slow():
+    try:
+        await asyncio.sleep(10)
+    except asyncio.CancelledError:
+        log.append("slow cancelled")
+        raise
+    return "too late"
+
+
+async def timeouts():
+    try:
+        await asyncio.wait_for(slow(), timeout=1)
+        assert False
+    except asyncio.TimeoutError:
+        log.append("timed out")
+    assert await asyncio.wait_for(worker("quick", 0.5), 1) == "QUICK"
+    task = asyncio.create_task(slow())
+    await asyncio.sleep(0)
+    task.cancel()
+    try:
+        await task
+    except asyncio.CancelledError:
+        log.append("task cancelled")
+    assert task.cancelled()
+
+
+log = []
+asyncio.run(timeouts())
+assert log == ["slow cancelled", "timed out", ("start", "quick"), ("end", "quick"), "slow cancelled", "task cancelled"]
+
+
+async def failing():
+    await asyncio.sleep(0)
+    raise ValueError("boom")
+
+
+async def collect():
+    results = await asyncio.gather(failing(), worker("e", 0), return_exceptions=True)
+    assert str(results[0]) == "boom" and results[1] == "E"
+    try:
+        await asyncio.gather(failing())
+        assert False
+    except ValueError as e:
+        assert str(e) == "boom"
+
+
+asyncio.run(collect())
+
+try:
+    asyncio.run(worker)
+    assert False
+except ValueError as e:
+    assert str(e).startswith("a coroutine was expected, got <function worker")
+
+try:
+    from asyncio import no_such_name
+    assert False
+except ImportError as e:
+    assert str(e).startswith("cannot import name 'no_such_name' from 'asyncio'")
@@ -0,0 +1,178 @@
+class Celsius:
+    def __init__(self, degrees=0):
+        self._degrees = degrees
+
+    @property
+    def degrees(self):
+        return self._degrees
+
+    @degrees.setter
+    def degrees(self, value):
+        self._degrees = value
+
+    @degrees.deleter
+    def degrees(self):
+        self._degrees = None
+
+    @property
+    def fahrenheit(self):
+        return self._degrees * 9 // 5 + 32
+
+    def get_kelvin(self):
+        return self._degrees + 273
+
+    kelvin = property(get_kelvin, doc="in kelvin")
+
+
+c = Celsius(100)
+assert c.degrees == 100
+assert c.fahrenheit == 212
+c.degrees = 0
+assert c.fahrenheit == 32
+assert c.kelvin == 273
+del c.degrees
+assert c._degrees is None
+assert Celsius.kelvin.__doc__ == "in kelvin"
+assert Celsius.kelvin.fget is Celsius.get_kelvin
+assert Celsius.fahrenheit.fset is None
+c.__dict__["degrees"] = "shadow"
+c.degrees = 5
+assert c.degrees == 5
+
+
+class Math:
+    factor = 3
+
+    @staticmethod
+    def double(x):
+        return x * 2
+
+    @classmethod
+    def scaled(cls, x):
+        return cls.factor * x
+
+    @classmethod
+    def make(cls):
+        return cls()
+
+
+class MoreMath(Math):
+    factor = 10
+
+
+assert Math.double(4) == 8
+assert Math().double(5) == 10
+assert Math.scaled(2) == 6
+assert MoreMath.scaled(2) == 20
+assert MoreMath().scaled(1) == 10
+assert isinstance(MoreMath.make(), MoreMath)
+assert Math.__dict__["double"].__func__(1) == 2
+assert Math.__dict__["double"](3) == 6
+
+
+class Typed:
+    def __init__(self, kind):
+        self.kind = kind
+
+    def __set_name__(self, owner, name):
+        self.owner = owner
+        self.name = name
+
+    def __get__(self, obj, objtype=None):
+        if obj is None:
+            return self
+        return obj.__dict__.get(self.name, "unset")
+
+    def __set__(self, obj, value):
+        if isinstance(value, self.kind):
+            obj.__dict__[self.name] = value
+        else:
+            obj.__dict__[self.name] = "rejected"
+
+    def __delete__(self, obj):
+        obj.__dict__[self.name] = "deleted"
+
+
+class Record:
+    title = Typed(object)
+    tag = Typed(Celsius)
+
+
+r = Record()
+assert Record.title.name == "title" and Record.title.owner is Record
+assert Record.__dict__["tag"].name == "tag"
+assert r.title == "unset"
+r.title = 5
+assert r.title == 5
+r.__dict__["title"] = 7
+assert r.title == 7
+r.tag = 1
+assert r.tag == "rejected"
+r.tag = c
+assert r.tag is c
+del r.title
+assert r.title == "deleted"
+
+
+class Constant:
+    def __get__(self, obj, objtype=None):
+        return 42
+
+
+class HasConstant:
+    answer = Constant()
+
+
+h = HasConstant()
+assert h.answer == 42
+assert HasConstant.answer == 42
+h.answer = 1
+assert h.answer == 1
+
+
+class Dynamic:
+    existing = 1
+
+    def __getattr__(self, name):
+        return "dynamic " + name
+
+
+d = Dynamic()
+assert d.existing == 1
+assert d.anything == "dynamic anything"
+
+
+class Logged:
+    def __init__(self):
+        self.log = []
+
+    def __getattribute__(self, name):
+        if name != "log":
+            object.__getattribute__(self, "log").append(name)
+        return object.__getattribute__(self, name)
+
+    def __setattr__(self, name, value):
+        if name != "log":
+            self.log.append("set " + name)
+        object.__setattr__(self, name, value)
+
+    def __delattr__(self, name):
+        self.log.append("del " + name)
+        super().__delattr__(name)
+
+
+lg = Logged()
+lg.x = 1
+assert lg.x == 1
+del lg.x
+assert lg.log == ["set x", "x", "del x"]
+
+
+class Frozen:
+    def __setattr__(self, name, value):
+        pass
+
+
+f = Frozen()
+f.x = 1
+assert "x" not in f.__dict__
@@ -0,0 +1,148 @@
+d = {"a": 1, "b": 2}
+assert d["a"] == 1
+assert len(d) == 2
+d["c"] = 3
+assert d["c"] == 3
+d["a"] = 10
+assert repr(d) == "{'a': 10, 'b': 2, 'c': 3}"
+del d["b"]
+assert repr(d) == "{'a': 10, 'c': 3}"
+assert "a" in d
+assert "b" not in d
+assert repr({}) == "{}"
+
+a = "x"
+b = "y"
+e = {a: 1, b: 2, 3: "three", None: 0, True: "t"}
+assert e[3] == "three"
+assert e[None] == 0
+assert e[True] == "t"
+assert e["x"] == 1
+
+assert d.get("a") == 10
+assert d.get("z") is None
+assert d.get("z", 5) == 5
+assert d.setdefault("a", 0) == 10
+assert d.setdefault("n", 7) == 7
+assert d["n"] == 7
+assert d.pop("n") == 7
+assert d.pop("n", "gone") == "gone"
+assert repr(d.popitem()) == "('c', 3)"
+assert repr(d) == "{'a': 10}"
+d.update({"b": 2, "c": 3})
+d.update([("d", 4)])
+assert repr(d) == "{'a': 10, 'b': 2, 'c': 3, 'd': 4}"
+
+keys = d.keys()
+assert repr(keys) == "dict_keys(['a', 'b', 'c', 'd'])"
+d["e"] = 5
+assert len(keys) == 5
+assert "e" in keys
+assert repr(d.values()) == "dict_values([10, 2, 3, 4, 5])"
+assert repr(d.items()) == "dict_items([('a', 10), ('b', 2), ('c', 3), ('d', 4), ('e', 5)])"
+assert ("a", 10) in d.items()
+assert 5 in d.values()
+
+c = d.copy()
+c["a"] = 0
+assert d["a"] == 10
+assert c != d
+c["a"] = 10
+assert c == d
+c.clear()
+assert len(c) == 0
+
+assert repr(dict.fromkeys("ab")) == "{'a': None, 'b': None}"
+assert repr(dict.fromkeys("ab", 0)) == "{'a': 0, 'b': 0}"
+assert repr(dict([("x", 1)])) == "{'x': 1}"
+assert repr(dict()) == "{}"
+
+m = {"a": 1, "b": 2} | {"b": 3, "c": 4}
+assert repr(m) == "{'a': 1, 'b': 3, 'c': 4}"
+m |= {"d": 5}
+assert repr(m) == "{'a': 1, 'b': 3, 'c': 4, 'd': 5}"
+merged = {**m, "e": 6}
+assert repr(merged) == "{'a': 1, 'b': 3, 'c': 4, 'd': 5, 'e': 6}"
+
+squares = {k: k * 2 for k in "abc"}
+assert repr(squares) == "{'a': 'aa', 'b': 'bb', 'c': 'cc'}"
+
+big = {}
+for k in "abcdefghijklmnopqrstuvwxyz":
+    big[k] = k.upper()
+for k in "abcdefghijklm":
+    del big[k]
+assert len(big) == 13
+assert big["z"] == "Z"
+assert "a" not in big
+big["a"] = 1
+assert repr(big.popitem()) == "('a', 1)"
+
+for k in d:
+    assert k in "abcde"
+
+assert hash("") == 0
+assert hash(1) == 1
+assert hash(-1) == -2
+assert hash("abc") == hash("abc")
+assert hash(True) == 1
+
+assert str(KeyError("k")) == "'k'"
+
+
+# a key whose __eq__ uses the dict being looked up
+watched = {}
+
+
+class Watcher:
+    def __init__(self, n):
+        self.n = n
+
+    def __hash__(self):
+        return 1
+
+    def __eq__(self, other):
+        watched["seen"] = self.n
+        return isinstance(other, Watcher) and self.n == other.n
+
+
+watched[Watcher(1)] = 1
+watched[Watcher(1)] = 2
+assert len(watched) == 2
+assert watched.get(Watcher(1)) == 2
+assert watched.get(Watcher(2)) is None
+assert Watcher(1) in watched
+assert watched[Watcher(1)] == 2
+del watched[Watcher(1)]
+assert Watcher(1) not in watched
+assert watched == {"seen": 1}
+
+emptied = {}
+
+
+class Emptier:
+    def __hash__(self):
+        return 7
+
+    def __eq__(self, other):
+        emptied.clear()
+        return False
+
+
+emptied[Emptier()] = 1
+emptied[Emptier()] = 2
+assert len(emptied) == 1
+
+noted = {}
+
+
+class Noter:
+    def __repr__(self):
+        noted["seen"] = True
+        noted.pop("gone", None)
+        return "Noter"
+
+
+noted["first"] = Noter()
+noted["gone"] = 1
+assert repr(noted) == "{'first': Noter, 'seen': True}"
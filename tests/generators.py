@@ -0,0 +1,216 @@
+def count_up(limit):
+    n = 0
+    while n < limit:
+        yield n
+        n += 1
+
+
+assert list(count_up(4)) == [0, 1, 2, 3]
+sum_up = 0
+for value in count_up(3):
+    sum_up += value
+assert sum_up == 3
+
+gen = count_up(2)
+assert (next(gen), next(gen)) == (0, 1)
+assert next(gen, "done") == "done"
+assert next(gen, "still done") == "still done"
+assert iter(gen) is gen
+assert repr(type(gen)) == "<class 'generator'>"
+assert gen.__name__ == "count_up"
+
+squares = (x * x for x in range(5) if x != 2)
+assert repr(type(squares)) == "<class 'generator'>"
+assert list(squares) == [0, 1, 9, 16]
+assert list(squares) == []
+assert [y for y in (x + 1 for x in [10, 20])] == [11, 21]
+
+
+def echo():
+    received = yield "ready"
+    while received is not None:
+        received = yield "got " + received
+    return "stopped"
+
+
+talker = echo()
+assert talker.send(None) == "ready"
+assert talker.send("a") == "got a"
+assert talker.send("b") == "got b"
+assert next(talker, "end") == "end"
+
+
+def fibonacci():
+    a, b = 0, 1
+    while True:
+        yield a
+        a, b = b, a + b
+
+
+fib = fibonacci()
+assert [next(fib) for _ in range(10)] == [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]
+fib.close()
+assert next(fib, "closed") == "closed"
+
+
+def stopping():
+    yield "only"
+    return "value"
+
+
+def inner():
+    first = yield 1
+    second = yield first * 10
+    return first + second
+
+
+def outer():
+    result = yield from inner()
+    yield "inner returned " + repr(result)
+    yield from [7, 8]
+    yield from "hi"
+
+
+delegating = outer()
+assert next(delegating) == 1
+assert delegating.send(2) == 20
+assert delegating.send(3) == "inner returned 5"
+assert list(delegating) == [7, 8, "h", "i"]
+
+
+def prefix():
+    total = yield from stopping()
+    yield total
+
+
+assert list(prefix()) == ["only", "value"]
+
+
+def tree(depth):
+    if depth == 0:
+        yield "leaf"
+        return
+    yield "node " + repr(depth)
+    yield from tree(depth - 1)
+    yield from tree(depth - 1)
+
+
+assert list(tree(2)) == ["node 2", "node 1", "leaf", "leaf", "node 1", "leaf", "leaf"]
+
+
+def closing():
+    yield 1
+    yield 2
+
+
+c = closing()
+assert next(c) == 1
+c.close()
+c.close()
+assert list(c) == []
+assert next(c, "after close") == "after close"
+
+unstarted = closing()
+unstarted.close()
+assert list(unstarted) == []
+
+
+s = stopping()
+assert next(s) == "only"
+assert next(s, "default") == "default"
+
+
+def middle():
+    yield from closing()
+
+
+m = middle()
+assert (next(m), next(m)) == (1, 2)
+assert next(m, "middle done") == "middle done"
+
+
+def thrower():
+    yield "a"
+    yield "b"
+
+
+t = thrower()
+assert next(t) == "a"
+assert t.gi_suspended
+assert t.gi_yieldfrom is None
+d = middle()
+assert next(d) == 1
+assert d.gi_yieldfrom is not None
+assert not d.gi_running
+d.close()
+assert d.gi_yieldfrom is None
+assert not d.gi_suspended
+assert next(d, "closed with its delegate") == "closed with its delegate"
+
+
+def counter():
+    total = 0
+    while True:
+        step = yield total
+        if step is None:
+            break
+        total += step
+
+
+acc = counter()
+next(acc)
+assert acc.send(5) == 5
+assert acc.send(10) == 15
+assert next(acc, "finished") == "finished"
+
+
+class Tree:
+    def __init__(self):
+        self.items = [3, 1, 2]
+
+    def __iter__(self):
+        for item in self.items:
+            yield item * 2
+
+
+assert list(Tree()) == [6, 2, 4]
+assert [i for i in Tree()] == [6, 2, 4]
+
+
+def gen_with_closure(base):
+    def add(x):
+        return base + x
+    for i in range(3):
+        yield add(i)
+
+
+assert list(gen_with_closure(100)) == [100, 101, 102]
+assert repr(count_up(1)).startswith("<generator object count_up at 0x")
+
+
+def finished_with(value):
+    yield 1
+    return value
+
+
+done = finished_with(5)
+assert next(done) == 1
+try:
+    next(done)
+    assert False
+except StopIteration as e:
+    assert e.value == 5
+    assert e.args == (5,)
+done = finished_with(5)
+next(done)
+assert next(done, "default") == "default"
+done = finished_with(None)
+next(done)
+try:
+    next(done)
+    assert False
+except StopIteration as e:
+    assert e.value is None
+    assert e.args == ()
+assert list(finished_with(5)) == [1]
+assert [x for x in finished_with(5)] == [1]
@@ -0,0 +1,45 @@
+x = None
+assert x is None
+assert not (x is not None)
+assert {}.get(1) is None
+l = [1]
+assert l.sort() is None
+assert (1 == 1) is True
+assert (1 == 2) is False
+assert bool([]) is False
+assert not ([] is False)
+e = ...
+assert e is Ellipsis
+assert repr(...) == "Ellipsis"
+assert repr(NotImplemented) == "NotImplemented"
+assert NotImplemented is NotImplemented
+
+a = 100
+b = 156
+k = 256
+assert a + b is k
+assert a + b is b + a
+c = 200
+assert a + c == c + a
+assert not (a + c is c + a)
+n = -3
+m = -5
+assert n - 2 is m
+
+l2 = l
+assert l2 is l
+assert l.copy() is not l
+assert l.copy() == l
+t = (1, 2)
+assert t is t
+d = {}
+assert d is not {}
+
+assert id(l) == id(l2)
+assert id(l) != id(l.copy())
+assert id(None) == id(x)
+assert id(a + b) == id(256)
+f = frozenset()
+assert f.copy() is f
+s = "abc"
+assert s is s
@@ -0,0 +1,129 @@
+class A:
+    def who(self):
+        return ["A"]
+
+    def greet(self):
+        return "A.greet"
+
+
+class B(A):
+    def who(self):
+        return ["B"] + super().who()
+
+
+class C(A):
+    def who(self):
+        return ["C"] + super().who()
+
+    def greet(self):
+        return "C.greet"
+
+
+class D(B, C):
+    def who(self):
+        return ["D"] + super().who()
+
+
+assert D.__mro__ == (D, B, C, A, object)
+assert D.__bases__ == (B, C)
+assert A.__bases__ == (object,)
+assert object.__bases__ == ()
+assert object.__mro__ == (object,)
+assert D().who() == ["D", "B", "C", "A"]
+assert D().greet() == "C.greet"
+assert super(B, D()).who() == ["C", "A"]
+assert super(C, D()).greet() == "A.greet"
+assert super(D, D).who(D()) == ["B", "C", "A"]
+
+
+class O: pass
+class X(O): pass
+class Y(O): pass
+class Z(O): pass
+class K1(X, Y): pass
+class K2(Y, Z): pass
+class K3(X, Z): pass
+class M(K1, K2, K3): pass
+
+
+names = [cls.__name__ for cls in M.__mro__]
+assert names == ["M", "K1", "K2", "K3", "X", "Y", "Z", "O", "object"]
+
+
+class Shape:
+    def __init__(self, name):
+        self.name = name
+
+
+class Square(Shape):
+    def __init__(self, side):
+        super().__init__("square")
+        self.side = side
+
+    def area(self):
+        return self.side * self.side
+
+
+sq = Square(3)
+assert sq.name == "square" and sq.area() == 9
+
+
+class Plain:
+    def __init__(self):
+        super().__init__()
+        self.ok = True
+
+
+assert Plain().ok
+
+
+class WithClass:
+    def cls(self):
+        return __class__
+
+
+class Sub(WithClass):
+    pass
+
+
+assert Sub().cls() is WithClass
+r = repr(super(Sub, Sub()))
+assert r == "<super: <class 'Sub'>, <Sub object>>"
+
+
+def counter():
+    count = 0
+
+    def inc(step=1):
+        nonlocal count
+        count += step
+        return count
+    return inc
+
+
+inc = counter()
+inc()
+inc(4)
+assert inc() == 6
+
+
+def outer():
+    x = "outer"
+
+    class Inner:
+        y = x
+
+        def get(self):
+            return x
+    return Inner
+
+
+Inner = outer()
+assert Inner.y == "outer" and Inner().get() == "outer"
+
+
+def adders():
+    return [lambda n, i=i: n + i for i in range(3)]
+
+
+assert [f(10) for f in adders()] == [10, 11, 12]
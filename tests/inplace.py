@@ -0,0 +1,107 @@
+a = [1, 2]
+alias = a
+a += [3]
+assert alias == [1, 2, 3]
+assert a is alias
+a += (4, 5)
+assert alias == [1, 2, 3, 4, 5]
+a += "xy"
+assert alias[-2:] == ["x", "y"]
+a *= 2
+assert len(alias) == 14
+assert a is alias
+a *= 0
+assert alias == []
+
+b = a + [1]
+assert b is not a
+
+s = {1, 2}
+alias = s
+s |= {3}
+s &= {2, 3, 4}
+assert alias == {2, 3}
+s -= {2}
+assert alias == {3}
+s ^= {3, 5}
+assert alias == {5}
+assert s is alias
+
+fs = frozenset({1})
+alias = fs
+fs |= {2}
+assert fs == {1, 2}
+assert alias == {1}
+assert fs is not alias
+
+d = {"a": 1}
+alias = d
+d |= {"b": 2}
+d |= [("c", 3)]
+assert alias == {"a": 1, "b": 2, "c": 3}
+assert d is alias
+
+ba = bytearray(b"ab")
+alias = ba
+ba += b"c"
+ba += bytearray(b"d")
+assert alias == b"abcd"
+assert ba is alias
+ba *= 2
+assert alias == bytearray(b"abcdabcd")
+assert ba is alias
+ba *= 0
+assert alias == bytearray()
+try:
+    ba += [1]
+    assert False
+except TypeError as e:
+    assert str(e) == "can't concat list to bytearray"
+ba.extend([104, 105])
+ba.append(33)
+assert repr(ba) == "bytearray(b'hi!')"
+joined = ba + b"?"
+assert joined == b"hi!?"
+assert joined is not ba
+assert repr(type(joined)) == "<class 'bytearray'>"
+assert list(ba * 2) == [104, 105, 33, 104, 105, 33]
+assert ba[0] == 104
+assert ba[1:] == b"i!"
+assert b"i" in ba
+assert bytearray(3) == b"\x00\x00\x00"
+assert not bytearray()
+
+t = (1,)
+alias = t
+t += (2,)
+assert t == (1, 2)
+assert alias == (1,)
+t *= 2
+assert t == (1, 2, 1, 2)
+
+x = "ab"
+alias = x
+x += "c"
+x *= 2
+assert x == "abcabc"
+assert alias == "ab"
+
+n = 5
+n += 1
+n -= 2
+n *= 3
+n //= 5
+n **= 3
+n %= 5
+n <<= 4
+n >>= 2
+n |= 1
+n &= 7
+n ^= 2
+assert n == 7
+n /= 2
+assert n == 3.5
+f = 1.5
+f += True
+f *= 2
+assert f == 5.0
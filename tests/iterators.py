@@ -0,0 +1,134 @@
+class Countdown:
+    def __init__(self, start):
+        self.current = start
+
+    def __iter__(self):
+        return self
+
+    def __next__(self):
+        if self.current <= 0:
+            raise StopIteration
+        self.current -= 1
+        return self.current + 1
+
+
+counted = []
+for n in Countdown(3):
+    counted.append(n)
+assert counted == [3, 2, 1]
+assert list(Countdown(4)) == [4, 3, 2, 1]
+assert tuple(Countdown(2)) == (2, 1)
+assert 3 in Countdown(5)
+assert 9 not in Countdown(5)
+
+
+class Deck:
+    def __init__(self, cards):
+        self.cards = cards
+
+    def __iter__(self):
+        return DeckIterator(self.cards)
+
+
+class DeckIterator:
+    def __init__(self, cards):
+        self.cards = cards
+        self.index = 0
+
+    def __iter__(self):
+        return self
+
+    def __next__(self):
+        if self.index == len(self.cards):
+            raise StopIteration
+        card = self.cards[self.index]
+        self.index += 1
+        return card
+
+
+deck = Deck(["A", "K", "Q"])
+assert [card for card in deck] == ["A", "K", "Q"]
+assert [card for card in deck] == ["A", "K", "Q"]
+first, *rest = deck
+assert first == "A"
+assert rest == ["K", "Q"]
+assert "K" in deck
+
+
+class Squares:
+    def __getitem__(self, index):
+        if index >= 4:
+            raise IndexError
+        return index * index
+
+
+assert list(Squares()) == [0, 1, 4, 9]
+assert 9 in Squares()
+
+it = iter([1, 2, 3])
+assert (next(it), next(it), next(it)) == (1, 2, 3)
+assert next(it, "done") == "done"
+assert next(it, None) is None
+assert iter(it) is it
+assert repr(type(it)) == "<class 'list_iterator'>"
+assert repr(type(iter((1,)))) == "<class 'tuple_iterator'>"
+assert repr(type(iter("abc"))) == "<class 'str_ascii_iterator'>"
+assert repr(type(iter({}))) == "<class 'dict_keyiterator'>"
+assert repr(type(iter(range(3)))) == "<class 'range_iterator'>"
+
+words = iter("héllo")
+assert next(words) == "h"
+assert next(words) == "é"
+assert list(words) == ["l", "l", "o"]
+
+state = [0]
+
+
+def tick():
+    state[0] += 1
+    return state[0]
+
+
+assert list(iter(tick, 4)) == [1, 2, 3]
+
+growing = [1, 2]
+for item in growing:
+    if item < 5:
+        growing.append(item + 2)
+assert growing == [1, 2, 3, 4, 5, 6]
+
+big = range(1000000000)
+for i in big:
+    if i == 3:
+        break
+assert i == 3
+assert next(iter(big)) == 0
+
+d = {"a": 1, "b": 2}
+keys = iter(d)
+assert next(keys) == "a"
+assert list(d.items()) == [("a", 1), ("b", 2)]
+assert list(iter(d.values())) == [1, 2]
+s = iter({7})
+assert next(s) == 7
+assert next(s, "empty") == "empty"
+
+stream = iter(Countdown(3))
+assert next(stream) == 3
+assert list(stream) == [2, 1]
+assert next(stream, "exhausted") == "exhausted"
+
+
+class Color(type):
+    def __iter__(cls):
+        return iter(cls.members)
+
+
+class Palette(metaclass=Color):
+    members = ["red", "green"]
+
+
+assert list(Palette) == ["red", "green"]
+assert [color.upper() for color in Palette] == ["RED", "GREEN"]
+a, b = Palette
+assert (a, b) == ("red", "green")
@@ -0,0 +1,181 @@
+l = [3, 1, 2]
+alias = l
+l.append(4)
+assert alias == [3, 1, 2, 4]
+l.extend((5, 6))
+l.extend(l)
+assert alias == [3, 1, 2, 4, 5, 6, 3, 1, 2, 4, 5, 6]
+l.clear()
+assert alias == []
+assert len(l) == 0
+
+l = [1, 2, 3]
+l.insert(0, 0)
+l.insert(-1, 9)
+l.insert(100, 7)
+assert l == [0, 1, 2, 9, 3, 7]
+assert l.pop() == 7
+assert l.pop(0) == 0
+assert l.pop(-2) == 9
+assert l == [1, 2, 3]
+l.remove(2)
+assert l == [1, 3]
+l.reverse()
+assert l == [3, 1]
+
+m = [1, 2, 1, 3, 1]
+assert m.count(1) == 3
+assert m.index(1) == 0
+assert m.index(1, 1) == 2
+assert m.index(1, -1) == 4
+assert 3 in m
+assert 4 not in m
+c = m.copy()
+c.append(0)
+assert m == [1, 2, 1, 3, 1]
+assert c == [1, 2, 1, 3, 1, 0]
+
+s = [5, 2, 8, 1, 9, 3]
+s.sort()
+assert s == [1, 2, 3, 5, 8, 9]
+s.sort(reverse=True)
+assert s == [9, 8, 5, 3, 2, 1]
+words = ["ccc", "a", "bb", "dd", "e"]
+words.sort(key=len)
+assert words == ["a", "e", "bb", "dd", "ccc"]
+words.sort(key=len, reverse=True)
+assert words == ["ccc", "bb", "dd", "a", "e"]
+pairs = [(2, "b"), (1, "z"), (2, "a"), (1, "y")]
+pairs.sort(key=lambda p: p[0])
+assert pairs == [(1, "z"), (1, "y"), (2, "b"), (2, "a")]
+pairs.sort()
+assert pairs == [(1, "y"), (1, "z"), (2, "a"), (2, "b")]
+big = []
+n = 0
+while n < 1500:
+    big.append((n * 7919) % 1009)
+    n += 1
+big.sort()
+prev = -1
+for x in big:
+    assert prev <= x
+    prev = x
+assert len(big) == 1500
+
+assert [x * 2 for x in [1, 2, 3]] == [2, 4, 6]
+assert [1, 2] + [3] == [1, 2, 3]
+assert [0] * 3 == [0, 0, 0]
+assert 2 * [1, 2] == [1, 2, 1, 2]
+assert [1] * -1 == []
+a = [1]
+b = a
+a += (2, 3)
+assert b == [1, 2, 3]
+a *= 2
+assert b == [1, 2, 3, 1, 2, 3]
+a = a + [4]
+assert b == [1, 2, 3, 1, 2, 3]
+
+assert [1, 2] < [1, 3]
+assert [1, 2] < [1, 2, 0]
+assert [2] > [1, 9]
+assert [1, 2] <= [1, 2]
+assert [1, 2] != [2, 1]
+assert [[1], [2]] == [[1], [2]]
+
+
+def keyed(a, b=1):
+    return [a, b]
+
+
+assert keyed(1, b=3) == [1, 3]
+assert keyed(b=2, a=4) == [4, 2]
+try:
+    keyed(1, 2, b=3)
+    assert False
+except TypeError as e:
+    assert str(e) == "keyed() got multiple values for argument 'b'"
+try:
+    keyed(1, a=2)
+    assert False
+except TypeError as e:
+    assert str(e) == "keyed() got multiple values for argument 'a'"
+
+
+def gather(first, *rest, sep=", ", **options):
+    return [first, list(rest), sep, options]
+
+
+assert gather(1) == [1, [], ", ", {}]
+assert gather(1, *[2, 3], sep="-", **{"end": "."}) == [1, [2, 3], "-", {"end": "."}]
+assert gather(*(1, 2)) == [1, [2], ", ", {}]
+try:
+    gather(1, end=1, **{"end": 2})
+    assert False
+except TypeError as e:
+    assert str(e) == "__main__.gather() got multiple values for keyword argument 'end'"
+try:
+    gather(1, **[2])
+    assert False
+except TypeError as e:
+    assert str(e) == "__main__.gather() argument after ** must be a mapping, not list"
+
+
+def only(a, *, b, c=3):
+    return [a, b, c]
+
+
+assert only(1, b=2) == [1, 2, 3]
+try:
+    only(1, 2)
+    assert False
+except TypeError as e:
+    assert str(e) == "only() takes 1 positional argument but 2 were given"
+try:
+    only(1)
+    assert False
+except TypeError as e:
+    assert str(e) == "only() missing 1 required keyword-only argument: 'b'"
+
+grown = [1, 2]
+
+
+class Grower:
+    def __eq__(self, other):
+        if len(grown) < 5:
+            grown.append(len(grown) + 1)
+        return other == 5
+
+
+assert Grower() in grown
+assert grown == [1, 2, 3, 4, 5]
+grown.append(Grower())
+assert 7 not in grown
+
+shrunk = [1, 2, 3]
+
+
+class Shrinker:
+    def __eq__(self, other):
+        shrunk.clear()
+        return True
+
+
+assert [Shrinker(), 2, 3] != shrunk
+assert shrunk == []
+shrunk.extend([Shrinker(), 5])
+assert shrunk < [0, 0]
+assert shrunk == []
+
+shown = []
+
+
+class Appender:
+    def __repr__(self):
+        shown.append(len(shown))
+        return "Appender"
+
+
+shown.append(Appender())
+assert repr(shown) == "[Appender, 1]"
+assert str((shown,)) == "([Appender, 1, 2],)"
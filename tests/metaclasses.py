@@ -0,0 +1,216 @@
+class Registry(type):
+    classes = []
+
+    def __new__(mcs, name, bases, ns, *, fmt=None, **kw):
+        ns["registered"] = True
+        cls = super().__new__(mcs, name, bases, ns, **kw)
+        Registry.classes.append(name)
+        return cls
+
+    def __init__(cls, name, bases, ns, *, fmt=None, **kw):
+        super().__init__(name, bases, ns, **kw)
+        cls.fmt = fmt
+
+
+class Plugin(metaclass=Registry):
+    pass
+
+
+class Exporter(Plugin, fmt="csv"):
+    def export(self):
+        return "exporting " + self.fmt
+
+
+assert Registry.classes == ["Plugin", "Exporter"]
+assert Exporter.registered
+assert Exporter().export() == "exporting csv"
+assert type(Exporter) is Registry
+assert type(Registry) is type
+assert isinstance(Exporter, Registry)
+assert isinstance(Exporter, type)
+assert repr(Exporter.__class__) == "<class '__main__.Registry'>"
+
+
+class Ordered(type):
+    @classmethod
+    def __prepare__(mcs, name, bases):
+        ns = {}
+        ns["prepared_for"] = name
+        return ns
+
+
+class Point(metaclass=Ordered):
+    x = 1
+    y = 2
+
+
+assert Point.prepared_for == "Point"
+assert [key for key in Point.__dict__ if not key.startswith("__")] == ["prepared_for", "x", "y"]
+
+
+class Singleton(type):
+    instances = {}
+
+    def __call__(cls, path):
+        if cls not in Singleton.instances:
+            Singleton.instances[cls] = super().__call__(path)
+        return Singleton.instances[cls]
+
+
+loaded = []
+
+
+class Config(metaclass=Singleton):
+    def __init__(self, path):
+        loaded.append(path)
+        self.path = path
+
+
+a = Config("a.ini")
+b = Config("b.ini")
+assert a is b
+assert b.path == "a.ini"
+assert loaded == ["a.ini"]
+
+
+class EnumMeta(type):
+    def __new__(mcs, name, bases, ns):
+        cls = super().__new__(mcs, name, bases, ns)
+        cls._members = [key for key in ns if not key.startswith("_")]
+        return cls
+
+    def __len__(cls):
+        return len(cls._members)
+
+    def __contains__(cls, name):
+        return name in cls._members
+
+    def __getitem__(cls, name):
+        return cls.__dict__[name]
+
+    def __repr__(cls):
+        return "<enum " + cls.__name__ + ">"
+
+
+class Color(metaclass=EnumMeta):
+    RED = 1
+    GREEN = 2
+    BLUE = 3
+
+
+assert len(Color) == 3
+assert "RED" in Color
+assert "PINK" not in Color
+assert Color["GREEN"] == 2
+assert repr(Color) == "<enum Color>"
+
+
+class Base:
+    subclasses = []
+
+    def __init_subclass__(cls, prefix=""):
+        super().__init_subclass__()
+        cls.tag = prefix + cls.__name__
+        Base.subclasses.append(cls.tag)
+
+
+class Child(Base, prefix="my_"):
+    pass
+
+
+class Grandchild(Child):
+    pass
+
+
+assert Base.subclasses == ["my_Child", "Grandchild"]
+assert Child.tag == "my_Child"
+assert Grandchild.tag == "Grandchild"
+
+
+class Tagged:
+    def __init_subclass__(cls, *, tags=(), **kwargs):
+        super().__init_subclass__(**kwargs)
+        cls.tags = tags
+
+
+class Options(Tagged, Base):
+    pass
+
+
+options = {"prefix": "opt_", "tags": ("a", "b")}
+
+
+class Configured(Options, metaclass=Registry, fmt="json", **options):
+    pass
+
+
+assert Configured.tags == ("a", "b")
+assert Configured.tag == "opt_Configured"
+assert Configured.fmt == "json"
+assert Registry.classes[-1] == "Configured"
+try:
+    class Twice(Options, prefix="x", **options):
+        pass
+    assert False
+except TypeError as e:
+    assert str(e) == "__build_class__() got multiple values for keyword argument 'prefix'"
+
+
+class Pair:
+    def __new__(cls, *args):
+        self = super().__new__(cls)
+        self.args = args
+        return self
+
+    def __init__(self, *args, **kwargs):
+        self.kwargs = kwargs
+
+
+pair = Pair(1, *[2, 3])
+assert pair.args == (1, 2, 3)
+assert pair.kwargs == {}
+pair = Pair(*(), **{})
+assert pair.args == ()
+
+
+class Box:
+    def __class_getitem__(cls, item):
+        return cls.__name__ + "[" + item.__name__ + "]"
+
+
+assert Box[Plugin] == "Box[Plugin]"
+assert Box[Point] == "Box[Point]"
+
+
+def add_greeting(cls):
+    cls.greet = lambda self: "hello from " + type(self).__name__
+    return cls
+
+
+def register(name):
+    def decorator(cls):
+        cls.registered_as = name
+        return cls
+    return decorator
+
+
+@register("widget")
+@add_greeting
+class Widget:
+    pass
+
+
+assert Widget().greet() == "hello from Widget"
+assert Widget.registered_as == "widget"
+
+Dynamic = type("Dynamic", (Base,), {"value": 42, "double": lambda self: self.value * 2})
+assert Dynamic.__name__ == "Dynamic"
+assert Dynamic().double() == 84
+assert Dynamic.tag == "Dynamic"
+assert Dynamic.__mro__ == (Dynamic, Base, object)
+assert type(1) is type(2)
+assert repr(type("a")) == "<class 'str'>"
+assert repr(type(None)) == "<class 'NoneType'>"
+assert type(Widget()) is Widget
+assert isinstance(3, type(4))
+assert type(type) is type
@@ -0,0 +1,109 @@
+xs = [3, 1]
+xs.append(2)
+xs.sort()
+assert xs == [1, 2, 3]
+assert "abc".upper() == "ABC"
+assert "-".join(["a", "b"]) == "a-b"
+d = {"k": 1}
+assert d.get("k") == 1 and d.get("x", 0) == 0
+assert dict.fromkeys("ab", 0) == {"a": 0, "b": 0}
+append = xs.append
+append(4)
+assert xs == [1, 2, 3, 4]
+
+
+class Greeter:
+    def __init__(self, name):
+        self.name = name
+
+    def greet(self, greeting="Hello", punct="!"):
+        return greeting + ", " + self.name + punct
+
+    @staticmethod
+    def static(x):
+        return x + 1
+
+    @classmethod
+    def create(cls, name):
+        return cls(name)
+
+
+g = Greeter("Ann")
+assert g.greet() == "Hello, Ann!"
+assert g.greet("Hi") == "Hi, Ann!"
+assert g.greet(punct="?") == "Hello, Ann?"
+assert g.greet("Hey", punct=".") == "Hey, Ann."
+assert Greeter.greet(g, "Yo") == "Yo, Ann!"
+assert g.static(1) == 2
+assert Greeter.create("Bob").greet() == "Hello, Bob!"
+assert g.create("Cy").name == "Cy"
+
+bound = g.greet
+g.name = "Dee"
+assert bound() == "Hello, Dee!"
+
+g.greet = lambda: "shadowed"
+assert g.greet() == "shadowed"
+del g.greet
+assert g.greet() == "Hello, Dee!"
+
+
+class Callback:
+    def __init__(self):
+        self.handler = len
+
+    def run(self):
+        return self.handler("abc")
+
+
+assert Callback().run() == 3
+
+
+class Traced:
+    def __init__(self):
+        self.calls = 0
+
+    def __getattribute__(self, name):
+        if name == "ping":
+            object.__setattr__(self, "calls", object.__getattribute__(self, "calls") + 1)
+        return object.__getattribute__(self, name)
+
+    def ping(self):
+        return "pong"
+
+
+t = Traced()
+assert t.ping() == "pong"
+assert t.ping() == "pong"
+assert t.calls == 2
+
+
+class Chain:
+    def __init__(self):
+        self.items = []
+
+    def add(self, item):
+        self.items.append(item)
+        return self
+
+
+assert Chain().add(1).add(2).items == [1, 2]
+
+
+class Base:
+    def describe(self):
+        return "base " + self.kind()
+
+    def kind(self):
+        return "base"
+
+
+class Child(Base):
+    def kind(self):
+        return "child"
+
+    def describe(self):
+        return super().describe() + "!"
+
+
+assert Child().describe() == "base child!"
@@ -0,0 +1,145 @@
+class Vec:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+    def __add__(self, other):
+        if isinstance(other, Vec):
+            return Vec(self.x + other.x, self.y + other.y)
+        if other == 0:
+            return self
+        return NotImplemented
+
+    def __radd__(self, other):
+        return self.__add__(other)
+
+    def __sub__(self, other):
+        return Vec(self.x - other.x, self.y - other.y)
+
+    def __mul__(self, k):
+        return Vec(self.x * k, self.y * k)
+
+    def __rmul__(self, k):
+        return Vec(self.x * k, self.y * k)
+
+    def __matmul__(self, other):
+        return self.x * other.x + self.y * other.y
+
+    def __neg__(self):
+        return Vec(-self.x, -self.y)
+
+    def __pos__(self):
+        return self
+
+    def __invert__(self):
+        return Vec(self.y, self.x)
+
+    def __eq__(self, other):
+        return isinstance(other, Vec) and self.x == other.x and self.y == other.y
+
+    def __repr__(self):
+        return "Vec(" + repr(self.x) + ", " + repr(self.y) + ")"
+
+
+a = Vec(1, 2)
+b = Vec(3, 4)
+assert a + b == Vec(4, 6)
+assert b - a == Vec(2, 2)
+assert a * 3 == Vec(3, 6)
+assert 3 * a == Vec(3, 6)
+assert a @ b == 11
+assert -a == Vec(-1, -2)
+assert +a is a
+assert ~a == Vec(2, 1)
+assert a + 0 is a
+assert 0 + a is a
+total = 0
+for v in [a, b]:
+    total = total + v
+assert total == Vec(4, 6)
+
+
+class Acc:
+    def __init__(self):
+        self.items = []
+
+    def __iadd__(self, item):
+        self.items.append(item)
+        return self
+
+
+acc = Acc()
+alias = acc
+acc += 1
+acc += 2
+assert acc is alias
+assert acc.items == [1, 2]
+
+
+class OnlyAdd:
+    def __init__(self, v):
+        self.v = v
+
+    def __add__(self, other):
+        return OnlyAdd(self.v + other)
+
+
+o = OnlyAdd(1)
+before = o
+o += 5
+assert o.v == 6
+assert o is not before
+
+
+class Meters:
+    def __init__(self, v):
+        self.v = v
+
+    def __add__(self, other):
+        return "Meters.__add__"
+
+    def __radd__(self, other):
+        return "Meters.__radd__"
+
+
+class Kilometers(Meters):
+    def __radd__(self, other):
+        return "Kilometers.__radd__"
+
+
+assert Meters(1) + Meters(2) == "Meters.__add__"
+assert Meters(1) + Kilometers(2) == "Kilometers.__radd__"
+assert 1 + Meters(1) == "Meters.__radd__"
+
+
+class Digits:
+    def __contains__(self, item):
+        return item in "0123456789"
+
+
+assert "7" in Digits()
+assert "x" not in Digits()
+
+
+class Num:
+    def __init__(self, v):
+        self.v = v
+
+    def __lt__(self, other):
+        return self.v < other.v
+
+    def __le__(self, other):
+        return self.v <= other.v
+
+    def __eq__(self, other):
+        return self.v == other.v
+
+    def __hash__(self):
+        return hash(self.v)
+
+
+assert Num(1) < Num(2) <= Num(2)
+assert Num(2) > Num(1)
+assert Num(2) >= Num(2)
+assert Num(1) != Num(2)
+assert len({Num(1), Num(1), Num(2)}) == 2
@@ -0,0 +1,63 @@
+def depth(n):
+    return 0 if n == 0 else 1 + depth(n - 1)
+
+
+assert depth(400) == 400
+assert depth(900) == 900
+
+
+def forever():
+    return forever()
+
+
+try:
+    forever()
+    assert False
+except RecursionError as e:
+    assert str(e) == "maximum recursion depth exceeded"
+
+try:
+    forever()
+    assert False
+except RuntimeError:
+    pass
+
+
+class Endless:
+    def __repr__(self):
+        return repr(self)
+
+
+try:
+    repr(Endless())
+    assert False
+except RecursionError:
+    pass
+
+
+def nested(n):
+    if n:
+        yield from nested(n - 1)
+    yield n
+
+
+assert list(nested(300)) == list(range(301))
+assert depth(900) == 900
+
+deep = []
+other = []
+for _ in range(150000):
+    deep = [deep]
+    other = [other]
+try:
+    deep == other
+    assert False
+except RecursionError as e:
+    assert str(e) == "maximum recursion depth exceeded in comparison"
+try:
+    repr(deep)
+    assert False
+except RecursionError as e:
+    assert str(e) == "maximum recursion depth exceeded while getting the repr of an object"
+assert [[[1]]] == [[[1]]]
+assert repr([[[1]]]) == "[[[1]]]"
@@ -0,0 +1,66 @@
+class Base:
+    def __add__(self, other):
+        return "Base.add"
+
+    def __radd__(self, other):
+        return "Base.radd"
+
+    def __sub__(self, other):
+        return "Base.sub"
+
+    def __rsub__(self, other):
+        return "Base.rsub"
+
+
+class Overriding(Base):
+    def __radd__(self, other):
+        return "Overriding.radd"
+
+
+class Inheriting(Base):
+    pass
+
+
+# a subclass goes first only when it overrides the reflected method
+assert Base() + Overriding() == "Overriding.radd"
+assert Base() - Overriding() == "Base.sub"
+assert Base() + Inheriting() == "Base.add"
+assert Overriding() + Base() == "Base.add"
+assert 1 + Base() == "Base.radd"
+assert Base() + 1 == "Base.add"
+
+
+class Declining:
+    def __add__(self, other):
+        return NotImplemented
+
+    def __radd__(self, other):
+        return "Declining.radd"
+
+
+class Accepting:
+    def __radd__(self, other):
+        return "Accepting.radd"
+
+
+assert Declining() + Accepting() == "Accepting.radd"
+try:
+    Declining() + Declining()
+    assert False
+except TypeError as e:
+    assert str(e) == "unsupported operand type(s) for +: 'Declining' and 'Declining'"
+
+
+def make_class():
+    class Value:
+        def __add__(self, other):
+            return NotImplemented
+
+        def __radd__(self, other):
+            return "reflected"
+    return Value
+
+
+# classes sharing a name are still different types
+First, Second = make_class(), make_class()
+assert First() + Second() == "reflected"
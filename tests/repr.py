@@ -0,0 +1,32 @@
+assert repr("a") == "'a'"
+assert str("a") == "a"
+assert repr("it's") == '"it\'s"'
+assert repr("both ' and \"") == "'both \\' and \"'"
+assert repr("tab\there\n") == "'tab\\there\\n'"
+assert repr("\x00\x7f") == "'\\x00\\x7f'"
+assert repr("é ") == "'é\\u2028'"
+assert repr("\u200b\u3000\xa0\u0378\ue000") == "'\\u200b\\u3000\\xa0\\u0378\\ue000'"
+assert repr("日本\U0001f600") == "'日本\U0001f600'"
+assert repr(b"a'\x00") == 'b"a\'\\x00"'
+assert str(b"x") == "b'x'"
+
+assert repr(["a", 1, None, True]) == "['a', 1, None, True]"
+assert str(["a"]) == "['a']"
+assert repr(("a",)) == "('a',)"
+assert repr(()) == "()"
+assert repr((1, ("c", "d"))) == "(1, ('c', 'd'))"
+assert repr(1) == "1"
+assert repr(None) == "None"
+
+
+def foo():
+    pass
+
+
+assert repr(foo).startswith("<function foo at 0x")
+assert repr(print) == "<built-in function print>"
+assert repr("abc".upper).startswith("<built-in method upper of str object at 0x")
+assert repr(ValueError("bad")) == "ValueError('bad')"
+assert str(ValueError("bad")) == "bad"
+assert repr(ValueError("bad", 1)) == "ValueError('bad', 1)"
+assert repr(ValueError()) == "ValueError()"
@@ -0,0 +1,150 @@
+s = {3, 1, 2}
+assert repr(s) == "{1, 2, 3}"
+assert len(s) == 3
+assert 2 in s
+assert 5 not in s
+s.add(4)
+s.add(4)
+assert repr(s) == "{1, 2, 3, 4}"
+s.discard(10)
+s.remove(1)
+assert repr(s) == "{2, 3, 4}"
+assert s.pop() == 2
+assert s.pop() == 3
+s.clear()
+assert repr(s) == "set()"
+
+a = {1, 2, 3, 4}
+b = {3, 4, 5}
+assert repr(a | b) == "{1, 2, 3, 4, 5}"
+assert repr(a & b) == "{3, 4}"
+assert repr(a - b) == "{1, 2}"
+assert repr(a ^ b) == "{1, 2, 5}"
+assert repr(a.union([9], (8,))) == "{1, 2, 3, 4, 8, 9}"
+assert repr(a.difference([1], [2])) == "{3, 4}"
+assert repr(a.symmetric_difference([4, 6])) == "{1, 2, 3, 6}"
+
+c = {1, 2}
+c |= {7}
+assert repr(c) == "{1, 2, 7}"
+c &= {1, 7, 8}
+assert repr(c) == "{1, 7}"
+c -= {1}
+assert repr(c) == "{7}"
+c ^= {7, 8}
+assert repr(c) == "{8}"
+d = c
+d |= {9}
+assert repr(c) == "{8, 9}"
+
+e = {1, 2, 3}
+e.update([4], {5})
+e.difference_update([1, 2])
+e.intersection_update({3, 4, 5, 6})
+e.symmetric_difference_update([5, 10])
+assert repr(e) == "{10, 3, 4}"
+
+assert {1, 2} <= {1, 2}
+assert {1, 2} < {1, 2, 3}
+assert not {1, 2} < {1, 2}
+assert {1, 2, 3} > {1}
+assert {1, 2} == {2, 1}
+assert {1, 2} != {1, 3}
+assert {1}.issubset([1, 2])
+assert {1, 2}.issuperset([2])
+assert {1}.isdisjoint([2, 3])
+assert not {1}.isdisjoint({1})
+
+f = frozenset([1, 2, 3])
+assert repr(f) == "frozenset({1, 2, 3})"
+assert repr(frozenset()) == "frozenset()"
+assert f == {1, 2, 3}
+assert hash(f) == hash(frozenset([3, 2, 1]))
+assert repr(f | {4}) == "frozenset({1, 2, 3, 4})"
+assert repr({4} | f) == "{1, 2, 3, 4}"
+g = f
+g |= {5}
+assert repr(f) == "frozenset({1, 2, 3})"
+table = {f: "frozen"}
+assert table[frozenset({1, 2, 3})] == "frozen"
+assert {1, 2} in {frozenset({1, 2})}
+
+words = set("hello")
+assert len(words) == 4
+assert "h" in words
+assert set({"a": 1, "b": 2}) == {"a", "b"}
+
+squares = {x * x for x in [1, 2, 3, 2]}
+assert squares == {1, 4, 9}
+merged = {*a, *b}
+assert merged == {1, 2, 3, 4, 5}
+
+big = set()
+for i in [5, 100, 7, 42, 0, 64, 33, 8, 16, 1000]:
+    big.add(i)
+assert repr(big) == "{0, 64, 33, 100, 5, 7, 8, 1000, 42, 16}"
+assert repr({5, 100, 7, 42, 0, 64, 33, 8, 16, 1000}) == "{0, 33, 64, 100, 5, 7, 8, 1000, 42, 16}"
+
+reused = {0, 1, 2, 3, 4}
+reused.remove(1)
+reused.add(17)
+assert repr(reused) == "{0, 17, 2, 3, 4}"
+churned = set()
+seed = 1
+for step in range(60):
+    seed = (seed * 75 + 74) % 65537
+    if seed % 7 < 3:
+        churned.discard(seed % 50 * 9)
+    else:
+        churned.add(seed % 50 * 9)
+assert repr(churned) == "{0, 387, 261, 9, 396, 270, 144, 27, 288, 162, 36, 171, 45, 432, 306, 315, 189, 63, 198, 207, 342, 216, 360, 243, 117, 252}"
+
+watched = set()
+
+
+class Watcher:
+    def __init__(self, n):
+        self.n = n
+
+    def __hash__(self):
+        return 1
+
+    def __eq__(self, other):
+        if not isinstance(other, Watcher):
+            return False
+        watched.add(self.n + 100)
+        return self.n == other.n
+
+
+first = Watcher(1)
+watched.add(first)
+watched.add(Watcher(1))
+assert len(watched) == 2
+assert Watcher(1) in watched
+assert Watcher(2) not in watched
+watched.discard(Watcher(1))
+assert first not in watched
+try:
+    watched.remove(Watcher(3))
+    assert False
+except KeyError:
+    pass
+assert watched == {101}
+
+cleared = set()
+
+
+class Clearer:
+    def __hash__(self):
+        return 7
+
+    def __eq__(self, other):
+        cleared.clear()
+        return False
+
+
+cleared.add(Clearer())
+cleared.add(Clearer())
+assert len(cleared) == 1
+assert Clearer() not in cleared
+assert len(cleared) == 0
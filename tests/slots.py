@@ -0,0 +1,128 @@
+class Point:
+    __slots__ = ("x", "y")
+
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+    def norm2(self):
+        return self.x * self.x + self.y * self.y
+
+
+p = Point(3, 4)
+assert p.x == 3
+assert p.y == 4
+assert p.norm2() == 25
+p.x = 6
+assert p.x == 6
+assert repr(Point.x) == "<member 'x' of 'Point' objects>"
+assert Point.__slots__ == ("x", "y")
+assert "__dict__" not in Point.__dict__
+del p.y
+assert "y" in Point.__dict__
+assert repr(type(Point.y)) == "<class 'member_descriptor'>"
+
+
+class Point3D(Point):
+    __slots__ = "z"
+
+    def __init__(self, x, y, z):
+        super().__init__(x, y)
+        self.z = z
+
+
+q = Point3D(1, 2, 3)
+assert (q.x, q.y, q.z) == (1, 2, 3)
+assert q.norm2() == 5
+assert repr(Point3D.z) == "<member 'z' of 'Point3D' objects>"
+
+
+class Tagged(Point):
+    pass
+
+
+t = Tagged(1, 1)
+t.label = "free"
+assert t.label == "free"
+assert t.__dict__ == {"label": "free"}
+assert t.x == 1
+
+
+class WithDict:
+    __slots__ = ("a", "__dict__")
+
+
+w = WithDict()
+w.a = 1
+w.b = 2
+assert w.a == 1
+assert w.__dict__ == {"b": 2}
+
+
+class Private:
+    __slots__ = ("__secret", "public")
+
+    def __init__(self):
+        self.__secret = 42
+        self.public = "hi"
+
+    def reveal(self):
+        return self.__secret
+
+
+r = Private()
+assert r.reveal() == 42
+assert r._Private__secret == 42
+assert "_Private__secret" in Private.__dict__
+
+
+class Named:
+    __slots__ = "name"
+
+
+named = Named()
+named.name = 5
+assert named.name == 5
+assert Named.__slots__ == "name"
+assert "n" not in Named.__dict__
+try:
+    named.other = 1
+    assert False
+except AttributeError:
+    pass
+
+
+class Empty:
+    __slots__ = ()
+
+
+class Mixin:
+    __slots__ = ()
+
+
+class Combined(Point, Mixin):
+    __slots__ = ("w",)
+
+
+c = Combined(5, 12)
+c.w = 0
+assert c.norm2() == 169
+assert c.w == 0
+
+
+class Validated:
+    __slots__ = ("_value",)
+
+    @property
+    def value(self):
+        return self._value
+
+    @value.setter
+    def value(self, value):
+        self._value = value * 2
+
+
+v = Validated()
+v.value = 21
+assert v.value == 42
+
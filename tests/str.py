@@ -0,0 +1,78 @@
+s = "héllo wörld"
+assert len(s) == 11
+assert s[1] == "é"
+assert s[-1] == "d"
+assert s[1:4] == "éll"
+assert s[::-1] == "dlröw olléh"
+assert s[::2] == "hlowrd"
+assert s[-5:] == "wörld"
+assert s[100:] == ""
+
+assert "ab" + "cd" == "abcd"
+assert "ab" * 3 == "ababab"
+assert 2 * "é" == "éé"
+assert "wör" in s
+assert "xyz" not in s
+assert "abc" < "abd"
+assert "é" > "z"
+assert "abc" != "abd"
+
+assert "|".join("a,b,,c".split(",")) == 'a|b||c'
+assert "|".join("  a  b c  ".split()) == 'a|b|c'
+assert "|".join("  a  b c  ".split(None, 1)) == 'a|b c  '
+assert "|".join("a,b,c".rsplit(",", 1)) == 'a,b|c'
+assert "|".join("  a  b c  ".rsplit(None, 1)) == '  a  b|c'
+assert "-".join(["x", "y", "z"]) == "x-y-z"
+assert "".join(("a", "b")) == "ab"
+assert "  pad  ".strip() == "pad"
+assert "xxpadxx".lstrip("x") == "padxx"
+assert "xxpadxx".rstrip("x") == "xxpad"
+assert "aaa".replace("a", "b", 2) == "bba"
+assert "ab".replace("", "-") == "-a-b-"
+assert s.find("ö") == 7
+assert s.find("l", 4) == 9
+assert s.rfind("l") == 9
+assert s.find("zz") == -1
+assert s.index("w") == 6
+assert "banana".count("a") == 3
+assert "banana".count("an", 2) == 1
+assert s.startswith("hé")
+assert s.startswith(("x", "wö"), 6)
+assert s.endswith("rld")
+assert s.upper() == "HÉLLO WÖRLD"
+assert "ABC".lower() == "abc"
+assert "hello world2x".title() == "Hello World2X"
+assert "Straße".casefold() == "strasse"
+assert "hELLO".capitalize() == "Hello"
+assert "hELLO".swapcase() == "Hello"
+assert "123".isdigit()
+assert not "12a".isdigit()
+assert "abé".isalpha()
+assert "ab1".isalnum()
+assert " \t".isspace()
+assert not "".isalpha()
+assert "ABC1".isupper()
+assert "abc1".islower()
+assert "Hello World".istitle()
+assert "|".join("a=b=c".partition("=")) == 'a|=|b=c'
+assert "|".join("a=b=c".rpartition("=")) == 'a=b|=|c'
+assert "|".join("abc".partition("x")) == 'abc||'
+assert "|".join("a\nb\r\nc".splitlines()) == 'a|b|c'
+assert "|".join("a\nb\r\n".splitlines(True)) == 'a\n|b\r\n'
+assert "42".zfill(5) == "00042"
+assert "-42".zfill(5) == "-0042"
+assert "abc".center(6, "*") == "*abc**"
+assert "abc".center(8) == "  abc   "
+assert "ab".ljust(4, ".") == "ab.."
+assert "ab".rjust(4) == "  ab"
+assert "é".encode() == b"\xc3\xa9"
+assert b"\xc3\xa9".decode() == "é"
+
+upper = s.upper
+assert upper() == "HÉLLO WÖRLD"
+
+try:
+    "a" + 1
+    assert False
+except TypeError as e:
+    assert str(e) == 'can only concatenate str (not "int") to str'
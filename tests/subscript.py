@@ -0,0 +1,70 @@
+s = "hello"
+assert s[0] == "h"
+assert s[-1] == "o"
+assert s[1:3] == "el"
+assert s[::-1] == "olleh"
+assert s[::2] == "hlo"
+assert s[10:] == ""
+
+b = b"abc"
+assert b[0] == 97
+assert b[-1] == 99
+assert b[1:] == b"bc"
+
+t = (1, 2, 3, 4)
+assert t[-2] == 3
+assert t[1::2] == (2, 4)
+
+l = [0, 1, 2, 3, 4, 5]
+assert l[0] == 0
+assert l[-1] == 5
+assert l[2:4] == [2, 3]
+assert l[:-4] == [0, 1]
+assert l[::2] == [0, 2, 4]
+assert l[::-2] == [5, 3, 1]
+assert l[4:1:-1] == [4, 3, 2]
+assert l[100:] == []
+i = 1
+j = 3
+assert l[i:j] == [1, 2]
+assert l[i:] == [1, 2, 3, 4, 5]
+
+l[0] = 10
+l[-1] = 50
+assert l == [10, 1, 2, 3, 4, 50]
+l[1:3] = [7]
+assert l == [10, 7, 3, 4, 50]
+l[1:1] = (8, 9)
+assert l == [10, 8, 9, 7, 3, 4, 50]
+l[::2] = [0, 0, 0, 0]
+assert l == [0, 8, 0, 7, 0, 4, 0]
+l[:] = "ab"
+assert l == ["a", "b"]
+l[:] = l
+assert l == ["a", "b"]
+l[len(l):] = l
+assert l == ["a", "b", "a", "b"]
+
+m = [0, 1, 2, 3, 4, 5, 6]
+del m[0]
+assert m == [1, 2, 3, 4, 5, 6]
+del m[-1]
+assert m == [1, 2, 3, 4, 5]
+del m[1:3]
+assert m == [1, 4, 5]
+n = [0, 1, 2, 3, 4, 5, 6]
+del n[::2]
+assert n == [1, 3, 5]
+del n[::-1]
+assert n == []
+
+d = {"a": 1}
+d["b"] = 2
+assert d["a"] + d["b"] == 3
+del d["a"]
+assert d == {"b": 2}
+
+grid = [[1, 2], [3, 4]]
+grid[1][0] = 9
+assert grid[1] == [9, 4]
+assert grid[-1][-1] == 4
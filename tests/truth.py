@@ -0,0 +1,62 @@
+def truth(x):
+    if x:
+        return True
+    return False
+
+assert truth(1)
+assert not truth(0)
+assert truth(-3)
+assert not truth("")
+assert truth("a")
+assert not truth(b"")
+assert not truth([])
+assert truth([0])
+assert not truth(())
+assert truth((None,))
+assert not truth({})
+assert truth({0: 0})
+assert not truth(set())
+assert truth({0})
+assert not truth(None)
+assert truth(True)
+assert not truth(False)
+assert not truth({}.keys())
+assert truth(truth)
+
+assert not 0
+assert not ""
+assert not None
+assert not not [1]
+assert bool([1]) == True
+assert bool(0) == False
+assert bool() == False
+
+items = [1, 2, 3]
+total = 0
+while items:
+    total += items.pop()
+assert total == 6
+
+zero = 0
+one = 1
+empty = ""
+x = "x"
+r = zero or x
+assert r == "x"
+r = one and x
+assert r == "x"
+r = empty and one
+assert r == ""
+r = None or zero or 5
+assert r == 5
+r = one and zero and x
+assert r == 0
+r = one or missing
+assert r == 1
+a = 0
+b = []
+if a or b:
+    assert False
+if not (a or b):
+    c = 1
+assert c == 1
@@ -0,0 +1,57 @@
+t = (1, 2, 3)
+assert len(t) == 3
+assert t[0] == 1
+assert t[-1] == 3
+assert t[1:] == (2, 3)
+assert t[::-1] == (3, 2, 1)
+assert t[5:] == ()
+assert 2 in t
+assert 4 not in t
+
+a = 1
+b = 2
+u = (a, b)
+assert u == (1, 2)
+assert repr(u) == "(1, 2)"
+assert repr((a,)) == "(1,)"
+assert repr(()) == "()"
+
+assert t + (4,) == (1, 2, 3, 4)
+assert u * 2 == (1, 2, 1, 2)
+assert 2 * u == (1, 2, 1, 2)
+assert u * 0 == ()
+v = u
+v += (3,)
+assert v == (1, 2, 3)
+assert u == (1, 2)
+
+assert (1, 2) < (1, 3)
+assert (1, 2) < (1, 2, 0)
+assert (1, 2, 0) > (1, 2)
+assert (2,) > (1, 9)
+assert (1, 2) <= (1, 2)
+assert (1, 2) != (2, 1)
+assert ("a", 1) < ("b", 0)
+
+w = (1, 2, 1, 3, 1)
+assert w.count(1) == 3
+assert w.count(5) == 0
+assert w.index(1) == 0
+assert w.index(1, 1) == 2
+assert w.index(1, -2) == 4
+assert w.index(3) == 3
+
+assert tuple() == ()
+assert tuple([1, 2]) == (1, 2)
+assert tuple("ab") == ("a", "b")
+assert tuple({7: 1}) == (7,)
+assert tuple(t) == t
+l = [3, 4]
+assert (*l, *t) == (3, 4, 1, 2, 3)
+
+assert hash((1, 2)) == hash((a, b))
+assert hash(()) == hash(tuple())
+d = {(1, 2): "x", (a, (b, 3)): "y"}
+assert d[(1, 2)] == "x"
+assert d[1, (2, 3)] == "y"
+assert (1, 2) in {u}
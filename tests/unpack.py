@@ -0,0 +1,51 @@
+pair = (1, 2)
+a, b = pair
+assert a == 1
+assert b == 2
+a, b = b, a
+assert (a, b) == (2, 1)
+assert 1 < a < 3
+assert not (1 < b < 3)
+x, y, z = [1, "two", 3.0]
+assert y == "two"
+c, d = "hi"
+assert c == "h"
+assert d == "i"
+e, f = {"k": 1, "j": 2}
+assert (e, f) == ("k", "j")
+g, h, i = range(3)
+assert g + h + i == 3
+j, k = {5}, b"xy"
+assert k == b"xy"
+(l, m), n = (1, 2), 3
+assert l + m + n == 6
+
+total = 0
+keys = ""
+for key, value in {"a": 1, "b": 2}.items():
+    keys += key
+    total += value
+assert keys == "ab"
+assert total == 3
+for p, (q, r) in [(1, (2, 3)), (4, (5, 6))]:
+    total += p * q * r
+assert total == 3 + 6 + 120
+
+first, *rest = [1, 2, 3, 4]
+assert first == 1
+assert rest == [2, 3, 4]
+*init, last = (1, 2, 3)
+assert init == [1, 2]
+assert last == 3
+head, *middle, tail = "abcde"
+assert middle == ["b", "c", "d"]
+assert head + tail == "ae"
+u, *v, w = 1, 2
+assert v == []
+s, *t = range(1)
+assert t == []
+*everything, = {1: 0, 2: 0}
+assert everything == [1, 2]
+o1, o2, *o3, o4, o5 = range(10)
+assert o3 == [2, 3, 4, 5, 6, 7]
+assert (o4, o5) == (8, 9)
@@ -0,0 +1,208 @@
+log = []
+
+
+class Resource:
+    def __init__(self, name, suppress=False):
+        self.name = name
+        self.suppress = suppress
+
+    def __enter__(self):
+        log.append(("enter", self.name))
+        return self
+
+    def __exit__(self, exc_type, exc, tb):
+        log.append(("exit", self.name, exc_type, str(exc) if exc is not None else None))
+        return self.suppress
+
+
+with Resource("plain") as r:
+    log.append(("body", r.name))
+assert log == [("enter", "plain"), ("body", "plain"), ("exit", "plain", None, None)]
+
+log = []
+with Resource("bare"):
+    pass
+assert log == [("enter", "bare"), ("exit", "bare", None, None)]
+
+
+def early_return():
+    with Resource("ret"):
+        return "returned"
+    return "unreachable"
+
+
+log = []
+assert early_return() == "returned"
+assert log == [("enter", "ret"), ("exit", "ret", None, None)]
+
+log = []
+for i in range(5):
+    with Resource(i):
+        if i == 1:
+            continue
+        if i == 2:
+            break
+assert log == [
+    ("enter", 0), ("exit", 0, None, None),
+    ("enter", 1), ("exit", 1, None, None),
+    ("enter", 2), ("exit", 2, None, None),
+]
+
+log = []
+with Resource("quiet", suppress=True):
+    raise ValueError("hidden")
+    log.append("unreachable")
+assert log == [("enter", "quiet"), ("exit", "quiet", ValueError, "hidden")]
+
+log = []
+try:
+    with Resource("loud"):
+        raise KeyError("shown")
+    assert False
+except KeyError as e:
+    log.append(("caught", str(e)))
+assert log == [("enter", "loud"), ("exit", "loud", KeyError, "'shown'"), ("caught", "'shown'")]
+
+
+def suppressed_in_function():
+    with Resource("fn", suppress=True):
+        1 / 0
+    return "after"
+
+
+log = []
+assert suppressed_in_function() == "after"
+assert log == [("enter", "fn"), ("exit", "fn", ZeroDivisionError, "division by zero")]
+
+log = []
+with Resource("outer"), Resource("inner", suppress=True):
+    raise RuntimeError("nested")
+assert log == [
+    ("enter", "outer"), ("enter", "inner"),
+    ("exit", "inner", RuntimeError, "nested"),
+    ("exit", "outer", None, None),
+]
+
+log = []
+try:
+    with Resource("outer"):
+        with Resource("inner"):
+            raise IndexError("deep")
+except IndexError:
+    log.append("propagated")
+assert log == [
+    ("enter", "outer"), ("enter", "inner"),
+    ("exit", "inner", IndexError, "deep"),
+    ("exit", "outer", IndexError, "deep"),
+    "propagated",
+]
+
+
+class Truthy:
+    def __enter__(self):
+        return "entered"
+
+    def __exit__(self, exc_type, exc, tb):
+        return 1
+
+
+with Truthy() as value:
+    assert value == "entered"
+    raise TypeError("swallowed by a truthy result")
+
+
+class FailingExit:
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc, tb):
+        raise AttributeError("from exit")
+
+
+try:
+    with FailingExit():
+        raise ValueError("original")
+except AttributeError as e:
+    assert str(e) == "from exit"
+
+try:
+    with FailingExit():
+        pass
+except AttributeError as e:
+    assert str(e) == "from exit"
+
+
+def in_generator():
+    with Resource("gen"):
+        yield 1
+        yield 2
+
+
+log = []
+g = in_generator()
+assert next(g) == 1
+g.close()
+assert log == [("enter", "gen"), ("exit", "gen", GeneratorExit, "")]
+
+log = []
+assert list(in_generator()) == [1, 2]
+assert log == [("enter", "gen"), ("exit", "gen", None, None)]
+
+
+def handled_again():
+    try:
+        with Resource("reraise"):
+            raise ValueError("first")
+    except ValueError:
+        try:
+            raise
+        except ValueError as e:
+            return str(e)
+
+
+log = []
+assert handled_again() == "first"
+
+
+class NoExit:
+    def __enter__(self):
+        return self
+
+
+try:
+    with NoExit():
+        pass
+    assert False
+except TypeError as e:
+    assert str(e) == "'NoExit' object does not support the context manager protocol (missed __exit__ method)"
+
+try:
+    with 42:
+        pass
+    assert False
+except TypeError as e:
+    assert str(e) == "'int' object does not support the context manager protocol"
+
+
+class AsyncResource:
+    async def __aenter__(self):
+        log.append("aenter")
+        return self
+
+    async def __aexit__(self, exc_type, exc, tb):
+        log.append(("aexit", exc_type))
+        return True
+
+
+async def use_async():
+    async with AsyncResource():
+        raise ValueError("in async with")
+    return "async done"
+
+
+log = []
+try:
+    use_async().send(None)
+except StopIteration as e:
+    assert e.value == "async done"
+assert log == ["aenter", ("aexit", ValueError)]